@@ -0,0 +1,249 @@
+/*
+Copyright 2024 Benjamin Richcreek
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+//! # Psuedo-Array Keys
+//! The key encoding used by [`structurray`](https://crates.io/crates/structurray), available at runtime.
+//!
+//! Procedural macro crates can only export macros, so this crate holds the functions that decide which key each slot of a psuedo-array is serialized under.
+//! The `faux_array` attribute uses these same functions while generating code, so keys computed with this crate always match the keys of generated fields.
+//! Application code and tooling can depend on this crate to compute keys without reimplementing (or depending directly on) the [Base62](https://en.wikipedia.org/wiki/Base62) encoding.
+use ascii_basing::encoding::encode;
+/// The digits used for keys, in order from least value (0 = 0) to greatest value (Z = 61)
+pub const ALPHABET: &str = "0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+/// The digits used for keys by `encoding = decimal`
+pub const DECIMAL_ALPHABET: &str = "0123456789";
+/// The digits used for keys by `encoding = hex`
+pub const HEX_ALPHABET: &str = "0123456789abcdef";
+/// The digits used for keys by `encoding = base36`
+pub const BASE36_ALPHABET: &str = "0123456789abcdefghijklmnopqrstuvwxyz";
+/// Creates the key of the slot at `index`
+///
+/// # Example
+/// ```
+/// # use structurray_keys::encode_index;
+/// assert_eq!(encode_index(0),"0");
+/// assert_eq!(encode_index(61),"Z");
+/// assert_eq!(encode_index(62),"10");
+/// assert_eq!(encode_index(u32::MAX),"4GFfc3");
+/// ```
+pub fn encode_index(index: u32) -> String {
+    encode(index,None).expect("An unexpected error occurred. Please try again. If the error persists, contact me at richcreekbenjamin@gmail.com with a description of what is causing the bug")
+}
+/// Finds the index of the slot whose key is `key`
+///
+/// # Output
+/// Returns [`None`] if `key` could not have been created by [`encode_index`], such as when it is empty, contains characters outside of [`ALPHABET`], has leading zeros, or is too large to be stored in a [`u32`].
+/// Otherwise, `encode_index(decode_key(key).unwrap())` is always equal to `key`.
+/// # Example
+/// ```
+/// # use structurray_keys::decode_key;
+/// assert_eq!(decode_key("10"),Some(62));
+/// assert_eq!(decode_key("4GFfc3"),Some(u32::MAX));
+/// assert_eq!(decode_key("4GFfc4"),None);
+/// assert_eq!(decode_key("010"),None);
+/// assert_eq!(decode_key("_0"),None);
+/// ```
+pub fn decode_key(key: &str) -> Option<u32> {
+    if key.is_empty() || (key.len() > 1 && key.starts_with('0')) {
+        return None;
+    }
+    let mut total: u32 = 0;
+    for digit in key.chars() {
+        let value = ALPHABET.find(digit)? as u32;
+        total = total.checked_mul(62)?.checked_add(value)?;
+    }
+    Some(total)
+}
+/// Creates the key of the slot at `index` using the digits of `alphabet`, in order from least value to greatest value
+///
+/// `encode_index_with(index, ALPHABET)` is always equal to `encode_index(index)`.
+/// # Example
+/// ```
+/// # use structurray_keys::{encode_index_with,HEX_ALPHABET};
+/// assert_eq!(encode_index_with(255,HEX_ALPHABET),"ff");
+/// assert_eq!(encode_index_with(0,"xyz"),"x");
+/// assert_eq!(encode_index_with(3,"xyz"),"yx");
+/// ```
+/// # Panics
+/// Panics if `alphabet` has fewer than two characters
+pub fn encode_index_with(index: u32, alphabet: &str) -> String {
+    let digits: Vec<char> = alphabet.chars().collect();
+    assert!(digits.len() >= 2,"an alphabet needs at least two characters");
+    let base = digits.len() as u32;
+    let mut reversed: Vec<char> = Vec::new();
+    let mut remaining = index;
+    loop {
+        reversed.push(digits[(remaining % base) as usize]);
+        remaining /= base;
+        if remaining == 0 {
+            break;
+        }
+    }
+    reversed.iter().rev().collect()
+}
+/// Finds the index of the slot whose key, created by [`encode_index_with`] and the same `alphabet`, is `key`
+///
+/// # Output
+/// Returns [`None`] under the same conditions as [`decode_key`], with the first character of `alphabet` taking the place of `0`.
+/// # Example
+/// ```
+/// # use structurray_keys::{decode_key_with,DECIMAL_ALPHABET};
+/// assert_eq!(decode_key_with("1000",DECIMAL_ALPHABET),Some(1000));
+/// assert_eq!(decode_key_with("01",DECIMAL_ALPHABET),None);
+/// assert_eq!(decode_key_with("a",DECIMAL_ALPHABET),None);
+/// ```
+pub fn decode_key_with(key: &str, alphabet: &str) -> Option<u32> {
+    let digits: Vec<char> = alphabet.chars().collect();
+    let zero = *digits.first()?;
+    if key.is_empty() || (key.chars().count() > 1 && key.starts_with(zero)) {
+        return None;
+    }
+    let mut total: u32 = 0;
+    for digit in key.chars() {
+        let value = digits.iter().position(|candidate| *candidate == digit)? as u32;
+        total = total.checked_mul(digits.len() as u32)?.checked_add(value)?;
+    }
+    Some(total)
+}
+/// A zero-sized type standing for the key of one slot of one psuedo-array
+///
+/// The `key_tokens` option of `faux_array` generates one implementor for every slot. Because each implementor names the psuedo-array it belongs to as its [`Owner`](KeyToken::Owner),
+/// functions that bound their key parameters by `KeyToken<Owner = ...>` reject keys taken from any other psuedo-array at compile time.
+pub trait KeyToken: Copy {
+    /// The psuedo-array whose slot this token stands for
+    type Owner;
+    /// The serde key of the slot, as created by [`encode_index`]
+    const KEY: &'static str;
+    /// The index of the slot
+    const INDEX: u32;
+}
+/// A psuedo-array whose keys share the namespace `N`, such as one flattened database document, with the keys of other psuedo-arrays
+///
+/// The `namespace` option of `faux_array` implements this trait, and the `assert_namespace!` macro of `structurray` fails compilation if the index ranges of any two members of a namespace overlap.
+pub trait NamespaceMember<N: ?Sized> {
+    /// The index of the first slot
+    const FIRST_INDEX: u32;
+    /// The number of slots
+    const SLOTS: u32;
+}
+/// A custom key scheme, which the `key_encoder` option of `faux_array` serializes and deserializes every slot with instead of the built-in encoding
+///
+/// Every index must be given its own key, and [`decode`](KeyEncoder::decode) must find the index of every key [`encode`](KeyEncoder::encode) creates.
+pub trait KeyEncoder {
+    /// Creates the key of the slot at `index`
+    fn encode(index: u32) -> String;
+    /// Finds the index whose key is `key`, or returns [`None`] if no index has that key
+    fn decode(key: &str) -> Option<u32>;
+}
+/// Hashes the keys of `count` consecutive slots, starting from index `0`, into a [SHA-256](https://en.wikipedia.org/wiki/SHA-2) digest
+///
+/// Each key is followed by a zero byte before hashing, so the digest changes whenever any key, the order of the keys, or the number of keys changes.
+/// # Example
+/// ```
+/// # use structurray_keys::key_digest;
+/// // Without any keys, the digest is that of an empty message
+/// assert_eq!(key_digest(0)[..4],[0xe3,0xb0,0xc4,0x42]);
+/// assert_ne!(key_digest(3),key_digest(4));
+/// ```
+pub fn key_digest(count: u32) -> [u8; 32] {
+    key_digest_from(0,count)
+}
+/// Hashes the keys of `count` consecutive slots, starting from index `start`, into a [SHA-256](https://en.wikipedia.org/wiki/SHA-2) digest
+///
+/// This is the digest of a psuedo-array whose keys were offset with the `start` option of `faux_array`, and is otherwise computed exactly like [`key_digest`].
+/// # Example
+/// ```
+/// # use structurray_keys::{key_digest,key_digest_from};
+/// assert_eq!(key_digest_from(0,3),key_digest(3));
+/// assert_ne!(key_digest_from(1000,3),key_digest(3));
+/// ```
+/// # Panics
+/// Panics if the index of the last slot, `start + count - 1`, is too large to be stored in a [`u32`]
+pub fn key_digest_from(start: u32, count: u32) -> [u8; 32] {
+    digest_keys((0..count).map(|position| encode_index(start + position)))
+}
+/// Hashes `keys`, in order, into a [SHA-256](https://en.wikipedia.org/wiki/SHA-2) digest
+///
+/// This is how every key digest is computed, so it gives the digest of a psuedo-array whose keys were customized in any way, such as with a different encoding.
+/// # Example
+/// ```
+/// # use structurray_keys::{digest_keys,key_digest};
+/// assert_eq!(digest_keys(["0","1","2"]),key_digest(3));
+/// ```
+pub fn digest_keys<I: IntoIterator<Item = K>, K: AsRef<str>>(keys: I) -> [u8; 32] {
+    let mut message: Vec<u8> = Vec::new();
+    for key in keys {
+        message.extend_from_slice(key.as_ref().as_bytes());
+        message.push(0);
+    }
+    sha256(&message)
+}
+/// The first 32 bits of the fractional parts of the cube roots of the first 64 primes
+const ROUND_CONSTANTS: [u32; 64] = [
+    0x428a2f98, 0x71374491, 0xb5c0fbcf, 0xe9b5dba5, 0x3956c25b, 0x59f111f1, 0x923f82a4, 0xab1c5ed5,
+    0xd807aa98, 0x12835b01, 0x243185be, 0x550c7dc3, 0x72be5d74, 0x80deb1fe, 0x9bdc06a7, 0xc19bf174,
+    0xe49b69c1, 0xefbe4786, 0x0fc19dc6, 0x240ca1cc, 0x2de92c6f, 0x4a7484aa, 0x5cb0a9dc, 0x76f988da,
+    0x983e5152, 0xa831c66d, 0xb00327c8, 0xbf597fc7, 0xc6e00bf3, 0xd5a79147, 0x06ca6351, 0x14292967,
+    0x27b70a85, 0x2e1b2138, 0x4d2c6dfc, 0x53380d13, 0x650a7354, 0x766a0abb, 0x81c2c92e, 0x92722c85,
+    0xa2bfe8a1, 0xa81a664b, 0xc24b8b70, 0xc76c51a3, 0xd192e819, 0xd6990624, 0xf40e3585, 0x106aa070,
+    0x19a4c116, 0x1e376c08, 0x2748774c, 0x34b0bcb5, 0x391c0cb3, 0x4ed8aa4a, 0x5b9cca4f, 0x682e6ff3,
+    0x748f82ee, 0x78a5636f, 0x84c87814, 0x8cc70208, 0x90befffa, 0xa4506ceb, 0xbef9a3f7, 0xc67178f2,
+];
+/// Computes the SHA-256 digest of `message`
+fn sha256(message: &[u8]) -> [u8; 32] {
+    let mut state: [u32; 8] = [0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab, 0x5be0cd19];
+    let mut padded = message.to_vec();
+    padded.push(0x80);
+    while padded.len() % 64 != 56 {
+        padded.push(0);
+    }
+    padded.extend_from_slice(&((message.len() as u64) * 8).to_be_bytes());
+    for block in padded.chunks_exact(64) {
+        let mut schedule = [0u32; 64];
+        for (word, bytes) in schedule.iter_mut().zip(block.chunks_exact(4)) {
+            *word = u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+        }
+        for index in 16..64 {
+            let small_zero = schedule[index - 15].rotate_right(7) ^ schedule[index - 15].rotate_right(18) ^ (schedule[index - 15] >> 3);
+            let small_one = schedule[index - 2].rotate_right(17) ^ schedule[index - 2].rotate_right(19) ^ (schedule[index - 2] >> 10);
+            schedule[index] = schedule[index - 16].wrapping_add(small_zero).wrapping_add(schedule[index - 7]).wrapping_add(small_one);
+        }
+        let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut h] = state;
+        for (constant, word) in ROUND_CONSTANTS.iter().zip(schedule) {
+            let big_one = e.rotate_right(6) ^ e.rotate_right(11) ^ e.rotate_right(25);
+            let choice = (e & f) ^ (!e & g);
+            let first = h.wrapping_add(big_one).wrapping_add(choice).wrapping_add(*constant).wrapping_add(word);
+            let big_zero = a.rotate_right(2) ^ a.rotate_right(13) ^ a.rotate_right(22);
+            let majority = (a & b) ^ (a & c) ^ (b & c);
+            let second = big_zero.wrapping_add(majority);
+            h = g;
+            g = f;
+            f = e;
+            e = d.wrapping_add(first);
+            d = c;
+            c = b;
+            b = a;
+            a = first.wrapping_add(second);
+        }
+        for (total, value) in state.iter_mut().zip([a, b, c, d, e, f, g, h]) {
+            *total = total.wrapping_add(value);
+        }
+    }
+    let mut digest = [0u8; 32];
+    for (bytes, word) in digest.chunks_exact_mut(4).zip(state) {
+        bytes.copy_from_slice(&word.to_be_bytes());
+    }
+    digest
+}
@@ -0,0 +1,136 @@
+/*
+Copyright 2024 Benjamin Richcreek
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+//! Generation of accessor methods for the encapsulated accessor mode
+use proc_macro2::{TokenStream,Span};
+use quote::{quote,format_ident};
+use syn::Ident;
+use syn::parse::{Parse,ParseStream};
+use crate::Layout;
+/// The naming scheme of the generated accessors
+#[derive(Clone,Copy)]
+pub(crate) enum Scheme {
+    /// `get_<key>`, `get_<key>_mut`, and `set_<key>`
+    Get,
+    /// `value_at(index)`, `value_at_mut(index)`, and `set_value_at(index, value)`
+    ValueAt,
+    /// `<key>`, `<key>_mut`, and `set_<key>`, with an underscore before keys that start with a digit
+    Key,
+}
+impl Parse for Scheme {
+    fn parse(input: ParseStream) -> Result<Self,syn::Error> {
+        let scheme: Ident = input.parse()?;
+        match scheme.to_string().as_str() {
+            "get" => Ok(Scheme::Get),
+            "value_at" => Ok(Scheme::ValueAt),
+            "key" => Ok(Scheme::Key),
+            _ => Err(syn::Error::new(scheme.span(),"the `accessors` option should be `get`, `value_at`, or `key`")),
+        }
+    }
+}
+/// Turns `key` into a method name for the `key` scheme
+///
+/// Keys that start with a digit, or that are keywords which can't be written as raw identifiers (like `self`), are given a leading underscore. Other keywords become raw identifiers, like `r#if`.
+fn key_ident(key: &str) -> Ident {
+    if key.starts_with(|first: char| first.is_ascii_digit()) || matches!(key,"self" | "Self" | "super" | "crate") {
+        format_ident!("_{}",key)
+    } else if syn::parse_str::<Ident>(key).is_err() {
+        Ident::new_raw(key,Span::call_site())
+    } else {
+        Ident::new(key,Span::call_site())
+    }
+}
+/// Creates the accessors of every slot using `scheme`
+pub(crate) fn generate(layout: &Layout, scheme: Scheme) -> TokenStream {
+    let name = &layout.structure.ident;
+    let (impl_generics, type_generics, where_clause) = layout.structure.generics.split_for_impl();
+    let tipe = layout.field_type;
+    let idents = layout.idents;
+    let methods = match scheme {
+        Scheme::ValueAt => {
+            let indices: Vec<u32> = (0..idents.len() as u32).collect();
+            let slot = layout.unwrap_index(quote! {
+                match index {
+                    #(#indices => ::std::option::Option::Some(&self.#idents),)*
+                    _ => ::std::option::Option::None,
+                }
+            });
+            let slot_mut = layout.unwrap_index(quote! {
+                match index {
+                    #(#indices => ::std::option::Option::Some(&mut self.#idents),)*
+                    _ => ::std::option::Option::None,
+                }
+            });
+            let borrowed = layout.checked_output(quote!(&#tipe));
+            let borrowed_mut = layout.checked_output(quote!(&mut #tipe));
+            let replaced = layout.checked_output(quote!(#tipe));
+            let slot_return = layout.checked_return(quote!(slot));
+            let replace_return = layout.checked_return(quote!(::std::mem::replace(slot,value)));
+            let index_doc = layout.checked_doc("`index` is out of range");
+            quote! {
+                /// Borrows the slot at `index`
+                #index_doc
+                pub fn value_at(&self, index: u32) #borrowed {
+                    let slot = #slot;
+                    #slot_return
+                }
+                /// Mutably borrows the slot at `index`
+                #index_doc
+                pub fn value_at_mut(&mut self, index: u32) #borrowed_mut {
+                    let slot = #slot_mut;
+                    #slot_return
+                }
+                /// Replaces the value of the slot at `index`, returning the old value
+                #index_doc
+                pub fn set_value_at(&mut self, index: u32, value: #tipe) #replaced {
+                    let slot = #slot_mut;
+                    #replace_return
+                }
+            }
+        },
+        Scheme::Get | Scheme::Key => {
+            let getters: Vec<Ident> = layout.suffixes.iter().map(|key| match scheme {
+                Scheme::Get => format_ident!("get_{}",key),
+                _ => key_ident(key),
+            }).collect();
+            let mutable_getters: Vec<Ident> = getters.iter().map(|getter| format_ident!("{}_mut",getter)).collect();
+            let setters: Vec<Ident> = layout.suffixes.iter().map(|key| format_ident!("set_{}",key)).collect();
+            let getter_documentation = layout.names.iter().map(|key| format!("Borrows the slot with the key `{}`",key));
+            let mutable_documentation = layout.names.iter().map(|key| format!("Mutably borrows the slot with the key `{}`",key));
+            let setter_documentation = layout.names.iter().map(|key| format!("Replaces the value of the slot with the key `{}`, returning the old value",key));
+            quote! {
+                #(
+                    #[doc = #getter_documentation]
+                    pub fn #getters(&self) -> &#tipe {
+                        &self.#idents
+                    }
+                    #[doc = #mutable_documentation]
+                    pub fn #mutable_getters(&mut self) -> &mut #tipe {
+                        &mut self.#idents
+                    }
+                    #[doc = #setter_documentation]
+                    pub fn #setters(&mut self, value: #tipe) -> #tipe {
+                        ::std::mem::replace(&mut self.#idents,value)
+                    }
+                )*
+            }
+        },
+    };
+    quote! {
+        impl #impl_generics #name #type_generics #where_clause {
+            #methods
+        }
+    }
+}
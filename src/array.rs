@@ -0,0 +1,46 @@
+/*
+Copyright 2024 Benjamin Richcreek
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+//! Generation of conversions to and from arrays holding one value per slot
+use proc_macro2::TokenStream;
+use quote::quote;
+use crate::Layout;
+/// Creates implementations of [`From`] converting an array with one element per slot into the [`struct`](https://doc.rust-lang.org/1.58.1/std/keyword.struct.html), and back
+pub(crate) fn generate(layout: &Layout) -> TokenStream {
+    let name = &layout.structure.ident;
+    let (impl_generics, type_generics, where_clause) = layout.structure.generics.split_for_impl();
+    let tipe = layout.field_type;
+    let idents = layout.idents;
+    let extra_fields = layout.extra_fields;
+    let count = idents.len();
+    quote! {
+        impl #impl_generics ::std::convert::From<[#tipe; #count]> for #name #type_generics #where_clause {
+            /// Moves each element of `array` into the slot with the same index
+            fn from(array: [#tipe; #count]) -> Self {
+                let [#(#idents),*] = array;
+                #name {
+                    #(#idents,)*
+                    #(#extra_fields: ::std::default::Default::default(),)*
+                }
+            }
+        }
+        impl #impl_generics ::std::convert::From<#name #type_generics> for [#tipe; #count] #where_clause {
+            /// Moves each slot of `value` into the element with the same index
+            fn from(value: #name #type_generics) -> Self {
+                [#(value.#idents),*]
+            }
+        }
+    }
+}
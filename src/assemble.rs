@@ -0,0 +1,79 @@
+/*
+Copyright 2024 Benjamin Richcreek
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+//! Generation of a constructor that merges several partial maps of keys to values
+use proc_macro2::TokenStream;
+use quote::{quote,format_ident};
+use syn::Ident;
+use crate::Layout;
+/// Names the error type generated for the [`struct`](https://doc.rust-lang.org/1.58.1/std/keyword.struct.html) named `name`
+pub(crate) fn type_name(name: &Ident) -> Ident {
+    format_ident!("{}MissingKeys",name)
+}
+/// Creates the missing keys error type and the `assemble` associated function
+pub(crate) fn generate(layout: &Layout) -> TokenStream {
+    let name = &layout.structure.ident;
+    let visibility = &layout.structure.vis;
+    let (impl_generics, type_generics, where_clause) = layout.structure.generics.split_for_impl();
+    let error = type_name(name);
+    let tipe = layout.field_type;
+    let idents = layout.idents;
+    let names = layout.names;
+    let extra_fields = layout.extra_fields;
+    let message = format!("{{}} keys of {} were missing: {{}}",name);
+    quote! {
+        /// The keys of the slots that none of the maps given to `assemble` contained
+        #[derive(Clone,PartialEq,Eq,Hash,Debug)]
+        #visibility struct #error {
+            /// The missing keys, in the order of their slots
+            pub keys: ::std::vec::Vec<::std::string::String>,
+        }
+        impl ::std::fmt::Display for #error {
+            fn fmt(&self, formatter: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                ::std::write!(formatter,#message,self.keys.len(),self.keys.join(", "))
+            }
+        }
+        impl ::std::error::Error for #error {}
+        impl #impl_generics #name #type_generics #where_clause {
+            /// Merges `parts`, each holding the values of some slots by key, into one instance, failing unless every slot is found in at least one part
+            ///
+            /// When several parts hold the same key, the value from the last of them is kept. Keys that don't belong to any slot are ignored.
+            pub fn assemble<I: ::std::iter::IntoIterator<Item = ::std::collections::HashMap<::std::string::String,#tipe>>>(parts: I) -> ::std::result::Result<Self,#error> {
+                #(let mut #idents: ::std::option::Option<#tipe> = ::std::option::Option::None;)*
+                for part in parts {
+                    for (key,value) in part {
+                        match key.as_str() {
+                            #(#names => #idents = ::std::option::Option::Some(value),)*
+                            _ => {},
+                        }
+                    }
+                }
+                let mut keys = ::std::vec::Vec::new();
+                #(
+                    if #idents.is_none() {
+                        keys.push(::std::string::String::from(#names));
+                    }
+                )*
+                if !keys.is_empty() {
+                    return ::std::result::Result::Err(#error { keys });
+                }
+                ::std::result::Result::Ok(#name {
+                    #(#idents: #idents.expect("Every slot was checked for a value"),)*
+                    #(#extra_fields: ::std::default::Default::default(),)*
+                })
+            }
+        }
+    }
+}
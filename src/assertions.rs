@@ -0,0 +1,33 @@
+/*
+Copyright 2024 Benjamin Richcreek
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+//! Generation of compile-time assertions about the element type
+use proc_macro2::TokenStream;
+use quote::quote;
+use crate::Layout;
+/// Creates a constant item that fails to compile unless the element type implements `bound`
+pub(crate) fn generate(layout: &Layout, bound: TokenStream) -> TokenStream {
+    let (impl_generics, _, where_clause) = layout.structure.generics.split_for_impl();
+    let tipe = layout.field_type;
+    quote! {
+        const _: () = {
+            fn implemented<Checked: ?::std::marker::Sized + #bound>() {}
+            #[allow(dead_code)]
+            fn check #impl_generics () #where_clause {
+                implemented::<#tipe>();
+            }
+        };
+    }
+}
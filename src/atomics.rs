@@ -0,0 +1,62 @@
+/*
+Copyright 2024 Benjamin Richcreek
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+//! Generation of bulk load and store helpers for atomic element types
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Type,Ident,parse_quote};
+use crate::Layout;
+/// Finds the primitive type stored by `tipe` if it is one of the standard library's atomic integer or [`bool`] types
+pub(crate) fn primitive(tipe: &Type) -> Option<Type> {
+    let Type::Path(path) = tipe else {
+        return None;
+    };
+    let last: &Ident = &path.path.segments.last()?.ident;
+    let primitive: Type = match last.to_string().as_str() {
+        "AtomicBool" => parse_quote!(bool),
+        "AtomicI8" => parse_quote!(i8),
+        "AtomicI16" => parse_quote!(i16),
+        "AtomicI32" => parse_quote!(i32),
+        "AtomicI64" => parse_quote!(i64),
+        "AtomicIsize" => parse_quote!(isize),
+        "AtomicU8" => parse_quote!(u8),
+        "AtomicU16" => parse_quote!(u16),
+        "AtomicU32" => parse_quote!(u32),
+        "AtomicU64" => parse_quote!(u64),
+        "AtomicUsize" => parse_quote!(usize),
+        _ => return None,
+    };
+    Some(primitive)
+}
+/// Creates the `load_all` and `store_all` methods, which move every slot to or from an array of `primitive` values
+pub(crate) fn generate(layout: &Layout, primitive: &Type) -> TokenStream {
+    let name = &layout.structure.ident;
+    let (impl_generics, type_generics, where_clause) = layout.structure.generics.split_for_impl();
+    let idents = layout.idents;
+    let count = idents.len();
+    let indices = 0..count;
+    quote! {
+        impl #impl_generics #name #type_generics #where_clause {
+            /// Loads every slot, in index order, using `ordering` for each load
+            pub fn load_all(&self, ordering: ::std::sync::atomic::Ordering) -> [#primitive; #count] {
+                [#(self.#idents.load(ordering)),*]
+            }
+            /// Stores each value of `values` in the slot with the same index, in index order, using `ordering` for each store
+            pub fn store_all(&self, values: [#primitive; #count], ordering: ::std::sync::atomic::Ordering) {
+                #(self.#idents.store(values[#indices],ordering);)*
+            }
+        }
+    }
+}
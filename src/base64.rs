@@ -0,0 +1,87 @@
+/*
+Copyright 2024 Benjamin Richcreek
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+//! Generation of the padded base64 encoding shared by every option that writes bytes as a string
+use proc_macro2::TokenStream;
+use quote::quote;
+/// Creates an expression that encodes `bytes`, an expression of type `&[u8]`, as a padded base64 [`String`]
+pub(crate) fn encode(bytes: TokenStream) -> TokenStream {
+    quote! {{
+        fn faux_to_base64(bytes: &[u8]) -> ::std::string::String {
+            const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+            let mut written = ::std::string::String::with_capacity(bytes.len().div_ceil(3) * 4);
+            for chunk in bytes.chunks(3) {
+                let mut group: u32 = 0;
+                let mut looper: usize = 0;
+                while looper < 3 {
+                    group <<= 8;
+                    if let ::std::option::Option::Some(byte) = chunk.get(looper) {
+                        group |= *byte as u32;
+                    }
+                    looper += 1;
+                }
+                let mut sextet: usize = 0;
+                while sextet < 4 {
+                    if sextet <= chunk.len() {
+                        written.push(ALPHABET[((group >> (18 - 6 * sextet)) & 63) as usize] as char);
+                    } else {
+                        written.push('=');
+                    }
+                    sextet += 1;
+                }
+            }
+            written
+        }
+        faux_to_base64(#bytes)
+    }}
+}
+/// Creates an expression that decodes `encoded`, an expression of type `&str`, from padded base64 into an [`Option<Vec<u8>>`], which is `None` if `encoded` isn't valid padded base64
+///
+/// The whole string is checked: its length must be a multiple of 4, padding may only end the last group of 4 characters, and the bits left over after the last byte must be zero,
+/// so every sequence of bytes has exactly one accepted encoding and truncated or corrupted strings are rejected.
+pub(crate) fn decode(encoded: TokenStream) -> TokenStream {
+    quote! {{
+        fn faux_from_base64(encoded: &str) -> ::std::option::Option<::std::vec::Vec<u8>> {
+            const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+            let characters = encoded.as_bytes();
+            if characters.len() % 4 != 0 {
+                return ::std::option::Option::None;
+            }
+            let groups = characters.len() / 4;
+            let mut decoded: ::std::vec::Vec<u8> = ::std::vec::Vec::with_capacity(groups * 3);
+            for (position, chunk) in characters.chunks(4).enumerate() {
+                let padding = chunk.iter().rev().take_while(|character| **character == b'=').count();
+                if padding > 2 || (padding > 0 && position + 1 != groups) {
+                    return ::std::option::Option::None;
+                }
+                let mut group: u32 = 0;
+                for character in &chunk[..4 - padding] {
+                    group = (group << 6) | ALPHABET.iter().position(|candidate| candidate == character)? as u32;
+                }
+                group <<= 6 * padding as u32;
+                if group & ((1 << (8 * padding as u32)) - 1) != 0 {
+                    return ::std::option::Option::None;
+                }
+                let mut looper: usize = 0;
+                while looper < 3 - padding {
+                    decoded.push((group >> (16 - 8 * looper)) as u8);
+                    looper += 1;
+                }
+            }
+            ::std::option::Option::Some(decoded)
+        }
+        faux_from_base64(#encoded)
+    }}
+}
@@ -0,0 +1,52 @@
+/*
+Copyright 2024 Benjamin Richcreek
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+//! Generation of an iterator that splits the slots into batches of writes
+use proc_macro2::TokenStream;
+use quote::quote;
+use crate::Layout;
+/// Creates the `write_batches` method
+pub(crate) fn generate(layout: &Layout) -> TokenStream {
+    let name = &layout.structure.ident;
+    let (impl_generics, type_generics, where_clause) = layout.structure.generics.split_for_impl();
+    let tipe = layout.field_type;
+    let idents = layout.idents;
+    let names = layout.names;
+    let (size, nonzero, documentation) = if layout.no_panic {
+        (quote!(::std::num::NonZeroUsize),quote! {},quote! {})
+    } else {
+        let nonzero = quote! {
+            let batch_size = ::std::num::NonZeroUsize::new(batch_size).expect("write batches must hold at least one slot");
+        };
+        let documentation = quote! {
+            ///
+            /// # Panics
+            /// Panics if `batch_size` is `0`
+        };
+        (quote!(usize),nonzero,documentation)
+    };
+    quote! {
+        impl #impl_generics #name #type_generics #where_clause {
+            /// Iterates over maps of keys to slots, each holding at most `batch_size` slots, in the order of the slots
+            #documentation
+            pub fn write_batches(&self, batch_size: #size) -> impl ::std::iter::DoubleEndedIterator<Item = ::std::collections::HashMap<&'static str,&#tipe>> + ::std::iter::ExactSizeIterator + '_ {
+                #nonzero
+                let entries: ::std::vec::Vec<(&'static str,&#tipe)> = ::std::vec![#((#names,&self.#idents)),*];
+                let batch_size = batch_size.get();
+                (0..entries.len().div_ceil(batch_size)).map(move |batch| entries.iter().skip(batch * batch_size).take(batch_size).copied().collect())
+            }
+        }
+    }
+}
@@ -0,0 +1,100 @@
+/*
+Copyright 2024 Benjamin Richcreek
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+//! Generation of serialization that packs every slot into one base64 string
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::Type;
+use crate::{Layout,with_lifetime,base64,numeric};
+/// The key the slots are written under when the `blob` option isn't given one
+pub(crate) const BLOB_KEY: &str = "blob";
+/// Finds whether `tipe` is a primitive number whose little-endian bytes are the same on every platform, which excludes [`isize`] and [`usize`]
+pub(crate) fn supports(tipe: &Type) -> bool {
+    let portable = !matches!(tipe,Type::Path(path) if path.path.is_ident("isize") || path.path.is_ident("usize"));
+    portable && numeric::kind(tipe).is_some()
+}
+/// Creates [`Serialize`](https://docs.rs/serde/latest/serde/trait.Serialize.html) and [`Deserialize`](https://docs.rs/serde/latest/serde/trait.Deserialize.html) implementations that write every slot
+/// as little-endian bytes, in index order, in a single base64 string under `key`
+pub(crate) fn generate(layout: &Layout, key: &str) -> TokenStream {
+    let name = &layout.structure.ident;
+    let generics = &layout.structure.generics;
+    let (impl_generics, type_generics, where_clause) = generics.split_for_impl();
+    let deserializable = with_lifetime(generics,"'__de");
+    let (deserialize_impl_generics, _, _) = deserializable.split_for_impl();
+    let tipe = layout.field_type;
+    let idents = layout.idents;
+    let extra_fields = layout.extra_fields;
+    let count = idents.len();
+    let encode = base64::encode(quote!(&bytes));
+    let decode = base64::decode(quote!(&encoded));
+    let expecting = format!("a map holding the slots of {} as base64 under \"{}\"",name,key);
+    let length = format!("{} little-endian values",count);
+    let serialize_body = layout.instrument("serialize",quote! { ::std::result::Result<__S::Ok,__S::Error> },quote! {
+        use ::serde::ser::SerializeMap;
+        let mut bytes: ::std::vec::Vec<u8> = ::std::vec::Vec::with_capacity(#count * ::std::mem::size_of::<#tipe>());
+        #(bytes.extend_from_slice(&self.#idents.to_le_bytes());)*
+        let mut map = serializer.serialize_map(::std::option::Option::Some(1))?;
+        map.serialize_entry(#key,&#encode)?;
+        map.end()
+    });
+    let deserialize_body = layout.instrument("deserialize",quote! { ::std::result::Result<Self,__D::Error> },quote! {
+        deserializer.deserialize_map(__FauxVisitor(::std::marker::PhantomData))
+    });
+    quote! {
+        const _: () = {
+            impl #impl_generics ::serde::Serialize for #name #type_generics #where_clause {
+                fn serialize<__S: ::serde::Serializer>(&self, serializer: __S) -> ::std::result::Result<__S::Ok,__S::Error> {
+                    #serialize_body
+                }
+            }
+            struct __FauxVisitor #generics (::std::marker::PhantomData<fn() -> #name #type_generics>) #where_clause;
+            impl #deserialize_impl_generics ::serde::de::Visitor<'__de> for __FauxVisitor #type_generics #where_clause {
+                type Value = #name #type_generics;
+                fn expecting(&self, formatter: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+                    formatter.write_str(#expecting)
+                }
+                fn visit_map<__A: ::serde::de::MapAccess<'__de>>(self, mut map: __A) -> ::std::result::Result<Self::Value,__A::Error> {
+                    let mut blob: ::std::option::Option<::std::string::String> = ::std::option::Option::None;
+                    while let ::std::option::Option::Some(key) = map.next_key::<::std::string::String>()? {
+                        if key == #key {
+                            if blob.is_some() {
+                                return ::std::result::Result::Err(<__A::Error as ::serde::de::Error>::duplicate_field(#key));
+                            }
+                            blob = ::std::option::Option::Some(map.next_value()?);
+                        } else {
+                            map.next_value::<::serde::de::IgnoredAny>()?;
+                        }
+                    }
+                    let encoded = blob.ok_or_else(|| <__A::Error as ::serde::de::Error>::missing_field(#key))?;
+                    let bytes = #decode.ok_or_else(|| <__A::Error as ::serde::de::Error>::invalid_value(::serde::de::Unexpected::Str(&encoded),&"padded base64"))?;
+                    if bytes.len() != #count * ::std::mem::size_of::<#tipe>() {
+                        return ::std::result::Result::Err(<__A::Error as ::serde::de::Error>::invalid_length(bytes.len() / ::std::mem::size_of::<#tipe>(),&#length));
+                    }
+                    #[allow(unused_mut,unused_variables)]
+                    let mut values = bytes.chunks_exact(::std::mem::size_of::<#tipe>()).map(|chunk| chunk.try_into().map(<#tipe>::from_le_bytes));
+                    ::std::result::Result::Ok(#name {
+                        #(#idents: values.next().and_then(::std::result::Result::ok).ok_or_else(|| <__A::Error as ::serde::de::Error>::invalid_length(#count,&#length))?,)*
+                        #(#extra_fields: ::std::default::Default::default(),)*
+                    })
+                }
+            }
+            impl #deserialize_impl_generics ::serde::Deserialize<'__de> for #name #type_generics #where_clause {
+                fn deserialize<__D: ::serde::Deserializer<'__de>>(deserializer: __D) -> ::std::result::Result<Self,__D::Error> {
+                    #deserialize_body
+                }
+            }
+        };
+    }
+}
@@ -0,0 +1,74 @@
+/*
+Copyright 2024 Benjamin Richcreek
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+//! Generation of borrowed twins whose slots are [`Cow`](std::borrow::Cow)s
+use proc_macro2::{TokenStream,Span};
+use quote::quote;
+use syn::{Ident,Lifetime,parse_quote};
+use crate::{Layout,with_lifetime,with_bound};
+/// Creates the borrowed twin named `twin`, along with conversions between it and the owned [`struct`](https://doc.rust-lang.org/1.58.1/std/keyword.struct.html)
+pub(crate) fn generate(layout: &Layout, twin: &Ident) -> TokenStream {
+    let name = &layout.structure.ident;
+    let visibility = &layout.structure.vis;
+    let (_, type_generics, _) = layout.structure.generics.split_for_impl();
+    let tipe = layout.field_type;
+    let idents = layout.idents;
+    let extra_fields = layout.extra_fields;
+    let slot_attributes = layout.slot_attributes;
+    let lifetime = Lifetime::new("'faux_borrow",Span::call_site());
+    let twin_generics = with_bound(&with_lifetime(&layout.structure.generics,"'faux_borrow"),parse_quote!(#tipe: ::std::clone::Clone + #lifetime));
+    let (twin_impl_generics, twin_type_generics, twin_where_clause) = twin_generics.split_for_impl();
+    let owned_generics = with_bound(&layout.structure.generics,parse_quote!(#tipe: ::std::clone::Clone));
+    let (owned_impl_generics, _, owned_where_clause) = owned_generics.split_for_impl();
+    let serde_attributes = if cfg!(feature = "serde") && slot_attributes.iter().any(|attribute| !attribute.is_empty()) {
+        layout.serde_attribute(quote! { derive(::serde::Serialize,::serde::Deserialize) })
+    } else {
+        quote! {}
+    };
+    let borrow = if serde_attributes.is_empty() {
+        quote! {}
+    } else {
+        layout.serde_attribute(quote! { serde(borrow) })
+    };
+    quote! {
+        /// A twin whose slots may borrow their values instead of owning them
+        #serde_attributes
+        #visibility struct #twin #twin_generics #twin_where_clause {
+            #(#slot_attributes #borrow #idents : ::std::borrow::Cow<#lifetime,#tipe>),*
+        }
+        impl #twin_impl_generics #twin #twin_type_generics #twin_where_clause {
+            /// Converts every slot into an owned value, cloning the slots that are still borrowed
+            pub fn into_owned(self) -> #name #type_generics {
+                #name {
+                    #(#idents : self.#idents.into_owned(),)*
+                    #(#extra_fields : ::std::default::Default::default(),)*
+                }
+            }
+        }
+        impl #twin_impl_generics ::std::convert::From<&#lifetime #name #type_generics> for #twin #twin_type_generics #twin_where_clause {
+            fn from(owned: &#lifetime #name #type_generics) -> Self {
+                #twin {
+                    #(#idents : ::std::borrow::Cow::Borrowed(&owned.#idents)),*
+                }
+            }
+        }
+        impl #owned_impl_generics #name #type_generics #owned_where_clause {
+            /// Borrows every slot without cloning it
+            pub fn as_borrowed<#lifetime>(&#lifetime self) -> #twin #twin_type_generics {
+                #twin::from(self)
+            }
+        }
+    }
+}
@@ -0,0 +1,74 @@
+/*
+Copyright 2024 Benjamin Richcreek
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+//! Generation of constructors that build instances directly on the heap
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::Expr;
+use crate::{Layout,element_bound};
+/// Creates the body of a constructor returning `Box<Self>` that writes `slot` into each slot in index order, without ever placing the whole instance on the stack
+///
+/// `slot` is evaluated once per slot, with `__faux_index` bound to the slot's index as a [`u32`], so the binding can't shadow a name the user's `init` expression refers to.
+pub(crate) fn constructor_body(layout: &Layout, slot: &TokenStream) -> TokenStream {
+    let idents = layout.idents;
+    let extra_fields = layout.extra_fields;
+    let indices = 0..idents.len() as u32;
+    quote! {
+        let mut boxed = ::std::boxed::Box::<Self>::new_uninit();
+        let pointer = boxed.as_mut_ptr();
+        // SAFETY: every field is written through a pointer into the allocation before the box is assumed to be initialized. If a slot panics, the fields written so far are leaked rather than dropped.
+        unsafe {
+            #({
+                let __faux_index: u32 = #indices;
+                ::std::ptr::addr_of_mut!((*pointer).#idents).write(#slot);
+            })*
+            #(::std::ptr::addr_of_mut!((*pointer).#extra_fields).write(::std::default::Default::default());)*
+            boxed.assume_init()
+        }
+    }
+}
+/// Creates the `new_boxed_filled` and `new_boxed_default` constructors
+///
+/// When `init` is given, `new_boxed_default` evaluates it for every slot instead of using the element type's default value.
+pub(crate) fn generate(layout: &Layout, init: Option<&Expr>) -> TokenStream {
+    let name = &layout.structure.ident;
+    let (impl_generics, type_generics, where_clause) = layout.structure.generics.split_for_impl();
+    let tipe = layout.field_type;
+    let filled = constructor_body(layout,&quote! { ::std::clone::Clone::clone(&value) });
+    let filled = element_bound(tipe,quote!(::std::clone::Clone)).map(|clone_bound| quote! {
+        /// Creates an instance on the heap with a clone of `value` in every slot
+        #[allow(unused_variables)]
+        pub fn new_boxed_filled(value: #tipe) -> ::std::boxed::Box<Self> #clone_bound {
+            #filled
+        }
+    });
+    let (defaulted, default_bound) = match init {
+        Some(value) => (constructor_body(layout,&quote! { #value }),Some(quote! {})),
+        None => (constructor_body(layout,&quote! { <#tipe as ::std::default::Default>::default() }),element_bound(tipe,quote!(::std::default::Default))),
+    };
+    let defaulted = default_bound.map(|default_bound| quote! {
+        /// Creates an instance on the heap with the initial value in every slot, which is the default value of the element type unless the `init` option was given
+        #[allow(unused_variables)]
+        pub fn new_boxed_default() -> ::std::boxed::Box<Self> #default_bound {
+            #defaulted
+        }
+    });
+    quote! {
+        impl #impl_generics #name #type_generics #where_clause {
+            #filled
+            #defaulted
+        }
+    }
+}
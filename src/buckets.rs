@@ -0,0 +1,101 @@
+/*
+Copyright 2024 Benjamin Richcreek
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+//! Generation of helpers that treat the slots as a ring of time buckets
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Ident,LitInt,Token};
+use syn::parse::{Parse,ParseStream};
+use crate::Layout;
+/// The arguments of the `time_buckets` option, in milliseconds
+pub(crate) struct Buckets {
+    epoch: u64,
+    width: u64,
+}
+impl Parse for Buckets {
+    fn parse(input: ParseStream) -> Result<Self,syn::Error> {
+        let mut epoch = 0;
+        let mut width = None;
+        while !input.is_empty() {
+            let option: Ident = input.parse()?;
+            input.parse::<Token![=]>()?;
+            match option.to_string().as_str() {
+                "epoch" => epoch = input.parse::<LitInt>()?.base10_parse()?,
+                "width" => {
+                    let millis = if input.peek(LitInt) {
+                        let literal: LitInt = input.parse()?;
+                        let millis = literal.base10_parse()?;
+                        if millis == 0 {
+                            return Err(syn::Error::new(literal.span(),"the width of a time bucket should be at least one millisecond"));
+                        }
+                        millis
+                    } else {
+                        let unit: Ident = input.parse()?;
+                        match unit.to_string().as_str() {
+                            "minute" => 60_000,
+                            "hour" => 3_600_000,
+                            "day" => 86_400_000,
+                            "week" => 604_800_000,
+                            _ => return Err(syn::Error::new(unit.span(),"the width of a time bucket should be `minute`, `hour`, `day`, `week`, or a number of milliseconds")),
+                        }
+                    };
+                    width = Some(millis);
+                },
+                _ => return Err(syn::Error::new(option.span(),"the options of `time_buckets` are `epoch` and `width`")),
+            }
+            if !input.is_empty() {
+                input.parse::<Token![,]>()?;
+            }
+        }
+        let Some(width) = width else {
+            return Err(input.error("the `time_buckets` option requires a `width`"));
+        };
+        Ok(Buckets {
+            epoch,
+            width,
+        })
+    }
+}
+/// Creates the `BUCKET_EPOCH` and `BUCKET_WIDTH` associated constants along with `slot_for` and `key_for`
+pub(crate) fn generate(layout: &Layout, buckets: &Buckets) -> TokenStream {
+    let name = &layout.structure.ident;
+    let (impl_generics, type_generics, where_clause) = layout.structure.generics.split_for_impl();
+    let names = layout.names;
+    let count = names.len();
+    let indices = 0..count as u32;
+    let epoch = buckets.epoch;
+    let width = buckets.width;
+    quote! {
+        impl #impl_generics #name #type_generics #where_clause {
+            /// The moment the bucket in the first slot starts, in milliseconds since the Unix epoch
+            pub const BUCKET_EPOCH: u64 = #epoch;
+            /// The length of time covered by each bucket, in milliseconds
+            pub const BUCKET_WIDTH: u64 = #width;
+            /// Returns the index of the slot holding the bucket that contains `millis`, in milliseconds since the Unix epoch
+            ///
+            /// Buckets wrap around after the last slot, so the slots form a ring that always holds the most recent buckets.
+            pub const fn slot_for(millis: u64) -> u32 {
+                (millis as i128 - #epoch as i128).div_euclid(#width as i128).rem_euclid(#count as i128) as u32
+            }
+            /// Returns the key of the slot holding the bucket that contains `millis`, in milliseconds since the Unix epoch
+            pub const fn key_for(millis: u64) -> &'static str {
+                match Self::slot_for(millis) {
+                    #(#indices => #names,)*
+                    _ => unreachable!(),
+                }
+            }
+        }
+    }
+}
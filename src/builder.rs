@@ -0,0 +1,126 @@
+/*
+Copyright 2024 Benjamin Richcreek
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+//! Generation of a builder that collects the slots one at a time
+use proc_macro2::TokenStream;
+use quote::{quote,format_ident};
+use crate::Layout;
+/// Creates the builder, named after the [`struct`](https://doc.rust-lang.org/1.58.1/std/keyword.struct.html) with `Builder` appended, along with its error type and the `builder` associated function
+pub(crate) fn generate(layout: &Layout) -> TokenStream {
+    let name = &layout.structure.ident;
+    let visibility = &layout.structure.vis;
+    let generics = &layout.structure.generics;
+    let (impl_generics, type_generics, where_clause) = generics.split_for_impl();
+    let builder = format_ident!("{}Builder",name);
+    let error = format_ident!("{}BuilderError",name);
+    let tipe = layout.field_type;
+    let idents = layout.idents;
+    let extra_fields = layout.extra_fields;
+    let count = idents.len();
+    let indices: Vec<u32> = (0..count as u32).collect();
+    let out_of_range = layout.out_of_range();
+    let builder_documentation = format!("Collects the slots of a [`{}`] one at a time",name);
+    let error_documentation = format!("The slots that weren't set before a [`{}`] was built",builder);
+    let message = format!("{{}} slots of {} weren't set: {{:?}}",name);
+    let setter = if layout.no_panic {
+        quote! {
+            /// Sets the slot at `index` to `value`, replacing any value it was already set to
+            ///
+            /// Returns `None` without using `value` if `index` is out of range.
+            pub fn set(&mut self, index: u32, value: #tipe) -> ::std::option::Option<&mut Self> {
+                match index {
+                    #(#indices => self.#idents = ::std::option::Option::Some(value),)*
+                    _ => return ::std::option::Option::None,
+                }
+                ::std::option::Option::Some(self)
+            }
+        }
+    } else {
+        quote! {
+            /// Sets the slot at `index` to `value`, replacing any value it was already set to
+            ///
+            /// # Panics
+            /// Panics if `index` is out of range
+            pub fn set(&mut self, index: u32, value: #tipe) -> &mut Self {
+                match index {
+                    #(#indices => self.#idents = ::std::option::Option::Some(value),)*
+                    _ => #out_of_range,
+                }
+                self
+            }
+        }
+    };
+    quote! {
+        #[doc = #builder_documentation]
+        #visibility struct #builder #generics #where_clause {
+            #(#idents: ::std::option::Option<#tipe>,)*
+        }
+        impl #impl_generics #builder #type_generics #where_clause {
+            /// Creates a builder with no slots set
+            pub fn new() -> Self {
+                #builder {
+                    #(#idents: ::std::option::Option::None,)*
+                }
+            }
+            #setter
+            /// Returns `true` if the slot at `index` has been set
+            pub fn is_set(&self, index: u32) -> bool {
+                match index {
+                    #(#indices => self.#idents.is_some(),)*
+                    _ => false,
+                }
+            }
+            /// Creates the psuedo-array, failing with the indices of every slot that wasn't set
+            pub fn build(self) -> ::std::result::Result<#name #type_generics,#error> {
+                let mut missing = ::std::vec::Vec::new();
+                #(
+                    if self.#idents.is_none() {
+                        missing.push(#indices);
+                    }
+                )*
+                if !missing.is_empty() {
+                    return ::std::result::Result::Err(#error { missing });
+                }
+                ::std::result::Result::Ok(#name {
+                    #(#idents: self.#idents.expect("Every slot was checked to be set"),)*
+                    #(#extra_fields: ::std::default::Default::default(),)*
+                })
+            }
+        }
+        impl #impl_generics ::std::default::Default for #builder #type_generics #where_clause {
+            fn default() -> Self {
+                Self::new()
+            }
+        }
+        #[doc = #error_documentation]
+        #[derive(Clone,PartialEq,Eq,Hash,Debug)]
+        #visibility struct #error {
+            /// The indices of the slots that weren't set, in order
+            pub missing: ::std::vec::Vec<u32>,
+        }
+        impl ::std::fmt::Display for #error {
+            fn fmt(&self, formatter: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                ::std::write!(formatter,#message,self.missing.len(),self.missing)
+            }
+        }
+        impl ::std::error::Error for #error {}
+        impl #impl_generics #name #type_generics #where_clause {
+            /// Creates a builder with no slots set
+            pub fn builder() -> #builder #type_generics {
+                #builder::new()
+            }
+        }
+    }
+}
@@ -0,0 +1,81 @@
+/*
+Copyright 2024 Benjamin Richcreek
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+//! Generation of a deterministic, pretty-printed JSON snapshot helper
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::LitStr;
+use crate::Layout;
+/// Creates the `to_canonical_json` method, which only exists when the crate feature named by `feature` is enabled if `feature` is given
+///
+/// The method returns the `serde_json::Error` instead of panicking if the `no_panic` option was given.
+pub(crate) fn generate(layout: &Layout, feature: Option<&LitStr>) -> TokenStream {
+    let name = &layout.structure.ident;
+    let (impl_generics, type_generics, where_clause) = layout.structure.generics.split_for_impl();
+    let gate = match feature {
+        Some(feature) => quote! { #[cfg(feature = #feature)] },
+        None => quote! {},
+    };
+    let (output, written, failure_doc) = if layout.no_panic {
+        let output = quote! { ::std::result::Result<::std::string::String,::serde_json::Error> };
+        (output, quote! { written }, quote! {
+            ///
+            /// Returns an error if this instance can't be represented as JSON.
+        })
+    } else {
+        let output = quote! { ::std::string::String };
+        (output, quote! { written.expect("A psuedo-array should be representable as JSON") }, quote! {
+            ///
+            /// # Panics
+            /// Panics if this instance can't be represented as JSON
+        })
+    };
+    quote! {
+        #gate
+        impl #impl_generics #name #type_generics #where_clause {
+            /// Writes this instance as pretty-printed JSON with the keys of every object sorted, so the output only changes when the data does
+            #failure_doc
+            pub fn to_canonical_json(&self) -> #output where Self: ::serde::Serialize {
+                struct Sorted<'faux_json>(&'faux_json ::serde_json::Value);
+                impl ::serde::Serialize for Sorted<'_> {
+                    fn serialize<__S: ::serde::Serializer>(&self, serializer: __S) -> ::std::result::Result<__S::Ok,__S::Error> {
+                        use ::serde::ser::{SerializeMap,SerializeSeq};
+                        match self.0 {
+                            ::serde_json::Value::Object(object) => {
+                                let mut entries: ::std::vec::Vec<(&::std::string::String,&::serde_json::Value)> = object.iter().collect();
+                                entries.sort_by(|(first, _), (second, _)| first.cmp(second));
+                                let mut map = serializer.serialize_map(::std::option::Option::Some(entries.len()))?;
+                                for (key, value) in entries {
+                                    map.serialize_entry(key,&Sorted(value))?;
+                                }
+                                map.end()
+                            },
+                            ::serde_json::Value::Array(array) => {
+                                let mut sequence = serializer.serialize_seq(::std::option::Option::Some(array.len()))?;
+                                for value in array {
+                                    sequence.serialize_element(&Sorted(value))?;
+                                }
+                                sequence.end()
+                            },
+                            other => ::serde::Serialize::serialize(other,serializer),
+                        }
+                    }
+                }
+                let written = ::serde_json::to_value(self).and_then(|value| ::serde_json::to_string_pretty(&Sorted(&value)));
+                #written
+            }
+        }
+    }
+}
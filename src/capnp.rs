@@ -0,0 +1,90 @@
+/*
+Copyright 2024 Benjamin Richcreek
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+//! Generation of the `CAPNP_SCHEMA` constant
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Type,GenericArgument,PathArguments};
+use crate::Layout;
+/// Creates an inherent `impl` block holding a Cap'n Proto schema for the psuedo-array described by `layout`
+pub(crate) fn generate(layout: &Layout) -> TokenStream {
+    let name = &layout.structure.ident;
+    let (impl_generics, type_generics, where_clause) = layout.structure.generics.split_for_impl();
+    let element = schema_type(layout.field_type);
+    let parameters: Vec<String> = layout.structure.generics.type_params().map(|parameter| parameter.ident.to_string()).collect();
+    let mut schema = if parameters.is_empty() {
+        format!("struct {} {{\n",name)
+    } else {
+        format!("struct {}({}) {{\n",name,parameters.join(", "))
+    };
+    let mut looper: usize = 0;
+    while looper < layout.names.len() {
+        schema.push_str(&format!("  slot{} @{} :{};\n",layout.suffixes[looper],looper,element));
+        looper += 1;
+    }
+    schema.push_str("}\n");
+    quote! {
+        impl #impl_generics #name #type_generics #where_clause {
+            /// A Cap'n Proto definition of this psuedo-array, with one field per slot ordered by index
+            pub const CAPNP_SCHEMA: &'static str = #schema;
+        }
+    }
+}
+/// Finds the name Cap'n Proto uses for `tipe`, falling back to the name of the type itself
+fn schema_type(tipe: &Type) -> String {
+    match tipe {
+        Type::Reference(reference) => schema_type(&reference.elem),
+        Type::Tuple(tuple) if tuple.elems.is_empty() => String::from("Void"),
+        Type::Path(path) => {
+            let last = path.path.segments.last().expect("Type paths always contain at least one segment");
+            let written = last.ident.to_string();
+            let translated = match written.as_str() {
+                "bool" => "Bool",
+                "i8" => "Int8",
+                "i16" => "Int16",
+                "i32" => "Int32",
+                "i64" => "Int64",
+                "u8" => "UInt8",
+                "u16" => "UInt16",
+                "u32" => "UInt32",
+                "u64" => "UInt64",
+                "f32" => "Float32",
+                "f64" => "Float64",
+                "String" | "str" => "Text",
+                "Vec" => {
+                    return match first_type_argument(&last.arguments) {
+                        Some(Type::Path(inner)) if inner.path.is_ident("u8") => String::from("Data"),
+                        Some(inner) => format!("List({})",schema_type(inner)),
+                        None => String::from("AnyPointer"),
+                    };
+                },
+                other => other,
+            };
+            String::from(translated)
+        },
+        _ => String::from("AnyPointer"),
+    }
+}
+/// Returns the first type inside the angle brackets of a path segment, if there is one
+fn first_type_argument(arguments: &PathArguments) -> Option<&Type> {
+    if let PathArguments::AngleBracketed(bracketed) = arguments {
+        for argument in &bracketed.args {
+            if let GenericArgument::Type(inner) = argument {
+                return Some(inner);
+            }
+        }
+    }
+    None
+}
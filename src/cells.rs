@@ -0,0 +1,119 @@
+/*
+Copyright 2024 Benjamin Richcreek
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+//! Generation of interior-mutability wrappers around each slot
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Type,Ident,parse_quote};
+use syn::parse::{Parse,ParseStream};
+use crate::{Layout,element_bound};
+/// The wrapper chosen with the `cells` option
+#[derive(Clone,Copy)]
+pub(crate) enum Cells {
+    /// [`Cell`](std::cell::Cell), for element types that are cheap to copy or replace
+    Cell,
+    /// [`RefCell`](std::cell::RefCell), for element types that need to be borrowed in place
+    RefCell,
+}
+impl Parse for Cells {
+    fn parse(input: ParseStream) -> Result<Self,syn::Error> {
+        let kind: Ident = input.parse()?;
+        match kind.to_string().as_str() {
+            "cell" => Ok(Cells::Cell),
+            "refcell" => Ok(Cells::RefCell),
+            _ => Err(syn::Error::new(kind.span(),"the `cells` option should be either `cell` or `refcell`")),
+        }
+    }
+}
+impl Cells {
+    /// Wraps the element type in the chosen cell
+    pub(crate) fn wrap(self, element: &Type) -> Type {
+        match self {
+            Cells::Cell => parse_quote!(::std::cell::Cell<#element>),
+            Cells::RefCell => parse_quote!(::std::cell::RefCell<#element>),
+        }
+    }
+}
+/// Creates the index-based helpers for reading and writing slots through shared references
+///
+/// With the `no_panic` option, the helpers of `cells = refcell` also return `None` instead of panicking when the slot is already borrowed.
+pub(crate) fn generate(layout: &Layout, cells: Cells, element: &Type) -> TokenStream {
+    let name = &layout.structure.ident;
+    let (impl_generics, type_generics, where_clause) = layout.structure.generics.split_for_impl();
+    let idents = layout.idents;
+    let indices: Vec<u32> = (0..idents.len() as u32).collect();
+    let cell = layout.unwrap_index(quote! {
+        match index {
+            #(#indices => ::std::option::Option::Some(&self.#idents),)*
+            _ => ::std::option::Option::None,
+        }
+    });
+    let index_doc = layout.checked_doc("`index` is out of range");
+    let borrow_doc = layout.checked_doc("`index` is out of range or the slot is currently mutably borrowed");
+    let borrow_mut_doc = layout.checked_doc("`index` is out of range or the slot is currently borrowed");
+    let replaced = layout.checked_output(quote!(#element));
+    let readers = match cells {
+        Cells::Cell => element_bound(element,quote!(::std::marker::Copy)).map(|copy_bound| {
+            let copied = layout.checked_return(quote!(cell.get()));
+            quote! {
+                /// Returns a copy of the value in the slot at `index`
+                #index_doc
+                pub fn get_value(&self, index: u32) #replaced #copy_bound {
+                    let cell = #cell;
+                    #copied
+                }
+            }
+        }).unwrap_or_default(),
+        Cells::RefCell => {
+            let borrowed = layout.checked_output(quote!(::std::cell::Ref<'_,#element>));
+            let borrowed_mut = layout.checked_output(quote!(::std::cell::RefMut<'_,#element>));
+            let (borrow, borrow_mut) = if layout.no_panic {
+                (quote!(cell.try_borrow().ok()),quote!(cell.try_borrow_mut().ok()))
+            } else {
+                (quote!(cell.borrow()),quote!(cell.borrow_mut()))
+            };
+            quote! {
+                /// Immutably borrows the value in the slot at `index`
+                #borrow_doc
+                pub fn borrow_slot(&self, index: u32) #borrowed {
+                    let cell = #cell;
+                    #borrow
+                }
+                /// Mutably borrows the value in the slot at `index`
+                #borrow_mut_doc
+                pub fn borrow_slot_mut(&self, index: u32) #borrowed_mut {
+                    let cell = #cell;
+                    #borrow_mut
+                }
+            }
+        },
+    };
+    let (replace, replace_doc) = match cells {
+        Cells::RefCell if layout.no_panic => (quote!(cell.try_borrow_mut().ok().map(|mut slot| ::std::mem::replace(&mut *slot,value))),&borrow_mut_doc),
+        Cells::RefCell => (quote!(cell.replace(value)),&borrow_mut_doc),
+        Cells::Cell => (layout.checked_return(quote!(cell.replace(value))),&index_doc),
+    };
+    quote! {
+        impl #impl_generics #name #type_generics #where_clause {
+            #readers
+            /// Replaces the value in the slot at `index` through a shared reference, returning the old value
+            #replace_doc
+            pub fn set_value(&self, index: u32, value: #element) #replaced {
+                let cell = #cell;
+                #replace
+            }
+        }
+    }
+}
@@ -0,0 +1,305 @@
+/*
+Copyright 2024 Benjamin Richcreek
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+//! Generation of checksummed [`Serialize`](https://docs.rs/serde/latest/serde/trait.Serialize.html) and [`Deserialize`](https://docs.rs/serde/latest/serde/trait.Deserialize.html) implementations
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Ident,parse_quote};
+use crate::{Layout,with_lifetime,deserialize};
+/// The key the checksum is serialized under. Key schemes that would give a slot this key are rejected when the attribute is expanded.
+pub(crate) const CHECKSUM_KEY: &str = "checksum";
+/// Creates a `checksum` method plus [`Serialize`](https://docs.rs/serde/latest/serde/trait.Serialize.html) and [`Deserialize`](https://docs.rs/serde/latest/serde/trait.Deserialize.html)
+/// implementations that write and verify the checksum
+pub(crate) fn generate(layout: &Layout) -> TokenStream {
+    let name = &layout.structure.ident;
+    let generics = &layout.structure.generics;
+    let (_, type_generics, where_clause) = generics.split_for_impl();
+    let tipe = layout.field_type;
+    let idents = layout.idents;
+    let names = layout.names;
+    let locals = deserialize::slot_locals(layout);
+    let carried_locals = deserialize::carried_locals(layout);
+    let carried_arms = deserialize::carried_arms(layout);
+    let slot_lookup = deserialize::slot_lookup(layout,&quote! {});
+    let slot_arms = deserialize::slot_arms(layout,&quote! {});
+    let ignore_arm = deserialize::ignore_arm();
+    let assembled = deserialize::assemble(layout);
+    let (carried, carried_keys): (Vec<&Ident>,Vec<&String>) = deserialize::written(layout).map(|(carried, key)| (&carried.ident,key)).unzip();
+    let total = names.len() + carried.len() + 1;
+    let serializable = layout.with_serde_bound(&deserialize::with_carried_bound(layout,generics,&quote!(::serde::Serialize)),parse_quote!(#tipe: ::serde::Serialize));
+    let (serialize_impl_generics, _, serialize_where_clause) = serializable.split_for_impl();
+    let deserializable = layout.with_serde_bound(&deserialize::with_carried_bound(layout,&with_lifetime(&serializable,"'__de"),&quote!(::serde::Deserialize<'__de>)),parse_quote!(#tipe: ::serde::Deserialize<'__de>));
+    let (deserialize_impl_generics, _, deserialize_where_clause) = deserializable.split_for_impl();
+    let expecting = format!("a map containing every key of {} and a checksum",name);
+    let serialize_body = layout.instrument("serialize",quote! { ::std::result::Result<__S::Ok,__S::Error> },quote! {
+        use ::serde::ser::SerializeMap;
+        let mut map = serializer.serialize_map(::std::option::Option::Some(#total))?;
+        #(map.serialize_entry(#names,&self.#idents)?;)*
+        #(map.serialize_entry(#carried_keys,&self.#carried)?;)*
+        map.serialize_entry(#CHECKSUM_KEY,&self.faux_checksum().map_err(<__S::Error as ::serde::ser::Error>::custom)?)?;
+        map.end()
+    });
+    let deserialize_body = layout.instrument("deserialize",quote! { ::std::result::Result<Self,__D::Error> },quote! {
+        deserializer.deserialize_map(__FauxVisitor(::std::marker::PhantomData))
+    });
+    let calculator = calculator();
+    let method = if layout.no_panic {
+        quote! {
+            /// Computes the CRC-32 checksum of every slot, in index order, followed by every other field that is serialized, or returns `None` if a field fails to serialize
+            pub fn checksum(&self) -> ::std::option::Option<u32> {
+                self.faux_checksum().ok()
+            }
+        }
+    } else {
+        quote! {
+            /// Computes the CRC-32 checksum of every slot, in index order, followed by every other field that is serialized
+            ///
+            /// # Panics
+            /// Panics if a field fails to serialize
+            pub fn checksum(&self) -> u32 {
+                self.faux_checksum().expect("Every field of a checksummed psuedo-array should serialize")
+            }
+        }
+    };
+    quote! {
+        const _: () = {
+            #calculator
+            impl #serialize_impl_generics #name #type_generics #serialize_where_clause {
+                #method
+                fn faux_checksum(&self) -> ::std::result::Result<u32,__FauxChecksumError> {
+                    let mut calculator = __FauxCrc32(u32::MAX);
+                    #(::serde::Serialize::serialize(&self.#idents,&mut calculator)?;)*
+                    #(::serde::Serialize::serialize(&self.#carried,&mut calculator)?;)*
+                    ::std::result::Result::Ok(!calculator.0)
+                }
+            }
+            impl #serialize_impl_generics ::serde::Serialize for #name #type_generics #serialize_where_clause {
+                fn serialize<__S: ::serde::Serializer>(&self, serializer: __S) -> ::std::result::Result<__S::Ok,__S::Error> {
+                    #serialize_body
+                }
+            }
+            struct __FauxVisitor #generics (::std::marker::PhantomData<fn() -> #name #type_generics>) #where_clause;
+            impl #deserialize_impl_generics ::serde::de::Visitor<'__de> for __FauxVisitor #type_generics #deserialize_where_clause {
+                type Value = #name #type_generics;
+                fn expecting(&self, formatter: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+                    formatter.write_str(#expecting)
+                }
+                fn visit_map<__A: ::serde::de::MapAccess<'__de>>(self, mut map: __A) -> ::std::result::Result<Self::Value,__A::Error> {
+                    #locals
+                    #carried_locals
+                    let mut checksum: ::std::option::Option<u32> = ::std::option::Option::None;
+                    while let ::std::option::Option::Some(key) = map.next_key::<::std::string::String>()? {
+                        #slot_lookup
+                        match key.as_str() {
+                            #slot_arms
+                            #carried_arms
+                            #CHECKSUM_KEY => {
+                                if checksum.is_some() {
+                                    return ::std::result::Result::Err(<__A::Error as ::serde::de::Error>::duplicate_field(#CHECKSUM_KEY));
+                                }
+                                checksum = ::std::option::Option::Some(map.next_value()?);
+                            },
+                            #ignore_arm
+                        }
+                    }
+                    let value = #assembled;
+                    let written = checksum.ok_or_else(|| <__A::Error as ::serde::de::Error>::missing_field(#CHECKSUM_KEY))?;
+                    let computed = value.faux_checksum().map_err(<__A::Error as ::serde::de::Error>::custom)?;
+                    if written != computed {
+                        return ::std::result::Result::Err(<__A::Error as ::serde::de::Error>::custom(::std::format!("checksum mismatch: the document recorded {:08x} but its fields add up to {:08x}",written,computed)));
+                    }
+                    ::std::result::Result::Ok(value)
+                }
+            }
+            impl #deserialize_impl_generics ::serde::Deserialize<'__de> for #name #type_generics #deserialize_where_clause {
+                fn deserialize<__D: ::serde::Deserializer<'__de>>(deserializer: __D) -> ::std::result::Result<Self,__D::Error> {
+                    #deserialize_body
+                }
+            }
+        };
+    }
+}
+/// Creates `__FauxCrc32`, a bitwise CRC-32 (IEEE) calculator fed through [`Serialize`](https://docs.rs/serde/latest/serde/trait.Serialize.html), and `__FauxChecksumError`, the error it reports
+///
+/// Values are fed in a fixed encoding that doesn't depend on the platform: numbers as their little-endian bytes (floats through their bits), `bool`s as one byte, `char`s as a `u32`, and strings and
+/// byte strings as their length as a `u64` followed by their bytes. Options are fed as a `0` byte or a `1` byte followed by their value, enum variants start with their index as a `u32`, and sequences
+/// and maps start with their length as a `u64` (or `u64::MAX` when the length isn't known in advance). Structs, tuples, and field names add nothing beyond their values.
+fn calculator() -> TokenStream {
+    let compound = |trait_name: TokenStream, method: TokenStream| quote! {
+        impl ::serde::ser::#trait_name for &mut __FauxCrc32 {
+            type Ok = ();
+            type Error = __FauxChecksumError;
+            #method
+            fn end(self) -> ::std::result::Result<(),__FauxChecksumError> {
+                ::std::result::Result::Ok(())
+            }
+        }
+    };
+    let element = |method: TokenStream| quote! {
+        fn #method<V: ?::std::marker::Sized + ::serde::Serialize>(&mut self, value: &V) -> ::std::result::Result<(),__FauxChecksumError> {
+            value.serialize(&mut **self)
+        }
+    };
+    let field = |method: TokenStream| quote! {
+        fn #method<V: ?::std::marker::Sized + ::serde::Serialize>(&mut self, _: &'static str, value: &V) -> ::std::result::Result<(),__FauxChecksumError> {
+            value.serialize(&mut **self)
+        }
+    };
+    let sequence = compound(quote!(SerializeSeq),element(quote!(serialize_element)));
+    let tuple = compound(quote!(SerializeTuple),element(quote!(serialize_element)));
+    let tuple_struct = compound(quote!(SerializeTupleStruct),element(quote!(serialize_field)));
+    let tuple_variant = compound(quote!(SerializeTupleVariant),element(quote!(serialize_field)));
+    let map = compound(quote!(SerializeMap),{
+        let key = element(quote!(serialize_key));
+        let value = element(quote!(serialize_value));
+        quote! { #key #value }
+    });
+    let structure = compound(quote!(SerializeStruct),field(quote!(serialize_field)));
+    let struct_variant = compound(quote!(SerializeStructVariant),field(quote!(serialize_field)));
+    let numbers = [quote!(i8),quote!(i16),quote!(i32),quote!(i64),quote!(i128),quote!(u8),quote!(u16),quote!(u32),quote!(u64),quote!(u128)].map(|number| {
+        let method = quote::format_ident!("serialize_{}",number.to_string());
+        quote! {
+            fn #method(self, value: #number) -> ::std::result::Result<(),__FauxChecksumError> {
+                self.write(&value.to_le_bytes());
+                ::std::result::Result::Ok(())
+            }
+        }
+    });
+    quote! {
+        struct __FauxCrc32(u32);
+        impl __FauxCrc32 {
+            fn write(&mut self, bytes: &[u8]) {
+                for byte in bytes {
+                    self.0 ^= *byte as u32;
+                    let mut bit: u8 = 0;
+                    while bit < 8 {
+                        let mask = (self.0 & 1).wrapping_neg();
+                        self.0 = (self.0 >> 1) ^ (0xEDB8_8320 & mask);
+                        bit += 1;
+                    }
+                }
+            }
+            fn write_length(&mut self, length: ::std::option::Option<usize>) {
+                self.write(&length.map_or(u64::MAX,|length| length as u64).to_le_bytes());
+            }
+        }
+        #[derive(Debug)]
+        struct __FauxChecksumError(::std::string::String);
+        impl ::std::fmt::Display for __FauxChecksumError {
+            fn fmt(&self, formatter: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+                formatter.write_str(&self.0)
+            }
+        }
+        impl ::std::error::Error for __FauxChecksumError {}
+        impl ::serde::ser::Error for __FauxChecksumError {
+            fn custom<M: ::std::fmt::Display>(message: M) -> Self {
+                __FauxChecksumError(::std::string::ToString::to_string(&message))
+            }
+        }
+        impl ::serde::Serializer for &mut __FauxCrc32 {
+            type Ok = ();
+            type Error = __FauxChecksumError;
+            type SerializeSeq = Self;
+            type SerializeTuple = Self;
+            type SerializeTupleStruct = Self;
+            type SerializeTupleVariant = Self;
+            type SerializeMap = Self;
+            type SerializeStruct = Self;
+            type SerializeStructVariant = Self;
+            #(#numbers)*
+            fn serialize_bool(self, value: bool) -> ::std::result::Result<(),__FauxChecksumError> {
+                self.write(&[value as u8]);
+                ::std::result::Result::Ok(())
+            }
+            fn serialize_f32(self, value: f32) -> ::std::result::Result<(),__FauxChecksumError> {
+                self.write(&value.to_bits().to_le_bytes());
+                ::std::result::Result::Ok(())
+            }
+            fn serialize_f64(self, value: f64) -> ::std::result::Result<(),__FauxChecksumError> {
+                self.write(&value.to_bits().to_le_bytes());
+                ::std::result::Result::Ok(())
+            }
+            fn serialize_char(self, value: char) -> ::std::result::Result<(),__FauxChecksumError> {
+                self.write(&(value as u32).to_le_bytes());
+                ::std::result::Result::Ok(())
+            }
+            fn serialize_str(self, value: &str) -> ::std::result::Result<(),__FauxChecksumError> {
+                self.serialize_bytes(value.as_bytes())
+            }
+            fn serialize_bytes(self, value: &[u8]) -> ::std::result::Result<(),__FauxChecksumError> {
+                self.write_length(::std::option::Option::Some(value.len()));
+                self.write(value);
+                ::std::result::Result::Ok(())
+            }
+            fn serialize_none(self) -> ::std::result::Result<(),__FauxChecksumError> {
+                self.write(&[0]);
+                ::std::result::Result::Ok(())
+            }
+            fn serialize_some<V: ?::std::marker::Sized + ::serde::Serialize>(self, value: &V) -> ::std::result::Result<(),__FauxChecksumError> {
+                self.write(&[1]);
+                value.serialize(self)
+            }
+            fn serialize_unit(self) -> ::std::result::Result<(),__FauxChecksumError> {
+                ::std::result::Result::Ok(())
+            }
+            fn serialize_unit_struct(self, _: &'static str) -> ::std::result::Result<(),__FauxChecksumError> {
+                ::std::result::Result::Ok(())
+            }
+            fn serialize_unit_variant(self, _: &'static str, index: u32, _: &'static str) -> ::std::result::Result<(),__FauxChecksumError> {
+                self.write(&index.to_le_bytes());
+                ::std::result::Result::Ok(())
+            }
+            fn serialize_newtype_struct<V: ?::std::marker::Sized + ::serde::Serialize>(self, _: &'static str, value: &V) -> ::std::result::Result<(),__FauxChecksumError> {
+                value.serialize(self)
+            }
+            fn serialize_newtype_variant<V: ?::std::marker::Sized + ::serde::Serialize>(self, _: &'static str, index: u32, _: &'static str, value: &V) -> ::std::result::Result<(),__FauxChecksumError> {
+                self.write(&index.to_le_bytes());
+                value.serialize(self)
+            }
+            fn serialize_seq(self, length: ::std::option::Option<usize>) -> ::std::result::Result<Self,__FauxChecksumError> {
+                self.write_length(length);
+                ::std::result::Result::Ok(self)
+            }
+            fn serialize_tuple(self, _: usize) -> ::std::result::Result<Self,__FauxChecksumError> {
+                ::std::result::Result::Ok(self)
+            }
+            fn serialize_tuple_struct(self, _: &'static str, _: usize) -> ::std::result::Result<Self,__FauxChecksumError> {
+                ::std::result::Result::Ok(self)
+            }
+            fn serialize_tuple_variant(self, _: &'static str, index: u32, _: &'static str, _: usize) -> ::std::result::Result<Self,__FauxChecksumError> {
+                self.write(&index.to_le_bytes());
+                ::std::result::Result::Ok(self)
+            }
+            fn serialize_map(self, length: ::std::option::Option<usize>) -> ::std::result::Result<Self,__FauxChecksumError> {
+                self.write_length(length);
+                ::std::result::Result::Ok(self)
+            }
+            fn serialize_struct(self, _: &'static str, _: usize) -> ::std::result::Result<Self,__FauxChecksumError> {
+                ::std::result::Result::Ok(self)
+            }
+            fn serialize_struct_variant(self, _: &'static str, index: u32, _: &'static str, _: usize) -> ::std::result::Result<Self,__FauxChecksumError> {
+                self.write(&index.to_le_bytes());
+                ::std::result::Result::Ok(self)
+            }
+        }
+        #sequence
+        #tuple
+        #tuple_struct
+        #tuple_variant
+        #map
+        #structure
+        #struct_variant
+    }
+}
@@ -0,0 +1,75 @@
+/*
+Copyright 2024 Benjamin Richcreek
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+//! Generation of companion structs that mirror the keys of a psuedo-array
+use proc_macro2::{TokenStream,Span};
+use quote::{quote,format_ident};
+use syn::{Type,Ident,LitStr,Token,Lifetime};
+use syn::parse::{Parse,ParseStream};
+use crate::Layout;
+/// The arguments of the `companion` option
+pub(crate) struct Companion {
+    element: Type,
+    suffix: String,
+}
+impl Parse for Companion {
+    fn parse(input: ParseStream) -> Result<Self,syn::Error> {
+        let element: Type = input.parse()?;
+        let mut suffix = String::from("Meta");
+        if input.peek(Token![,]) {
+            input.parse::<Token![,]>()?;
+            let option: Ident = input.parse()?;
+            if option != "suffix" {
+                return Err(syn::Error::new(option.span(),"`suffix` is the only option of `companion`"));
+            }
+            input.parse::<Token![=]>()?;
+            suffix = input.parse::<LitStr>()?.value();
+        }
+        Ok(Companion {
+            element,
+            suffix,
+        })
+    }
+}
+/// Creates the companion [`struct`](https://doc.rust-lang.org/1.58.1/std/keyword.struct.html) along with the `zip` and `zip_mut` methods
+pub(crate) fn generate(layout: &Layout, companion: &Companion) -> TokenStream {
+    let name = &layout.structure.ident;
+    let attributes = &layout.structure.attrs;
+    let visibility = &layout.structure.vis;
+    let (impl_generics, type_generics, where_clause) = layout.structure.generics.split_for_impl();
+    let mirror = format_ident!("{}{}",name,companion.suffix);
+    let element = &companion.element;
+    let tipe = layout.field_type;
+    let idents = layout.idents;
+    let slot_attributes = layout.slot_attributes;
+    let lifetime = Lifetime::new("'faux_zip",Span::call_site());
+    quote! {
+        #(#attributes)*
+        #[allow(non_camel_case_types)]
+        #visibility struct #mirror {
+            #(#slot_attributes #idents : #element),*
+        }
+        impl #impl_generics #name #type_generics #where_clause {
+            /// Pairs each slot with the matching slot of `companion`, in index order
+            pub fn zip<#lifetime>(&#lifetime self, companion: &#lifetime #mirror) -> impl ::std::iter::DoubleEndedIterator<Item = (&#lifetime #tipe,&#lifetime #element)> + ::std::iter::ExactSizeIterator {
+                ::std::vec![#((&self.#idents,&companion.#idents)),*].into_iter()
+            }
+            /// Pairs each slot mutably with the matching slot of `companion`, in index order
+            pub fn zip_mut<#lifetime>(&#lifetime mut self, companion: &#lifetime mut #mirror) -> impl ::std::iter::DoubleEndedIterator<Item = (&#lifetime mut #tipe,&#lifetime mut #element)> + ::std::iter::ExactSizeIterator {
+                ::std::vec![#((&mut self.#idents,&mut companion.#idents)),*].into_iter()
+            }
+        }
+    }
+}
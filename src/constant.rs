@@ -0,0 +1,40 @@
+/*
+Copyright 2024 Benjamin Richcreek
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+//! Generation of a compile-time default instance
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::Expr;
+use crate::Layout;
+/// Creates the `DEFAULT` associated constant, with `value` in every slot
+///
+/// # Errors
+/// Returns an error if the struct has fields besides the slots, since those fields can't be given values in a constant
+pub(crate) fn generate(layout: &Layout, value: &Expr) -> Result<TokenStream,syn::Error> {
+    let name = &layout.structure.ident;
+    if !layout.extra_fields.is_empty() {
+        return Err(syn::Error::new_spanned(value,format!("the const_default option can't be used on {}, because it has fields besides the slots that can't be initialized in a constant",name)));
+    }
+    let (impl_generics, type_generics, where_clause) = layout.structure.generics.split_for_impl();
+    let idents = layout.idents;
+    Ok(quote! {
+        impl #impl_generics #name #type_generics #where_clause {
+            /// An instance built at compile time, with the same value in every slot
+            pub const DEFAULT: Self = #name {
+                #(#idents: #value),*
+            };
+        }
+    })
+}
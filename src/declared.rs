@@ -0,0 +1,94 @@
+/*
+Copyright 2024 Benjamin Richcreek
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+//! Handling of the fields declared in the body of a psuedo-array's struct
+use syn::{Field,Fields,Ident,ItemStruct,LitStr,Token,Expr};
+use syn::token::Paren;
+use crate::{Arguments,reserved};
+use crate::deserialize::Carried;
+/// Collects the named fields declared in the body of `structure`, which are kept in front of the generated fields
+///
+/// # Errors
+/// Returns an error if `structure` is a tuple struct with fields, since those can't be kept alongside generated fields
+pub(crate) fn fields(structure: &ItemStruct) -> Result<Vec<&Field>,syn::Error> {
+    match &structure.fields {
+        Fields::Named(named) => Ok(named.named.iter().collect()),
+        Fields::Unnamed(unnamed) if !unnamed.unnamed.is_empty() => Err(syn::Error::new_spanned(unnamed,format!("the fields of {} should be named, since they are kept alongside the generated fields, which are named. Write it as `struct {}();` to generate the slots as unnamed fields instead",structure.ident,structure.ident))),
+        _ => Ok(Vec::new()),
+    }
+}
+/// Finds the key `field` is serialized under, which is its name unless it has a `#[serde(rename = "...")]` attribute
+fn key(field: &Field) -> Option<String> {
+    let mut key = field.ident.as_ref()?.to_string().trim_start_matches("r#").to_owned();
+    for attribute in field.attrs.iter().filter(|attribute| attribute.path().is_ident("serde")) {
+        let _ = attribute.parse_nested_meta(|meta| {
+            if meta.path.is_ident("rename") {
+                key = meta.value()?.parse::<LitStr>()?.value();
+            } else if meta.input.peek(Token![=]) {
+                meta.value()?.parse::<Expr>()?;
+            } else if meta.input.peek(Paren) {
+                let _nested;
+                syn::parenthesized!(_nested in meta.input);
+            }
+            Ok(())
+        });
+    }
+    Some(key)
+}
+/// Rejects declared fields whose names match a generated field, or whose keys match a generated key
+///
+/// # Errors
+/// Returns an error pointing at the first declared field that collides with a generated one
+pub(crate) fn check(declared: &[&Field], generated: &[&Ident], arguments: &Arguments) -> Result<(),syn::Error> {
+    for field in declared {
+        let Some(ident) = &field.ident else {
+            continue;
+        };
+        if generated.contains(&ident) {
+            return Err(syn::Error::new(ident.span(),format!("the field {} has the same name as a generated field. Rename it, or remove it and use the generated field instead",ident)));
+        }
+        if let Some(key) = key(field) {
+            if reserved::generates(arguments,&key) {
+                return Err(syn::Error::new(ident.span(),format!("the field {} is serialized under the key \"{}\", which is also generated. Rename it, or give it a different key with #[serde(rename = \"...\")]",ident,key)));
+            }
+        }
+    }
+    Ok(())
+}
+/// Describes how the serde implementations this attribute generates write and read `field`, following its `rename`, `alias`, `skip`, and `default` serde attributes
+///
+/// # Errors
+/// Returns an error pointing at the first serde attribute of `field` that generated serde implementations can't follow, such as `flatten` or `with`
+pub(crate) fn carried(field: &Field) -> Result<Carried,syn::Error> {
+    let ident = field.ident.clone().expect("Declared fields are always named");
+    let mut carried = Carried::keyed(ident.clone(),field.ty.clone(),ident.to_string().trim_start_matches("r#").to_owned());
+    for attribute in field.attrs.iter().filter(|attribute| attribute.path().is_ident("serde")) {
+        attribute.parse_nested_meta(|meta| {
+            if meta.path.is_ident("rename") {
+                carried.key = Some(meta.value()?.parse::<LitStr>()?.value());
+            } else if meta.path.is_ident("alias") {
+                carried.aliases.push(meta.value()?.parse::<LitStr>()?.value());
+            } else if meta.path.is_ident("skip") {
+                carried = Carried::skipped(ident.clone(),field.ty.clone());
+            } else if meta.path.is_ident("default") && !meta.input.peek(Token![=]) {
+                carried.default = true;
+            } else {
+                return Err(meta.error(format!("the field {} has a serde attribute that the serde implementations generated by faux_array can't follow. Only `rename`, `alias`, `skip`, and `default` are supported",ident)));
+            }
+            Ok(())
+        })?;
+    }
+    Ok(carried)
+}
@@ -0,0 +1,40 @@
+/*
+Copyright 2024 Benjamin Richcreek
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+//! Generation of a [`Default`] implementation for element types that implement it
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::parse_quote;
+use crate::{Layout,with_bound};
+/// Creates an implementation of [`Default`] filling every slot, and every other field, with its default value
+pub(crate) fn generate(layout: &Layout) -> TokenStream {
+    let name = &layout.structure.ident;
+    let (_, type_generics, _) = layout.structure.generics.split_for_impl();
+    let tipe = layout.field_type;
+    let bounded = with_bound(&layout.structure.generics,parse_quote!(for<'faux_bound> #tipe: ::std::default::Default));
+    let (impl_generics, _, where_clause) = bounded.split_for_impl();
+    let idents = layout.idents;
+    let extra_fields = layout.extra_fields;
+    quote! {
+        impl #impl_generics ::std::default::Default for #name #type_generics #where_clause {
+            fn default() -> Self {
+                #name {
+                    #(#idents: <#tipe as ::std::default::Default>::default(),)*
+                    #(#extra_fields: ::std::default::Default::default(),)*
+                }
+            }
+        }
+    }
+}
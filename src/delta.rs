@@ -0,0 +1,77 @@
+/*
+Copyright 2024 Benjamin Richcreek
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+//! Generation of delta serialization against a baseline instance
+use proc_macro2::{TokenStream,Span};
+use quote::{quote,format_ident};
+use syn::{Lifetime,parse_quote};
+use crate::{Layout,with_lifetime,with_bound};
+/// Creates the `Delta` wrapper type along with the `delta` and `apply_delta` methods
+pub(crate) fn generate(layout: &Layout) -> TokenStream {
+    let name = &layout.structure.ident;
+    let visibility = &layout.structure.vis;
+    let delta_name = format_ident!("{}Delta",name);
+    let (impl_generics, type_generics, where_clause) = layout.structure.generics.split_for_impl();
+    let lifetime = Lifetime::new("'faux_delta",Span::call_site());
+    let delta_generics = with_lifetime(&layout.structure.generics,"'faux_delta");
+    let (_, delta_type_generics, delta_where_clause) = delta_generics.split_for_impl();
+    let tipe = layout.field_type;
+    let serializable = layout.with_serde_bound(&with_bound(&delta_generics,parse_quote!(#tipe: ::std::cmp::PartialEq)),parse_quote!(#tipe: ::serde::Serialize));
+    let (serialize_impl_generics, _, serialize_where_clause) = serializable.split_for_impl();
+    let idents = layout.idents;
+    let names = layout.names;
+    let missing = format!("is not a key of {}",name);
+    quote! {
+        /// A view of the slots that differ between two instances, which serializes only those slots
+        #visibility struct #delta_name #delta_generics #delta_where_clause {
+            current: &#lifetime #name #type_generics,
+            baseline: &#lifetime #name #type_generics,
+        }
+        impl #serialize_impl_generics ::serde::Serialize for #delta_name #delta_type_generics #serialize_where_clause {
+            fn serialize<__S: ::serde::Serializer>(&self, serializer: __S) -> ::std::result::Result<__S::Ok,__S::Error> {
+                use ::serde::ser::SerializeMap;
+                let mut changed: usize = 0;
+                #(if self.current.#idents != self.baseline.#idents {
+                    changed += 1;
+                })*
+                let mut map = serializer.serialize_map(::std::option::Option::Some(changed))?;
+                #(if self.current.#idents != self.baseline.#idents {
+                    map.serialize_entry(#names,&self.current.#idents)?;
+                })*
+                map.end()
+            }
+        }
+        impl #impl_generics #name #type_generics #where_clause {
+            /// Returns a view that serializes only the slots of `self` that differ from `baseline`
+            pub fn delta<#lifetime>(&#lifetime self, baseline: &#lifetime Self) -> #delta_name #delta_type_generics {
+                #delta_name {
+                    current: self,
+                    baseline,
+                }
+            }
+            /// Overwrites each slot named in a map of keys to values, such as one produced by serializing a delta, leaving every other slot untouched
+            pub fn apply_delta<'__de, __D: ::serde::Deserializer<'__de>>(&mut self, deserializer: __D) -> ::std::result::Result<(),__D::Error> where #tipe: ::serde::Deserialize<'__de> {
+                let changes: ::std::collections::HashMap<::std::string::String,#tipe> = ::serde::Deserialize::deserialize(deserializer)?;
+                for (key, value) in changes {
+                    match key.as_str() {
+                        #(#names => self.#idents = value,)*
+                        _ => return ::std::result::Result::Err(<__D::Error as ::serde::de::Error>::custom(::std::format!("`{}` {}",key,#missing))),
+                    }
+                }
+                ::std::result::Result::Ok(())
+            }
+        }
+    }
+}
@@ -0,0 +1,208 @@
+/*
+Copyright 2024 Benjamin Richcreek
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+//! Pieces shared by the generated map visitors
+//!
+//! The snippets created here expect to be placed inside `visit_map` of a [`Visitor`](https://docs.rs/serde/latest/serde/de/trait.Visitor.html) whose map parameter is named `map` and has the type `__A`.
+//! Fields besides the slots, such as declared fields, are read under their own keys, so a document written by the serialization of the same [`struct`](https://doc.rust-lang.org/1.58.1/std/keyword.struct.html)
+//! reads back whole.
+use proc_macro2::TokenStream;
+use quote::{quote,format_ident};
+use syn::{Generics,Ident,Type,WherePredicate,parse_quote};
+use crate::{Layout,with_bound};
+/// A field besides the slots, which generated serde implementations write and read alongside the slots
+pub(crate) struct Carried {
+    pub(crate) ident: Ident,
+    pub(crate) tipe: Type,
+    /// The key the field is serialized under, or `None` if it is skipped and filled using [`Default`]
+    pub(crate) key: Option<String>,
+    /// The extra keys the field is read from
+    pub(crate) aliases: Vec<String>,
+    /// Whether a missing key fills the field using [`Default`] instead of failing
+    pub(crate) default: bool,
+}
+impl Carried {
+    /// Describes a field that is skipped by serialization, and filled using [`Default`] during deserialization
+    pub(crate) fn skipped(ident: Ident, tipe: Type) -> Self {
+        Carried {
+            ident,
+            tipe,
+            key: None,
+            aliases: Vec::new(),
+            default: true,
+        }
+    }
+    /// Describes a field that is serialized under `key`, and must be present during deserialization
+    pub(crate) fn keyed(ident: Ident, tipe: Type, key: String) -> Self {
+        Carried {
+            ident,
+            tipe,
+            key: Some(key),
+            aliases: Vec::new(),
+            default: false,
+        }
+    }
+    /// Names the local that the value of this field is read into
+    fn local(&self) -> Ident {
+        format_ident!("faux_{}",self.ident)
+    }
+}
+/// Iterates over the fields besides the slots that are written and read, along with their keys
+pub(crate) fn written<'a>(layout: &'a Layout) -> impl Iterator<Item = (&'a Carried,&'a String)> {
+    layout.carried.iter().filter_map(|carried| Some((carried,carried.key.as_ref()?)))
+}
+/// Creates a bound of `bound` on the type of every field besides the slots that is written and read, unless bounds were chosen with the `bound` option
+pub(crate) fn carried_predicates(layout: &Layout, bound: &TokenStream) -> Vec<WherePredicate> {
+    if layout.serde_bounds.is_some() {
+        return Vec::new();
+    }
+    written(layout).map(|(carried, _)| {
+        let tipe = &carried.tipe;
+        parse_quote!(#tipe: #bound)
+    }).collect()
+}
+/// Adds the bounds created by [`carried_predicates`] to `generics`
+pub(crate) fn with_carried_bound(layout: &Layout, generics: &Generics, bound: &TokenStream) -> Generics {
+    carried_predicates(layout,bound).into_iter().fold(generics.clone(),|generics, predicate| with_bound(&generics,predicate))
+}
+/// Declares one empty [`Option`] per field besides the slots that is read, to read values into
+pub(crate) fn carried_locals(layout: &Layout) -> TokenStream {
+    let locals = written(layout).map(|(carried, _)| carried.local());
+    let types = written(layout).map(|(carried, _)| &carried.tipe);
+    quote! {
+        #(let mut #locals: ::std::option::Option<#types> = ::std::option::Option::None;)*
+    }
+}
+/// Creates one `match` arm per field besides the slots that is read, rejecting duplicates
+pub(crate) fn carried_arms(layout: &Layout) -> TokenStream {
+    let arms = written(layout).map(|(carried, key)| {
+        let local = carried.local();
+        let aliases = &carried.aliases;
+        quote! {
+            #key #(| #aliases)* => {
+                if #local.is_some() {
+                    return ::std::result::Result::Err(<__A::Error as ::serde::de::Error>::duplicate_field(#key));
+                }
+                #local = ::std::option::Option::Some(map.next_value()?);
+            },
+        }
+    });
+    quote! { #(#arms)* }
+}
+/// Declares one empty [`Option`] per slot, named after the slot, to read values into
+pub(crate) fn slot_locals(layout: &Layout) -> TokenStream {
+    let idents = layout.idents;
+    let tipe = layout.field_type;
+    quote! {
+        #(let mut #idents: ::std::option::Option<#tipe> = ::std::option::Option::None;)*
+    }
+}
+/// Creates the block that reads the value of the slot `ident`, serialized under `key`, rejecting duplicates
+///
+/// `after_slot` runs after the value is read, with `index` bound to the slot's index as a [`u32`] and `key` bound to its key.
+fn read_slot(ident: &Ident, key: &str, index: u32, after_slot: &TokenStream) -> TokenStream {
+    quote! {
+        {
+            if #ident.is_some() {
+                return ::std::result::Result::Err(<__A::Error as ::serde::de::Error>::duplicate_field(#key));
+            }
+            #ident = ::std::option::Option::Some(map.next_value()?);
+            #[allow(unused_variables)]
+            let index: u32 = #index;
+            #[allow(unused_variables)]
+            let key: &str = #key;
+            #after_slot
+        }
+    }
+}
+/// Creates one `match` arm per key that reads the value of a slot, rejecting duplicates
+///
+/// Each arm also matches the aliases of its key. When the `key_table` option is given, the keys themselves are looked up by [`slot_lookup`] instead, so only the aliases are matched here.
+/// `after_slot` runs after each value is read, as in [`read_slot`].
+pub(crate) fn slot_arms(layout: &Layout, after_slot: &TokenStream) -> TokenStream {
+    let arms = layout.idents.iter().zip(layout.names).zip(layout.aliases).zip(0..).filter_map(|(((ident, key), aliases), index)| {
+        let read = read_slot(ident,key,index,after_slot);
+        if !layout.key_table {
+            Some(quote! { #key #(| #aliases)* => #read, })
+        } else if aliases.is_empty() {
+            None
+        } else {
+            Some(quote! { #(#aliases)|* => #read, })
+        }
+    });
+    quote! { #(#arms)* }
+}
+/// Creates a statement that looks up `key` in the sorted table generated by the `key_table` option, reading the value of the slot it finds and moving on to the next key, or nothing if the option wasn't given
+///
+/// `after_slot` runs after each value is read, as in [`read_slot`].
+pub(crate) fn slot_lookup(layout: &Layout, after_slot: &TokenStream) -> TokenStream {
+    if !layout.key_table {
+        return quote! {};
+    }
+    let name = &layout.structure.ident;
+    let (_, type_generics, _) = layout.structure.generics.split_for_impl();
+    let reads = layout.idents.iter().zip(layout.names).zip(0..).map(|((ident, key), index)| read_slot(ident,key,index,after_slot));
+    let indices = 0..layout.idents.len() as u32;
+    quote! {
+        if let ::std::option::Option::Some(index) = <#name #type_generics>::index_of(&key) {
+            match index {
+                #(#indices => #reads,)*
+                _ => {
+                    map.next_value::<::serde::de::IgnoredAny>()?;
+                },
+            }
+            continue;
+        }
+    }
+}
+/// Creates the `match` arm that skips the value of any key that isn't recognized
+pub(crate) fn ignore_arm() -> TokenStream {
+    quote! {
+        _ => {
+            map.next_value::<::serde::de::IgnoredAny>()?;
+        },
+    }
+}
+/// Builds the [`struct`](https://doc.rust-lang.org/1.58.1/std/keyword.struct.html) from the locals declared by [`slot_locals`] and [`carried_locals`], returning an error for the first slot or field that is missing
+///
+/// Like a derived implementation, a missing field whose type accepts a missing value (such as an [`Option`]) is filled with that value instead.
+pub(crate) fn assemble(layout: &Layout) -> TokenStream {
+    let name = &layout.structure.ident;
+    let idents = layout.idents;
+    let names = layout.names;
+    let extra_fields = layout.extra_fields.iter().map(|field| match layout.carried.iter().find(|carried| carried.ident == *field) {
+        Some(carried @ Carried { key: Some(key), .. }) => {
+            let local = carried.local();
+            let tipe = &carried.tipe;
+            if carried.default {
+                quote! { #field: #local.unwrap_or_default() }
+            } else {
+                quote! {
+                    #field: match #local {
+                        ::std::option::Option::Some(value) => value,
+                        ::std::option::Option::None => <#tipe as ::serde::Deserialize>::deserialize(::serde::de::IntoDeserializer::<'__de,__A::Error>::into_deserializer(())).map_err(|_| <__A::Error as ::serde::de::Error>::missing_field(#key))?,
+                    }
+                }
+            }
+        },
+        _ => quote! { #field: ::std::default::Default::default() },
+    });
+    quote! {
+        #name {
+            #(#idents: #idents.ok_or_else(|| <__A::Error as ::serde::de::Error>::missing_field(#names))?,)*
+            #(#extra_fields,)*
+        }
+    }
+}
@@ -0,0 +1,67 @@
+/*
+Copyright 2024 Benjamin Richcreek
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+//! Generation of the `KEY_DIGEST` constant and of assertions that pin it
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Type,Expr,Token};
+use syn::parse::{Parse,ParseStream};
+use structurray_keys::digest_keys;
+use crate::Layout;
+/// The arguments of `assert_key_digest!`, written as `Type, [u8; 32]`
+pub(crate) struct Pinned {
+    tipe: Type,
+    digest: Expr,
+}
+impl Parse for Pinned {
+    fn parse(input: ParseStream) -> Result<Self,syn::Error> {
+        let tipe: Type = input.parse()?;
+        input.parse::<Token![,]>()?;
+        let digest: Expr = input.parse()?;
+        input.parse::<Option<Token![,]>>()?;
+        Ok(Pinned {
+            tipe,
+            digest,
+        })
+    }
+}
+/// Creates the `KEY_DIGEST` associated constant
+pub(crate) fn generate(layout: &Layout) -> TokenStream {
+    let name = &layout.structure.ident;
+    let (impl_generics, type_generics, where_clause) = layout.structure.generics.split_for_impl();
+    let digest = digest_keys(layout.names);
+    quote! {
+        impl #impl_generics #name #type_generics #where_clause {
+            /// The SHA-256 digest of the key of every slot, in order
+            pub const KEY_DIGEST: [u8; 32] = [#(#digest),*];
+        }
+    }
+}
+/// Creates a constant item that fails to compile unless the `KEY_DIGEST` of `pinned.tipe` equals `pinned.digest`
+pub(crate) fn assert(pinned: &Pinned) -> TokenStream {
+    let tipe = &pinned.tipe;
+    let digest = &pinned.digest;
+    quote! {
+        const _: () = {
+            let actual: [u8; 32] = <#tipe>::KEY_DIGEST;
+            let pinned: [u8; 32] = #digest;
+            let mut looper = 0;
+            while looper < 32 {
+                assert!(actual[looper] == pinned[looper],"the keys of a psuedo-array no longer match their pinned digest");
+                looper += 1;
+            }
+        };
+    }
+}
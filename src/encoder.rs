@@ -0,0 +1,87 @@
+/*
+Copyright 2024 Benjamin Richcreek
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+//! Generation of serialization that creates the keys with a custom encoder
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::parse_quote;
+use crate::{Layout,with_lifetime};
+/// Creates [`Serialize`](https://docs.rs/serde/latest/serde/trait.Serialize.html) and [`Deserialize`](https://docs.rs/serde/latest/serde/trait.Deserialize.html) implementations that write each slot
+/// under the key `encoder` creates for its index, counting from `start`
+pub(crate) fn generate(layout: &Layout, encoder: &syn::Path, start: u32) -> TokenStream {
+    let name = &layout.structure.ident;
+    let generics = &layout.structure.generics;
+    let (_, type_generics, where_clause) = generics.split_for_impl();
+    let tipe = layout.field_type;
+    let serializable = layout.with_serde_bound(generics,parse_quote!(#tipe: ::serde::Serialize));
+    let (serialize_impl_generics, _, serialize_where_clause) = serializable.split_for_impl();
+    let deserializable = layout.with_serde_bound(&with_lifetime(generics,"'__de"),parse_quote!(#tipe: ::serde::Deserialize<'__de>));
+    let (deserialize_impl_generics, _, deserialize_where_clause) = deserializable.split_for_impl();
+    let idents = layout.idents;
+    let extra_fields = layout.extra_fields;
+    let count = idents.len();
+    let indices: Vec<u32> = (start..start + count as u32).collect();
+    let expecting = format!("a map of the keys of {}",name);
+    let serialize_body = layout.instrument("serialize",quote! { ::std::result::Result<__S::Ok,__S::Error> },quote! {
+        use ::serde::ser::SerializeMap;
+        let mut map = serializer.serialize_map(::std::option::Option::Some(#count))?;
+        #(map.serialize_entry(&<#encoder as ::structurray_keys::KeyEncoder>::encode(#indices),&self.#idents)?;)*
+        map.end()
+    });
+    let deserialize_body = layout.instrument("deserialize",quote! { ::std::result::Result<Self,__D::Error> },quote! {
+        deserializer.deserialize_map(__FauxVisitor(::std::marker::PhantomData))
+    });
+    quote! {
+        const _: () = {
+            impl #serialize_impl_generics ::serde::Serialize for #name #type_generics #serialize_where_clause {
+                fn serialize<__S: ::serde::Serializer>(&self, serializer: __S) -> ::std::result::Result<__S::Ok,__S::Error> {
+                    #serialize_body
+                }
+            }
+            struct __FauxVisitor #generics (::std::marker::PhantomData<fn() -> #name #type_generics>) #where_clause;
+            impl #deserialize_impl_generics ::serde::de::Visitor<'__de> for __FauxVisitor #type_generics #deserialize_where_clause {
+                type Value = #name #type_generics;
+                fn expecting(&self, formatter: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+                    formatter.write_str(#expecting)
+                }
+                fn visit_map<__A: ::serde::de::MapAccess<'__de>>(self, mut map: __A) -> ::std::result::Result<Self::Value,__A::Error> {
+                    #(let mut #idents = ::std::option::Option::None;)*
+                    while let ::std::option::Option::Some(key) = map.next_key::<::std::string::String>()? {
+                        match <#encoder as ::structurray_keys::KeyEncoder>::decode(&key) {
+                            #(::std::option::Option::Some(#indices) => {
+                                if #idents.is_some() {
+                                    return ::std::result::Result::Err(<__A::Error as ::serde::de::Error>::custom(::std::format_args!("duplicate key `{}`",key)));
+                                }
+                                #idents = ::std::option::Option::Some(map.next_value()?);
+                            },)*
+                            _ => {
+                                map.next_value::<::serde::de::IgnoredAny>()?;
+                            },
+                        }
+                    }
+                    ::std::result::Result::Ok(#name {
+                        #(#idents: #idents.ok_or_else(|| <__A::Error as ::serde::de::Error>::custom(::std::format_args!("missing key `{}`",<#encoder as ::structurray_keys::KeyEncoder>::encode(#indices))))?,)*
+                        #(#extra_fields: ::std::default::Default::default(),)*
+                    })
+                }
+            }
+            impl #deserialize_impl_generics ::serde::Deserialize<'__de> for #name #type_generics #deserialize_where_clause {
+                fn deserialize<__D: ::serde::Deserializer<'__de>>(deserializer: __D) -> ::std::result::Result<Self,__D::Error> {
+                    #deserialize_body
+                }
+            }
+        };
+    }
+}
@@ -0,0 +1,218 @@
+/*
+Copyright 2024 Benjamin Richcreek
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+//! Turning the index of each slot into its key and back
+use syn::{LitStr,LitInt,Ident};
+use syn::parse::{Parse,ParseStream};
+use structurray_keys::{encode_index,decode_key,encode_index_with,decode_key_with,DECIMAL_ALPHABET,HEX_ALPHABET,BASE36_ALPHABET,ALPHABET};
+use crate::Arguments;
+/// The numbering bases that can be chosen with the `encoding` option
+#[derive(Clone,Copy)]
+pub(crate) enum Encoding {
+    /// The digits `0` through `9`
+    Decimal,
+    /// The digits `0` through `9` followed by `a` through `f`
+    Hex,
+    /// The digits `0` through `9` followed by `a` through `z`
+    Base36,
+    /// The Base62 alphabet used when no encoding is chosen
+    Base62,
+}
+impl Parse for Encoding {
+    fn parse(input: ParseStream) -> Result<Self,syn::Error> {
+        let encoding: Ident = input.parse()?;
+        match encoding.to_string().as_str() {
+            "decimal" => Ok(Encoding::Decimal),
+            "hex" => Ok(Encoding::Hex),
+            "base36" => Ok(Encoding::Base36),
+            "base62" => Ok(Encoding::Base62),
+            _ => Err(syn::Error::new(encoding.span(),"the `encoding` option should be `decimal`, `hex`, `base36`, or `base62`")),
+        }
+    }
+}
+impl Encoding {
+    /// The digits of this numbering base, in order from least value to greatest value
+    pub(crate) fn alphabet(self) -> &'static str {
+        match self {
+            Encoding::Decimal => DECIMAL_ALPHABET,
+            Encoding::Hex => HEX_ALPHABET,
+            Encoding::Base36 => BASE36_ALPHABET,
+            Encoding::Base62 => ALPHABET,
+        }
+    }
+}
+/// A name or key with one `{}`, which is replaced by the encoded index of each slot
+pub(crate) struct Template {
+    /// The text before the `{}`
+    before: String,
+    /// The text after the `{}`
+    after: String,
+}
+impl Template {
+    /// Parses the template given to `option`, which must contain `{}` exactly once
+    pub(crate) fn parse(literal: &LitStr, option: &str) -> Result<Self,syn::Error> {
+        let value = literal.value();
+        match value.split_once("{}") {
+            Some((before, after)) if !after.contains("{}") => Ok(Template {
+                before: before.to_owned(),
+                after: after.to_owned(),
+            }),
+            _ => Err(syn::Error::new(literal.span(),format!("the `{}` option should contain `{{}}` exactly once, where the encoded index of each slot goes",option))),
+        }
+    }
+    /// Creates a template that puts `prefix` in front of the encoded index
+    pub(crate) fn prefixed(prefix: String) -> Self {
+        Template {
+            before: prefix,
+            after: String::new(),
+        }
+    }
+    /// Replaces the `{}` with `suffix`
+    pub(crate) fn fill(&self, suffix: &str) -> String {
+        format!("{}{}{}",self.before,suffix,self.after)
+    }
+    /// Finds the encoded index that [`fill`](Template::fill) replaced the `{}` with to create `filled`, or [`None`] if `filled` doesn't match the template
+    fn strip<'a>(&self, filled: &'a str) -> Option<&'a str> {
+        filled.strip_prefix(self.before.as_str())?.strip_suffix(self.after.as_str())
+    }
+}
+/// Turns indices into the digits of keys and back, before the padding of the `pad` option and the templates of the naming options are applied
+pub(crate) trait Encoder {
+    /// Creates the digits of `index`
+    fn encode(&self, index: u32) -> String;
+    /// Finds the index written with `digits`, or [`None`] if `digits` isn't the encoding of any index
+    fn decode(&self, digits: &str) -> Option<u32>;
+    /// The digit with no value, which the `pad` option fills keys with
+    fn zero(&self) -> char;
+}
+/// Writes indices in the numbering base given by a custom alphabet, or in Base62 if there is none
+struct Alphabet<'a>(Option<&'a str>);
+impl Encoder for Alphabet<'_> {
+    fn encode(&self, index: u32) -> String {
+        match self.0 {
+            Some(alphabet) => encode_index_with(index,alphabet),
+            None => encode_index(index),
+        }
+    }
+    fn decode(&self, digits: &str) -> Option<u32> {
+        match self.0 {
+            Some(alphabet) => decode_key_with(digits,alphabet),
+            None => decode_key(digits),
+        }
+    }
+    fn zero(&self) -> char {
+        self.0.unwrap_or(ALPHABET).chars().next().expect("Alphabets are checked to have at least two characters")
+    }
+}
+/// The encoder chosen by the `alphabet` and `encoding` options
+fn encoder(arguments: &Arguments) -> impl Encoder + '_ {
+    Alphabet(arguments.alphabet.as_deref())
+}
+/// Encodes `index` with the chosen encoder, without the padding of the `pad` option
+fn digits(arguments: &Arguments, index: u32) -> String {
+    encoder(arguments).encode(index)
+}
+/// The digit with no value for the chosen encoder, which the `pad` option fills keys with
+fn zero(arguments: &Arguments) -> char {
+    encoder(arguments).zero()
+}
+/// Encodes the slot at `position` (counting from the first slot of the [`struct`](https://doc.rust-lang.org/1.58.1/std/keyword.struct.html)), which names its field and every identifier generated for it
+pub(crate) fn suffix(arguments: &Arguments, position: u32) -> String {
+    let encoded = digits(arguments,arguments.start + position);
+    match arguments.pad {
+        Some(width) if encoded.chars().count() < width => {
+            let mut padded: String = std::iter::repeat_n(zero(arguments),width - encoded.chars().count()).collect();
+            padded.push_str(&encoded);
+            padded
+        },
+        _ => encoded,
+    }
+}
+/// Creates the key the slot at `position` is serialized under
+pub(crate) fn key(arguments: &Arguments, position: u32) -> String {
+    let suffix = suffix(arguments,position);
+    match &arguments.key_format {
+        Some(template) => template.fill(&suffix),
+        None => suffix,
+    }
+}
+/// Finds the position of the slot serialized under `key`, or [`None`] if no slot is
+pub(crate) fn position(arguments: &Arguments, key: &str) -> Option<u32> {
+    let mut suffix = match &arguments.key_format {
+        Some(template) => template.strip(key)?,
+        None => key,
+    };
+    if let Some(width) = arguments.pad {
+        if suffix.chars().count() != width {
+            return None;
+        }
+        let zero = zero(arguments);
+        while suffix.chars().count() > 1 && suffix.starts_with(zero) {
+            suffix = &suffix[zero.len_utf8()..];
+        }
+    }
+    let index = encoder(arguments).decode(suffix)?;
+    index.checked_sub(arguments.start).filter(|position| *position < arguments.field_count)
+}
+/// Rejects a `pad` width narrower than the key of the last slot, since keys are never truncated
+pub(crate) fn check_pad(arguments: &Arguments, width: &LitInt) -> Result<(),syn::Error> {
+    let Some(last) = arguments.field_count.checked_sub(1) else {
+        return Ok(());
+    };
+    let needed = digits(arguments,arguments.start + last).chars().count();
+    if arguments.pad.is_some_and(|pad| pad < needed) {
+        Err(syn::Error::new(width.span(),format!("the `pad` option should be at least {}, the width of the key of the last slot",needed)))
+    } else {
+        Ok(())
+    }
+}
+/// Rejects an `alphabet` with fewer than two characters, repeated characters, or characters that can't be part of an identifier
+pub(crate) fn check_alphabet(alphabet: &LitStr) -> Result<(),syn::Error> {
+    let value = alphabet.value();
+    let digits: Vec<char> = value.chars().collect();
+    if digits.len() < 2 {
+        return Err(syn::Error::new(alphabet.span(),"the `alphabet` option should have at least two characters"));
+    }
+    if let Some(repeated) = digits.iter().enumerate().find_map(|(position, digit)| digits[..position].contains(digit).then_some(digit)) {
+        return Err(syn::Error::new(alphabet.span(),format!("the character `{}` appears more than once in the `alphabet` option",repeated)));
+    }
+    if syn::parse_str::<Ident>(&format!("_{}",value)).is_err() {
+        return Err(syn::Error::new(alphabet.span(),"every character of the `alphabet` option should be able to appear in an identifier, since generated fields are named after their keys"));
+    }
+    Ok(())
+}
+/// Creates the identifier of the field of the slot at `position`
+pub(crate) fn field_name(arguments: &Arguments, position: u32) -> String {
+    let suffix = suffix(arguments,position);
+    match &arguments.name_format {
+        Some(template) => template.fill(&suffix),
+        None => format!("_{}",suffix),
+    }
+}
+/// Rejects a `name_format` that doesn't form an identifier for every slot, pointing at `option`
+pub(crate) fn check_names(arguments: &Arguments, option: &Ident) -> Result<(),syn::Error> {
+    match (0..arguments.field_count).map(|position| field_name(arguments,position)).find(|name| syn::parse_str::<Ident>(name).is_err()) {
+        Some(name) => Err(syn::Error::new(option.span(),format!("the `{}` option generates `{}`, which isn't an identifier",option,name))),
+        None => Ok(()),
+    }
+}
+/// Rejects a `flatten_prefix` that is empty, since slots already use their own keys without one
+pub(crate) fn check_prefix(prefix: &LitStr) -> Result<(),syn::Error> {
+    if prefix.value().is_empty() {
+        Err(syn::Error::new(prefix.span(),"the `flatten_prefix` option should not be empty"))
+    } else {
+        Ok(())
+    }
+}
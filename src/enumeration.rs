@@ -0,0 +1,119 @@
+/*
+Copyright 2024 Benjamin Richcreek
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+//! Generation of an enum with one variant for the key of each slot
+use proc_macro2::TokenStream;
+use quote::{quote,format_ident};
+use syn::Ident;
+use crate::Layout;
+/// Names the variant of the slot whose encoded index is `suffix`
+pub(crate) fn variants(suffixes: &[String]) -> Vec<Ident> {
+    suffixes.iter().map(|suffix| format_ident!("K{}",suffix)).collect()
+}
+/// Names the key enum generated for the [`struct`](https://doc.rust-lang.org/1.58.1/std/keyword.struct.html) named `name`, unless the `key_enum` option was given a name
+pub(crate) fn default_name(name: &Ident) -> Ident {
+    format_ident!("{}Key",name)
+}
+/// Creates the key enum named `enumeration`, along with the error type of its [`FromStr`](std::str::FromStr) implementation
+pub(crate) fn generate(layout: &Layout, enumeration: &Ident) -> TokenStream {
+    let name = &layout.structure.ident;
+    let visibility = &layout.structure.vis;
+    let error = format_ident!("{}Error",enumeration);
+    let variants = variants(layout.suffixes);
+    let names = layout.names;
+    let count = names.len();
+    let indices: Vec<u32> = (0..count as u32).collect();
+    let documentation = format!("The key of each slot of [`{}`]",name);
+    let variant_documentation = names.iter().map(|key| format!("The key `{}`",key));
+    let error_documentation = format!("A string that isn't the key of any slot of [`{}`]",name);
+    let message = format!("`{{}}` isn't a key of {}",name);
+    quote! {
+        #[doc = #documentation]
+        #[derive(Clone,Copy,Debug,PartialEq,Eq,PartialOrd,Ord,Hash)]
+        #visibility enum #enumeration {
+            #(
+                #[doc = #variant_documentation]
+                #variants,
+            )*
+        }
+        impl #enumeration {
+            /// Every key, in the order of the slots
+            pub const ALL: [Self; #count] = [#(Self::#variants),*];
+            /// Returns the key this variant stands for
+            pub const fn key(self) -> &'static str {
+                match self {
+                    #(Self::#variants => #names,)*
+                }
+            }
+            /// Returns the index of the slot with this key
+            pub const fn index(self) -> u32 {
+                match self {
+                    #(Self::#variants => #indices,)*
+                }
+            }
+        }
+        impl ::std::fmt::Display for #enumeration {
+            fn fmt(&self, formatter: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                formatter.write_str(self.key())
+            }
+        }
+        #[doc = #error_documentation]
+        #[derive(Clone,PartialEq,Eq,Hash,Debug)]
+        #visibility struct #error {
+            /// The string that was parsed
+            pub key: ::std::string::String,
+        }
+        impl ::std::fmt::Display for #error {
+            fn fmt(&self, formatter: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                ::std::write!(formatter,#message,self.key)
+            }
+        }
+        impl ::std::error::Error for #error {}
+        impl ::std::str::FromStr for #enumeration {
+            type Err = #error;
+            fn from_str(key: &str) -> ::std::result::Result<Self,#error> {
+                match key {
+                    #(#names => ::std::result::Result::Ok(Self::#variants),)*
+                    _ => ::std::result::Result::Err(#error { key: ::std::string::String::from(key) }),
+                }
+            }
+        }
+    }
+}
+/// Creates implementations of [`Index`](std::ops::Index) and [`IndexMut`](std::ops::IndexMut) that take the variants of the key enum named `enumeration`
+pub(crate) fn generate_index(layout: &Layout, enumeration: &Ident) -> TokenStream {
+    let name = &layout.structure.ident;
+    let (impl_generics, type_generics, where_clause) = layout.structure.generics.split_for_impl();
+    let tipe = layout.field_type;
+    let idents = layout.idents;
+    let variants = variants(layout.suffixes);
+    quote! {
+        impl #impl_generics ::std::ops::Index<#enumeration> for #name #type_generics #where_clause {
+            type Output = #tipe;
+            fn index(&self, key: #enumeration) -> &#tipe {
+                match key {
+                    #(#enumeration::#variants => &self.#idents,)*
+                }
+            }
+        }
+        impl #impl_generics ::std::ops::IndexMut<#enumeration> for #name #type_generics #where_clause {
+            fn index_mut(&mut self, key: #enumeration) -> &mut #tipe {
+                match key {
+                    #(#enumeration::#variants => &mut self.#idents,)*
+                }
+            }
+        }
+    }
+}
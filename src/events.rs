@@ -0,0 +1,69 @@
+/*
+Copyright 2024 Benjamin Richcreek
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+//! Generation of helpers that apply real-time child events to the slots
+use proc_macro2::TokenStream;
+use quote::quote;
+use crate::{Layout,element_bound};
+/// Creates methods that translate child-added, child-changed, and child-removed events (a key and a serialized value) into updates of the slots
+pub(crate) fn generate(layout: &Layout) -> TokenStream {
+    let name = &layout.structure.ident;
+    let (impl_generics, type_generics, where_clause) = layout.structure.generics.split_for_impl();
+    let tipe = layout.field_type;
+    let idents = layout.idents;
+    let names = layout.names;
+    let indices: Vec<u32> = (0..idents.len() as u32).collect();
+    let apply_child_removed = element_bound(tipe,quote!(::std::default::Default)).map(|default_bound| quote! {
+        /// Applies a child-removed event by resetting the slot with `key` to its default value, returning the index of that slot and its old value
+        ///
+        /// Returns `None` when `key` isn't the key of any slot.
+        pub fn apply_child_removed(&mut self, key: &str) -> ::std::option::Option<(u32,#tipe)> #default_bound {
+            match key {
+                #(#names => ::std::option::Option::Some((#indices,::std::mem::take(&mut self.#idents))),)*
+                _ => ::std::option::Option::None,
+            }
+        }
+    });
+    quote! {
+        impl #impl_generics #name #type_generics #where_clause {
+            /// Translates a child event into the index of the slot it targets and the new value of that slot
+            ///
+            /// Returns `Ok(None)` when `key` isn't the key of any slot.
+            /// # Errors
+            /// Returns an error if `value` can't be deserialized into a slot
+            pub fn child_update<'__de, __D: ::serde::Deserializer<'__de>>(key: &str, value: __D) -> ::std::result::Result<::std::option::Option<(u32,#tipe)>,__D::Error> where #tipe: ::serde::Deserialize<'__de> {
+                let index: u32 = match key {
+                    #(#names => #indices,)*
+                    _ => return ::std::result::Result::Ok(::std::option::Option::None),
+                };
+                ::std::result::Result::Ok(::std::option::Option::Some((index,<#tipe as ::serde::Deserialize>::deserialize(value)?)))
+            }
+            /// Applies a child-added or child-changed event, returning the index of the updated slot and its old value
+            ///
+            /// Returns `Ok(None)`, leaving every slot untouched, when `key` isn't the key of any slot.
+            /// # Errors
+            /// Returns an error if `value` can't be deserialized into a slot
+            pub fn apply_child_changed<'__de, __D: ::serde::Deserializer<'__de>>(&mut self, key: &str, value: __D) -> ::std::result::Result<::std::option::Option<(u32,#tipe)>,__D::Error> where #tipe: ::serde::Deserialize<'__de> {
+                let (index,slot) = match key {
+                    #(#names => (#indices,&mut self.#idents),)*
+                    _ => return ::std::result::Result::Ok(::std::option::Option::None),
+                };
+                let value = <#tipe as ::serde::Deserialize>::deserialize(value)?;
+                ::std::result::Result::Ok(::std::option::Option::Some((index,::std::mem::replace(slot,value))))
+            }
+            #apply_child_removed
+        }
+    }
+}
@@ -0,0 +1,61 @@
+/*
+Copyright 2024 Benjamin Richcreek
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+//! Generation of fuzzing harnesses
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::parse_quote;
+use crate::{Layout,with_bound};
+/// Creates the `fuzz_round_trip` associated function, which only exists under `cfg(fuzzing)`
+pub(crate) fn generate(layout: &Layout) -> TokenStream {
+    let name = &layout.structure.ident;
+    let (_, type_generics, _) = layout.structure.generics.split_for_impl();
+    let serializable = with_bound(&layout.structure.generics,parse_quote!(#name #type_generics: ::serde::Serialize + ::serde::de::DeserializeOwned));
+    let (impl_generics, _, where_clause) = serializable.split_for_impl();
+    let (output, checked, failure_doc) = if layout.no_panic {
+        (quote! { -> bool }, quote! { round_trips }, quote! {
+            ///
+            /// Returns `false` if a document that was read successfully can't be written and read back to the same document, so the fuzz target can decide how to fail.
+        })
+    } else {
+        (quote! {}, quote! { assert!(round_trips,"a document that was read successfully should be written and read back to the same document"); }, quote! {
+            ///
+            /// # Panics
+            /// Panics if a document that was read successfully can't be written and read back to the same document
+        })
+    };
+    quote! {
+        #[allow(unexpected_cfgs)]
+        const _: () = {
+            #[cfg(fuzzing)]
+            impl #impl_generics #name #type_generics #where_clause {
+                /// Reads `data` as a JSON document and, if that succeeds, checks that the document survives being written and read again unchanged
+                #failure_doc
+                pub fn fuzz_round_trip(data: &[u8]) #output {
+                    let round_trips = match ::serde_json::from_slice::<Self>(data) {
+                        ::std::result::Result::Ok(value) => match ::serde_json::to_vec(&value) {
+                            ::std::result::Result::Ok(written) => ::serde_json::from_slice::<Self>(&written).ok()
+                                .and_then(|read| ::serde_json::to_vec(&read).ok())
+                                .is_some_and(|rewritten| rewritten == written),
+                            ::std::result::Result::Err(_) => false,
+                        },
+                        ::std::result::Result::Err(_) => true,
+                    };
+                    #checked
+                }
+            }
+        };
+    }
+}
@@ -0,0 +1,67 @@
+/*
+Copyright 2024 Benjamin Richcreek
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+//! Marking of generated items, so that lints and coverage tools can tell them apart from handwritten code
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Item,Expr,Stmt,parse_quote};
+/// The lints allowed when the `allow` option is given without a list
+pub(crate) fn default_lints() -> Vec<syn::Path> {
+    vec![parse_quote!(non_snake_case),parse_quote!(non_camel_case_types),parse_quote!(clippy::too_many_lines)]
+}
+/// Adds `#[allow(...)]` with every lint in `lints` to each item in `generated`, and `#[automatically_derived]` to each implementation, including those nested in `const _` blocks
+pub(crate) fn mark(generated: TokenStream, lints: &[syn::Path]) -> Result<TokenStream,syn::Error> {
+    let mut file: syn::File = syn::parse2(generated)?;
+    for item in &mut file.items {
+        mark_implementations(item);
+        if let Some(attributes) = attributes(item) {
+            attributes.push(parse_quote!(#[allow(#(#lints),*)]));
+        }
+    }
+    let items = &file.items;
+    Ok(quote! { #(#items)* })
+}
+/// Adds `#[automatically_derived]` to `item` if it's an implementation, or to the implementations inside it if it's a `const` item with a block
+fn mark_implementations(item: &mut Item) {
+    match item {
+        Item::Impl(implementation) => implementation.attrs.push(parse_quote!(#[automatically_derived])),
+        Item::Const(constant) => {
+            if let Expr::Block(block) = &mut *constant.expr {
+                for statement in &mut block.block.stmts {
+                    if let Stmt::Item(inner) = statement {
+                        mark_implementations(inner);
+                    }
+                }
+            }
+        },
+        _ => (),
+    }
+}
+/// Finds the attributes of `item`, if it's a kind of item this crate generates
+fn attributes(item: &mut Item) -> Option<&mut Vec<syn::Attribute>> {
+    match item {
+        Item::Const(item) => Some(&mut item.attrs),
+        Item::Enum(item) => Some(&mut item.attrs),
+        Item::Fn(item) => Some(&mut item.attrs),
+        Item::Impl(item) => Some(&mut item.attrs),
+        Item::Mod(item) => Some(&mut item.attrs),
+        Item::Static(item) => Some(&mut item.attrs),
+        Item::Struct(item) => Some(&mut item.attrs),
+        Item::Trait(item) => Some(&mut item.attrs),
+        Item::Type(item) => Some(&mut item.attrs),
+        Item::Use(item) => Some(&mut item.attrs),
+        _ => None,
+    }
+}
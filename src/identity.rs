@@ -0,0 +1,67 @@
+/*
+Copyright 2024 Benjamin Richcreek
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+//! Generation of equality and hashing over a few identifying slots
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{LitInt,parse_quote};
+use crate::{Layout,with_bound};
+/// Rejects an empty list of identifying slots, indices past the last of `count` slots, and indices listed more than once
+pub(crate) fn validate(identity: &[LitInt], span: proc_macro2::Span, count: u32) -> Result<(),syn::Error> {
+    if identity.is_empty() {
+        return Err(syn::Error::new(span,"the `identity` option should list at least one slot"));
+    }
+    let mut looper = 0;
+    while looper < identity.len() {
+        let index: u32 = identity[looper].base10_parse()?;
+        if index >= count {
+            return Err(syn::Error::new(identity[looper].span(),format!("there is no slot at index {}, since there are only {} slots",index,count)));
+        }
+        if identity[..looper].iter().any(|other| other.base10_parse::<u32>().is_ok_and(|other| other == index)) {
+            return Err(syn::Error::new(identity[looper].span(),format!("the slot at index {} is listed more than once",index)));
+        }
+        looper += 1;
+    }
+    Ok(())
+}
+/// Creates [`PartialEq`], [`Eq`], and [`Hash`] implementations that only consider the slots at the indices in `identity`, in the order they're listed
+pub(crate) fn generate(layout: &Layout, identity: &[LitInt]) -> TokenStream {
+    let name = &layout.structure.ident;
+    let generics = &layout.structure.generics;
+    let (_, type_generics, _) = generics.split_for_impl();
+    let tipe = layout.field_type;
+    let slots: Vec<_> = identity.iter().map(|index| &layout.idents[index.base10_parse::<usize>().expect("The identifying indices were already parsed successfully")]).collect();
+    let comparable = with_bound(generics,parse_quote!(for<'faux_bound> #tipe: ::std::cmp::PartialEq));
+    let (comparable_impl_generics, _, comparable_where_clause) = comparable.split_for_impl();
+    let equivalent = with_bound(generics,parse_quote!(for<'faux_bound> #tipe: ::std::cmp::Eq));
+    let (equivalent_impl_generics, _, equivalent_where_clause) = equivalent.split_for_impl();
+    let hashable = with_bound(generics,parse_quote!(for<'faux_bound> #tipe: ::std::hash::Hash));
+    let (hash_impl_generics, _, hash_where_clause) = hashable.split_for_impl();
+    quote! {
+        impl #comparable_impl_generics ::std::cmp::PartialEq for #name #type_generics #comparable_where_clause {
+            /// Compares only the identifying slots
+            fn eq(&self, other: &Self) -> bool {
+                true #(&& ::std::cmp::PartialEq::eq(&self.#slots,&other.#slots))*
+            }
+        }
+        impl #equivalent_impl_generics ::std::cmp::Eq for #name #type_generics #equivalent_where_clause {}
+        impl #hash_impl_generics ::std::hash::Hash for #name #type_generics #hash_where_clause {
+            /// Hashes only the identifying slots
+            fn hash<H: ::std::hash::Hasher>(&self, state: &mut H) {
+                #(::std::hash::Hash::hash(&self.#slots,state);)*
+            }
+        }
+    }
+}
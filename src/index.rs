@@ -0,0 +1,74 @@
+/*
+Copyright 2024 Benjamin Richcreek
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+//! Generation of numeric indexing over the slots
+use proc_macro2::TokenStream;
+use quote::quote;
+use crate::Layout;
+/// Creates `Index<usize>` and `IndexMut<usize>` implementations that match the index against every slot
+pub(crate) fn generate(layout: &Layout) -> TokenStream {
+    let name = &layout.structure.ident;
+    let (impl_generics, type_generics, where_clause) = layout.structure.generics.split_for_impl();
+    let tipe = layout.field_type;
+    let idents = layout.idents;
+    let count = idents.len();
+    let indices: Vec<usize> = (0..count).collect();
+    let out_of_range = layout.out_of_range();
+    quote! {
+        impl #impl_generics ::std::ops::Index<usize> for #name #type_generics #where_clause {
+            type Output = #tipe;
+            fn index(&self, index: usize) -> &#tipe {
+                match index {
+                    #(#indices => &self.#idents,)*
+                    _ => #out_of_range,
+                }
+            }
+        }
+        impl #impl_generics ::std::ops::IndexMut<usize> for #name #type_generics #where_clause {
+            fn index_mut(&mut self, index: usize) -> &mut #tipe {
+                match index {
+                    #(#indices => &mut self.#idents,)*
+                    _ => #out_of_range,
+                }
+            }
+        }
+    }
+}
+/// Creates `get` and `get_mut`, which borrow the slot at an index without panicking when it is out of range
+pub(crate) fn generate_get(layout: &Layout) -> TokenStream {
+    let name = &layout.structure.ident;
+    let (impl_generics, type_generics, where_clause) = layout.structure.generics.split_for_impl();
+    let tipe = layout.field_type;
+    let idents = layout.idents;
+    let indices: Vec<usize> = (0..idents.len()).collect();
+    quote! {
+        impl #impl_generics #name #type_generics #where_clause {
+            /// Borrows the slot at `index`, or returns `None` if `index` is out of range
+            pub fn get(&self, index: usize) -> ::std::option::Option<&#tipe> {
+                match index {
+                    #(#indices => ::std::option::Option::Some(&self.#idents),)*
+                    _ => ::std::option::Option::None,
+                }
+            }
+            /// Mutably borrows the slot at `index`, or returns `None` if `index` is out of range
+            pub fn get_mut(&mut self, index: usize) -> ::std::option::Option<&mut #tipe> {
+                match index {
+                    #(#indices => ::std::option::Option::Some(&mut self.#idents),)*
+                    _ => ::std::option::Option::None,
+                }
+            }
+        }
+    }
+}
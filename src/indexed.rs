@@ -0,0 +1,51 @@
+/*
+Copyright 2024 Benjamin Richcreek
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+//! Generation of conversions to and from `IndexMap`s in index order
+use proc_macro2::TokenStream;
+use quote::quote;
+use crate::{Layout,element_bound};
+/// Creates the `to_index_map` and `from_index_map` methods
+pub(crate) fn generate(layout: &Layout) -> TokenStream {
+    let name = &layout.structure.ident;
+    let (impl_generics, type_generics, where_clause) = layout.structure.generics.split_for_impl();
+    let tipe = layout.field_type;
+    let idents = layout.idents;
+    let names = layout.names;
+    let extra_fields = layout.extra_fields;
+    let count = idents.len();
+    let to_index_map = element_bound(tipe,quote!(::std::clone::Clone)).map(|clone_bound| quote! {
+        /// Copies every slot into an [`IndexMap`](::indexmap::IndexMap) from keys to values, which iterates in index order
+        pub fn to_index_map(&self) -> ::indexmap::IndexMap<&'static str,#tipe> #clone_bound {
+            let mut map = ::indexmap::IndexMap::with_capacity(#count);
+            #(map.insert(#names,::std::clone::Clone::clone(&self.#idents));)*
+            map
+        }
+    });
+    quote! {
+        impl #impl_generics #name #type_generics #where_clause {
+            #to_index_map
+            /// Moves the value of each key in `map` into the slot with that key, ignoring keys that don't belong to any slot
+            ///
+            /// Returns `None` if the key of any slot is missing from `map`.
+            pub fn from_index_map(mut map: ::indexmap::IndexMap<&'static str,#tipe>) -> ::std::option::Option<Self> {
+                ::std::option::Option::Some(#name {
+                    #(#idents: map.swap_remove(#names)?,)*
+                    #(#extra_fields: ::std::default::Default::default(),)*
+                })
+            }
+        }
+    }
+}
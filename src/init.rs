@@ -0,0 +1,86 @@
+/*
+Copyright 2024 Benjamin Richcreek
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+//! Generation of constructors that fill the slots with a chosen expression, closure, or function
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::Expr;
+use crate::Layout;
+/// Creates `new`, `clear`, and a [`Default`] implementation that evaluate `value` once for every slot
+pub(crate) fn generate(layout: &Layout, value: &Expr) -> TokenStream {
+    let name = &layout.structure.ident;
+    let (impl_generics, type_generics, where_clause) = layout.structure.generics.split_for_impl();
+    let idents = layout.idents;
+    let extra_fields = layout.extra_fields;
+    quote! {
+        impl #impl_generics #name #type_generics #where_clause {
+            /// Creates an instance with the initial value in every slot
+            pub fn new() -> Self {
+                #name {
+                    #(#idents: #value,)*
+                    #(#extra_fields: ::std::default::Default::default(),)*
+                }
+            }
+            /// Puts the initial value back into every slot
+            pub fn clear(&mut self) {
+                #(self.#idents = #value;)*
+            }
+        }
+        impl #impl_generics ::std::default::Default for #name #type_generics #where_clause {
+            fn default() -> Self {
+                Self::new()
+            }
+        }
+    }
+}
+/// Creates `new_with`, which fills each slot by calling a closure with the slot's index
+pub(crate) fn generate_with(layout: &Layout) -> TokenStream {
+    let name = &layout.structure.ident;
+    let (impl_generics, type_generics, where_clause) = layout.structure.generics.split_for_impl();
+    let tipe = layout.field_type;
+    let idents = layout.idents;
+    let indices = 0..idents.len() as u32;
+    let extra_fields = layout.extra_fields;
+    quote! {
+        impl #impl_generics #name #type_generics #where_clause {
+            /// Creates an instance holding the result of calling `f` with the index of each slot, calling it once per slot in index order
+            pub fn new_with<F: ::std::ops::FnMut(u32) -> #tipe>(mut f: F) -> Self {
+                #name {
+                    #(#idents: f(#indices),)*
+                    #(#extra_fields: ::std::default::Default::default(),)*
+                }
+            }
+        }
+    }
+}
+/// Creates `example`, which fills each slot by calling the function at `function` with the slot's index
+pub(crate) fn generate_example(layout: &Layout, function: &syn::Path) -> TokenStream {
+    let name = &layout.structure.ident;
+    let (impl_generics, type_generics, where_clause) = layout.structure.generics.split_for_impl();
+    let idents = layout.idents;
+    let indices = 0..idents.len() as u32;
+    let extra_fields = layout.extra_fields;
+    quote! {
+        impl #impl_generics #name #type_generics #where_clause {
+            /// Creates the canonical example instance, holding the value the example function gives for the index of each slot
+            pub fn example() -> Self {
+                #name {
+                    #(#idents: #function(#indices),)*
+                    #(#extra_fields: ::std::default::Default::default(),)*
+                }
+            }
+        }
+    }
+}
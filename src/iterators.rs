@@ -0,0 +1,90 @@
+/*
+Copyright 2024 Benjamin Richcreek
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+//! Generation of iterators over the slots
+use proc_macro2::{TokenStream,Span};
+use quote::quote;
+use syn::Lifetime;
+use crate::{Layout,with_lifetime};
+/// Creates `iter` and `iter_mut`, which borrow every slot in index order
+pub(crate) fn generate(layout: &Layout) -> TokenStream {
+    let name = &layout.structure.ident;
+    let (impl_generics, type_generics, where_clause) = layout.structure.generics.split_for_impl();
+    let tipe = layout.field_type;
+    let idents = layout.idents;
+    let lifetime = Lifetime::new("'faux_iter",Span::call_site());
+    quote! {
+        impl #impl_generics #name #type_generics #where_clause {
+            /// Borrows every slot, in index order
+            pub fn iter<#lifetime>(&#lifetime self) -> impl ::std::iter::DoubleEndedIterator<Item = &#lifetime #tipe> + ::std::iter::ExactSizeIterator {
+                ::std::vec![#(&self.#idents),*].into_iter()
+            }
+            /// Mutably borrows every slot, in index order
+            pub fn iter_mut<#lifetime>(&#lifetime mut self) -> impl ::std::iter::DoubleEndedIterator<Item = &#lifetime mut #tipe> + ::std::iter::ExactSizeIterator {
+                ::std::vec![#(&mut self.#idents),*].into_iter()
+            }
+        }
+    }
+}
+/// Creates `iter_mut_indexed`, which pairs each slot with its index
+pub(crate) fn generate_indexed(layout: &Layout) -> TokenStream {
+    let name = &layout.structure.ident;
+    let (impl_generics, type_generics, where_clause) = layout.structure.generics.split_for_impl();
+    let tipe = layout.field_type;
+    let idents = layout.idents;
+    let indices = 0..idents.len() as u32;
+    let lifetime = Lifetime::new("'faux_iter",Span::call_site());
+    quote! {
+        impl #impl_generics #name #type_generics #where_clause {
+            /// Mutably borrows each slot along with its index, in index order
+            pub fn iter_mut_indexed<#lifetime>(&#lifetime mut self) -> impl ::std::iter::DoubleEndedIterator<Item = (u32,&#lifetime mut #tipe)> + ::std::iter::ExactSizeIterator {
+                ::std::vec![#((#indices,&mut self.#idents)),*].into_iter()
+            }
+        }
+    }
+}
+/// Creates [`IntoIterator`] implementations for the [`struct`](https://doc.rust-lang.org/1.58.1/std/keyword.struct.html) and for references to it, which yield the slots in index order
+pub(crate) fn generate_into_iter(layout: &Layout) -> TokenStream {
+    let name = &layout.structure.ident;
+    let generics = &layout.structure.generics;
+    let (impl_generics, type_generics, where_clause) = generics.split_for_impl();
+    let borrowed = with_lifetime(generics,"'faux_iter");
+    let (borrowed_impl_generics, _, _) = borrowed.split_for_impl();
+    let tipe = layout.field_type;
+    let idents = layout.idents;
+    quote! {
+        impl #impl_generics ::std::iter::IntoIterator for #name #type_generics #where_clause {
+            type Item = #tipe;
+            type IntoIter = ::std::vec::IntoIter<#tipe>;
+            fn into_iter(self) -> Self::IntoIter {
+                ::std::vec![#(self.#idents),*].into_iter()
+            }
+        }
+        impl #borrowed_impl_generics ::std::iter::IntoIterator for &'faux_iter #name #type_generics #where_clause {
+            type Item = &'faux_iter #tipe;
+            type IntoIter = ::std::vec::IntoIter<&'faux_iter #tipe>;
+            fn into_iter(self) -> Self::IntoIter {
+                ::std::vec![#(&self.#idents),*].into_iter()
+            }
+        }
+        impl #borrowed_impl_generics ::std::iter::IntoIterator for &'faux_iter mut #name #type_generics #where_clause {
+            type Item = &'faux_iter mut #tipe;
+            type IntoIter = ::std::vec::IntoIter<&'faux_iter mut #tipe>;
+            fn into_iter(self) -> Self::IntoIter {
+                ::std::vec![#(&mut self.#idents),*].into_iter()
+            }
+        }
+    }
+}
@@ -0,0 +1,90 @@
+/*
+Copyright 2024 Benjamin Richcreek
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+//! Generation of the table of keys and helpers that select subsets of it
+use proc_macro2::TokenStream;
+use quote::quote;
+use crate::Layout;
+/// Creates the `KEYS` and `KEY_INDICES` associated constants along with `keys_range`, `keys_of`, and `index_of`
+pub(crate) fn generate(layout: &Layout) -> TokenStream {
+    let name = &layout.structure.ident;
+    let (impl_generics, type_generics, where_clause) = layout.structure.generics.split_for_impl();
+    let names = layout.names;
+    let count = names.len();
+    let mut sorted: Vec<(&String, u32)> = names.iter().zip(0..count as u32).collect();
+    sorted.sort();
+    let sorted_names = sorted.iter().map(|(key, _)| key);
+    let sorted_indices = sorted.iter().map(|(_, index)| index);
+    let selectors = if layout.no_panic {
+        quote! {
+            /// Iterates over the keys of the slots whose indices are in `range`, or returns `None` if `range` extends past the last slot
+            pub fn keys_range(range: ::std::ops::Range<u32>) -> ::std::option::Option<impl ::std::iter::DoubleEndedIterator<Item = &'static str> + ::std::iter::ExactSizeIterator> {
+                ::std::option::Option::Some(Self::KEYS.get(range.start as usize..range.end as usize)?.iter().copied())
+            }
+            /// Iterates over the keys of the slots at `indices`, in the order the indices are given, yielding `None` for each index that is out of range
+            ///
+            /// The returned iterator can be reversed or asked for its length whenever the iterator of `indices` can.
+            pub fn keys_of<I: ::std::iter::IntoIterator<Item = u32>>(indices: I) -> ::std::iter::Map<I::IntoIter,fn(u32) -> ::std::option::Option<&'static str>> {
+                indices.into_iter().map(|index| Self::KEYS.get(index as usize).copied())
+            }
+        }
+    } else {
+        quote! {
+            /// Iterates over the keys of the slots whose indices are in `range`
+            ///
+            /// # Panics
+            /// Panics if `range` extends past the last slot
+            pub fn keys_range(range: ::std::ops::Range<u32>) -> impl ::std::iter::DoubleEndedIterator<Item = &'static str> + ::std::iter::ExactSizeIterator {
+                Self::KEYS[range.start as usize..range.end as usize].iter().copied()
+            }
+            /// Iterates over the keys of the slots at `indices`, in the order the indices are given
+            ///
+            /// The returned iterator can be reversed or asked for its length whenever the iterator of `indices` can.
+            /// # Panics
+            /// The returned iterator panics if it reaches an index that is out of range
+            pub fn keys_of<I: ::std::iter::IntoIterator<Item = u32>>(indices: I) -> ::std::iter::Map<I::IntoIter,fn(u32) -> &'static str> {
+                indices.into_iter().map(|index| Self::KEYS[index as usize])
+            }
+        }
+    };
+    quote! {
+        impl #impl_generics #name #type_generics #where_clause {
+            /// The key of every slot, in order
+            pub const KEYS: [&'static str; #count] = [#(#names),*];
+            /// Every key paired with the index of its slot, sorted by key so that it can be binary searched
+            pub const KEY_INDICES: [(&'static str, u32); #count] = [#((#sorted_names,#sorted_indices)),*];
+            /// Returns the index of the slot with `key`, or `None` if no slot has that key
+            ///
+            /// This searches `KEY_INDICES` rather than decoding `key`.
+            pub fn index_of(key: &str) -> ::std::option::Option<u32> {
+                Self::KEY_INDICES.binary_search_by(|(candidate, _)| (*candidate).cmp(key)).ok().and_then(|position| Self::KEY_INDICES.get(position)).map(|(_, index)| *index)
+            }
+            #selectors
+        }
+    }
+}
+/// Creates the `FIELD_NAMES` associated constant
+pub(crate) fn generate_field_names(layout: &Layout) -> TokenStream {
+    let name = &layout.structure.ident;
+    let (impl_generics, type_generics, where_clause) = layout.structure.generics.split_for_impl();
+    let names = layout.names;
+    let count = names.len();
+    quote! {
+        impl #impl_generics #name #type_generics #where_clause {
+            /// The name each slot is serialized under, in order
+            pub const FIELD_NAMES: [&'static str; #count] = [#(#names),*];
+        }
+    }
+}
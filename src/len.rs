@@ -0,0 +1,35 @@
+/*
+Copyright 2024 Benjamin Richcreek
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+//! Generation of the number of slots as an associated constant
+use proc_macro2::TokenStream;
+use quote::quote;
+use crate::Layout;
+/// Creates the `LEN` associated constant and the `len` method
+pub(crate) fn generate(layout: &Layout) -> TokenStream {
+    let name = &layout.structure.ident;
+    let (impl_generics, type_generics, where_clause) = layout.structure.generics.split_for_impl();
+    let count = layout.idents.len();
+    quote! {
+        impl #impl_generics #name #type_generics #where_clause {
+            /// The number of slots
+            pub const LEN: usize = #count;
+            /// Returns the number of slots, which is always `LEN`
+            pub const fn len(&self) -> usize {
+                Self::LEN
+            }
+        }
+    }
+}
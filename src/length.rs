@@ -0,0 +1,142 @@
+/*
+Copyright 2024 Benjamin Richcreek
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+//! Generation of the conversions from iterators and vectors
+use proc_macro2::TokenStream;
+use quote::{quote,format_ident};
+use syn::Ident;
+use crate::Layout;
+/// Names the error type generated for the [`struct`](https://doc.rust-lang.org/1.58.1/std/keyword.struct.html) named `name`
+pub(crate) fn type_name(name: &Ident) -> Ident {
+    format_ident!("{}LengthError",name)
+}
+/// Creates the length error type shared by `try_from_iter` and the [`TryFrom<Vec<T>>`](TryFrom) implementation
+pub(crate) fn generate_error(layout: &Layout) -> TokenStream {
+    let name = &layout.structure.ident;
+    let visibility = &layout.structure.vis;
+    let error = type_name(name);
+    let message = format!("expected {} items for {}, but received {{}}",layout.idents.len(),name);
+    quote! {
+        /// The number of items given for the slots didn't match the number of slots
+        #[derive(Clone,Copy,PartialEq,Eq,Hash,Debug)]
+        #visibility struct #error {
+            /// The number of slots
+            pub expected: usize,
+            /// The number of items given
+            pub received: usize,
+        }
+        impl ::std::fmt::Display for #error {
+            fn fmt(&self, formatter: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                ::std::write!(formatter,#message,self.received)
+            }
+        }
+        impl ::std::error::Error for #error {}
+    }
+}
+/// Creates the `try_from_iter` associated function
+pub(crate) fn generate(layout: &Layout) -> TokenStream {
+    let name = &layout.structure.ident;
+    let (impl_generics, type_generics, where_clause) = layout.structure.generics.split_for_impl();
+    let error = type_name(name);
+    let tipe = layout.field_type;
+    let idents = layout.idents;
+    let extra_fields = layout.extra_fields;
+    let expected = idents.len();
+    let indices = 0..expected;
+    quote! {
+        impl #impl_generics #name #type_generics #where_clause {
+            /// Puts each item of `iter` in the slot with the same index, failing unless it yields exactly one item per slot
+            ///
+            /// An iterator with too many items is consumed to the end so that every item is counted
+            pub fn try_from_iter<I: ::std::iter::IntoIterator<Item = #tipe>>(iter: I) -> ::std::result::Result<Self,#error> {
+                let mut iter = iter.into_iter();
+                #(
+                    let ::std::option::Option::Some(#idents) = iter.next() else {
+                        return ::std::result::Result::Err(#error { expected: #expected, received: #indices });
+                    };
+                )*
+                let surplus = iter.count();
+                if surplus > 0 {
+                    return ::std::result::Result::Err(#error { expected: #expected, received: #expected + surplus });
+                }
+                ::std::result::Result::Ok(#name {
+                    #(#idents,)*
+                    #(#extra_fields: ::std::default::Default::default(),)*
+                })
+            }
+        }
+    }
+}
+/// Creates an implementation of [`TryFrom<Vec<T>>`](TryFrom), which fails unless the vector holds exactly one element per slot
+pub(crate) fn generate_vec(layout: &Layout) -> TokenStream {
+    let name = &layout.structure.ident;
+    let (impl_generics, type_generics, where_clause) = layout.structure.generics.split_for_impl();
+    let error = type_name(name);
+    let tipe = layout.field_type;
+    let idents = layout.idents;
+    let extra_fields = layout.extra_fields;
+    let expected = idents.len();
+    quote! {
+        impl #impl_generics ::std::convert::TryFrom<::std::vec::Vec<#tipe>> for #name #type_generics #where_clause {
+            type Error = #error;
+            /// Moves each element of `vector` into the slot with the same index, failing unless its length equals the number of slots
+            fn try_from(vector: ::std::vec::Vec<#tipe>) -> ::std::result::Result<Self,#error> {
+                let received = vector.len();
+                let ::std::result::Result::Ok::<[#tipe; #expected],_>([#(#idents),*]) = ::std::convert::TryFrom::try_from(vector) else {
+                    return ::std::result::Result::Err(#error { expected: #expected, received });
+                };
+                ::std::result::Result::Ok(#name {
+                    #(#idents,)*
+                    #(#extra_fields: ::std::default::Default::default(),)*
+                })
+            }
+        }
+    }
+}
+/// Creates an implementation of [`FromIterator`], which panics unless the iterator yields exactly one item per slot
+pub(crate) fn generate_from_iter(layout: &Layout) -> TokenStream {
+    let name = &layout.structure.ident;
+    let (impl_generics, type_generics, where_clause) = layout.structure.generics.split_for_impl();
+    let tipe = layout.field_type;
+    let idents = layout.idents;
+    let extra_fields = layout.extra_fields;
+    let expected = idents.len();
+    let indices = 0..expected;
+    let message = format!("expected {} items for {}, but received {{}}",expected,name);
+    quote! {
+        impl #impl_generics ::std::iter::FromIterator<#tipe> for #name #type_generics #where_clause {
+            /// Puts each item of `iter` in the slot with the same index
+            ///
+            /// # Panics
+            /// Panics unless `iter` yields exactly one item per slot
+            fn from_iter<I: ::std::iter::IntoIterator<Item = #tipe>>(iter: I) -> Self {
+                let mut iter = iter.into_iter();
+                #(
+                    let ::std::option::Option::Some(#idents) = iter.next() else {
+                        panic!(#message,#indices);
+                    };
+                )*
+                let surplus = iter.count();
+                if surplus > 0 {
+                    panic!(#message,#expected + surplus);
+                }
+                #name {
+                    #(#idents,)*
+                    #(#extra_fields: ::std::default::Default::default(),)*
+                }
+            }
+        }
+    }
+}
@@ -15,50 +15,1100 @@ limitations under the License.
 */
 //! # Psuedo-Array Generation
 //! This crate allows for the generation of [`struct`]s with an arbitrary, programmer-provided number (less than [`u32::MAX`]) of identical fields with different names. 
-//! Generally speaking, it is also useful to use another crate, [`structinator`](https://crates.io/crates/structinator),
-//! on large [`struct`]s generated with this crate to allow your [`struct`] to be automatically constructed from an [`Iterator`].
+//! Large [`struct`]s generated with this crate can be constructed from an [`Iterator`] with the `from_iter` option, which implements [`FromIterator`], or the `try_from_iter` option, which rejects
+//! iterators of the wrong length with an error, so another crate such as [`structinator`](https://crates.io/crates/structinator) isn't needed.
 //!
 //! Psuedo-Array [`struct`]s like this are ideal for reducing data spent on identifiers in online databases like [Google Firebase](https://firebase.google.com).
 //!
 //! [`struct`]: https://doc.rust-lang.org/1.58.1/std/keyword.struct.html
 //! 
-//! To learn more about what this crate does, look at the documentation for this crates only public attribute, [`macro@faux_array`].
+//! # Crate Features
+//! - `serde` (enabled by default): adds serde attributes to the generated fields and allows options that generate serde implementations. Disabling it lets projects that only want the generated fields
+//!   avoid serde entirely.
+//! - `fuzz`: allows the `fuzz` option, which generates fuzzing harnesses.
+//! - `indexmap`: allows the `index_map` option, which generates conversions to and from [`IndexMap`](https://docs.rs/indexmap)s.
+//! - `metrics`: allows the `metrics` option, which reports how long generated serde implementations take.
+//! - `validator`: allows the `validate` option, which puts [`validator`](https://docs.rs/validator) attributes on every generated field.
+//!
+//! To learn more about what this crate does, look at the documentation for this crates main attribute, [`macro@faux_array`].
 //! 
+mod capnp;
+mod soa;
+mod delta;
+mod checksum;
+mod timestamps;
+mod tombstones;
+mod companion;
+mod tests;
+mod fuzz;
+mod borrowed;
+mod cells;
+mod atomics;
+mod staged;
+mod boxed;
+mod shadow;
+mod deserialize;
+mod progress;
+mod constant;
+mod accessors;
+mod slice;
+mod split;
+mod events;
+mod keys;
+mod batches;
+mod init;
+mod sections;
+mod reserved;
+mod numeric;
+mod digest;
+mod validate;
+mod tuple;
+mod canonical;
+mod scheme;
+mod indexed;
+mod declared;
+mod threads;
+mod named;
+mod tokens;
+mod length;
+mod visitor;
+mod predicates;
+mod encoding;
+mod base64;
+mod blob;
+mod namespace;
+mod index;
+mod iterators;
+mod objects;
+mod memory;
+mod generated;
+mod array;
+mod identity;
+mod tiers;
+mod defaults;
+mod assemble;
+mod len;
+mod buckets;
+mod lossy;
+mod assertions;
+mod encoder;
+mod mapping;
+mod mutation;
+mod enumeration;
+mod builder;
+mod unnamed;
 use proc_macro::TokenStream;
-use proc_macro2::Span;
-use syn;
-use syn::{Type,parse,ItemStruct,Ident,Token};
+use proc_macro2::{Span,TokenTree};
+use syn::{Type,parse,ItemStruct,Attribute,Ident,Token,LitInt,LitBool,Meta,MetaList,Visibility,Generics,GenericParam,LifetimeParam,Lifetime,WherePredicate,Expr,LitStr};
 use syn::token::Pound;
+use syn::punctuated::Punctuated;
 use syn::parse::{Parse,ParseStream};
-use std::str::FromStr;
 use quote::quote;
-use ascii_basing::encoding::encode;
-const ARGUMENT_ERROR_MESSAGE: &'static str = "The faux_array attribute should be given two arguments, the first of which should be a type and the second should be an integer";
+/// The option [`compose`] adds to the other `faux_array` attributes of a [`struct`](https://doc.rust-lang.org/1.58.1/std/keyword.struct.html), which users can't write themselves
+const COMPOSED_MARKER: &str = "__faux_composed";
+const ARGUMENT_ERROR_MESSAGE: &str = "The faux_array attribute should be given two arguments, the first of which should be a type and the second should be an integer (either positionally or as `ty = <type>, count = <integer>`), optionally followed by named options";
 struct Arguments {
     field_count: u32,
     field_type: Type,
+    capnp: bool,
+    soa: Option<Ident>,
+    delta: bool,
+    checksum: bool,
+    timestamps: bool,
+    tombstones: bool,
+    companion: Option<companion::Companion>,
+    gen_tests: bool,
+    fuzz: bool,
+    borrowed: Option<Ident>,
+    cells: Option<cells::Cells>,
+    staged: Option<Ident>,
+    boxed: bool,
+    shadow: Option<Type>,
+    progress: bool,
+    const_default: Option<Expr>,
+    accessors: Option<accessors::Scheme>,
+    serde_feature: Option<LitStr>,
+    repr_c: bool,
+    deref: bool,
+    split_keys: Option<u32>,
+    hot_cold: Option<u32>,
+    child_events: bool,
+    key_table: bool,
+    write_batches: bool,
+    init: Option<Expr>,
+    sections: Vec<sections::Section>,
+    reserved_keys: Vec<LitStr>,
+    sparse: bool,
+    key_digest: bool,
+    validate_with: Option<syn::Path>,
+    validation_error: Option<Type>,
+    validate: Option<proc_macro2::TokenStream>,
+    tuple_from: Option<u32>,
+    canonical_json: Option<Option<LitStr>>,
+    serde_bound: Option<(LitStr,Vec<WherePredicate>)>,
+    assert_send: Option<bool>,
+    assert_sync: Option<bool>,
+    assert_copy: bool,
+    assert_serialize_stable: bool,
+    named: Vec<named::Named>,
+    key_tokens: bool,
+    metadata_only: bool,
+    key_scheme: Option<u32>,
+    index_map: bool,
+    try_from_iter: bool,
+    try_from_vec: bool,
+    from_iter: bool,
+    serde: bool,
+    aliases: Vec<LitStr>,
+    deserialize: bool,
+    metrics: Option<syn::Path>,
+    name_format: Option<encoding::Template>,
+    container_attributes: Vec<Attribute>,
+    predicates: bool,
+    start: u32,
+    key_format: Option<encoding::Template>,
+    alphabet: Option<String>,
+    no_panic: bool,
+    blob: Option<String>,
+    key_encoder: Option<syn::Path>,
+    key_enum: Option<Option<Ident>>,
+    index_by_key: bool,
+    pad: Option<usize>,
+    namespace: Option<Type>,
+    index: bool,
+    iter_mut_indexed: bool,
+    get: bool,
+    heap_size: bool,
+    iter: bool,
+    allow: Option<Vec<syn::Path>>,
+    into_iter: bool,
+    array: bool,
+    new_with: bool,
+    builder: bool,
+    swap: bool,
+    fill: bool,
+    map: Option<mapping::Mapping>,
+    example_with: Option<syn::Path>,
+    field_names: bool,
+    lossy: bool,
+    time_buckets: Option<buckets::Buckets>,
+    len: bool,
+    assemble: bool,
+    default: bool,
+    identity: Vec<LitInt>,
+    composed: bool,
+    field_vis: Visibility,
+    field_attributes: Vec<Attribute>,
+    options: Vec<Ident>,
 }
-impl Parse for Arguments {
+/// The element type and number of slots, which come before any options
+struct Shape {
+    field_type: Type,
+    count: LitInt,
+}
+impl Parse for Shape {
+    /// Parses either the positional form, `<type>, <count>`, or the keyword form, `ty = <type>, count = <count>` (in either order)
     fn parse(input: ParseStream) -> Result<Self,syn::Error> {
-        let inner_type: Type = input.parse()?;
-        Ok(Arguments {
-            field_count: 0,
-            field_type: inner_type,
+        let required = |problem: syn::Error| syn::Error::new(problem.span(),format!("{}. {}",ARGUMENT_ERROR_MESSAGE,problem));
+        let keyword = input.fork().parse::<Ident>().is_ok_and(|first| first == "ty" || first == "count") && input.peek2(Token![=]);
+        if !keyword {
+            let field_type: Type = input.parse().map_err(required)?;
+            input.parse::<Token![,]>().map_err(required)?;
+            let count: LitInt = input.parse().map_err(required)?;
+            return Ok(Shape {
+                field_type,
+                count,
+            });
+        }
+        let mut field_type: Option<Type> = None;
+        let mut count: Option<LitInt> = None;
+        while field_type.is_none() || count.is_none() {
+            if count.is_some() || field_type.is_some() {
+                input.parse::<Token![,]>().map_err(required)?;
+            }
+            let keyword: Ident = input.parse().map_err(required)?;
+            match keyword.to_string().as_str() {
+                "ty" if field_type.is_none() => {
+                    input.parse::<Token![=]>().map_err(required)?;
+                    field_type = Some(input.parse().map_err(required)?);
+                },
+                "count" if count.is_none() => {
+                    input.parse::<Token![=]>().map_err(required)?;
+                    count = Some(input.parse().map_err(required)?);
+                },
+                "ty" | "count" => return Err(syn::Error::new(keyword.span(),format!("`{}` was given more than once",keyword))),
+                _ => return Err(syn::Error::new(keyword.span(),format!("{}. `ty` and `count` should both be given before `{}`",ARGUMENT_ERROR_MESSAGE,keyword))),
+            }
+        }
+        Ok(Shape {
+            field_type: field_type.expect("The loop only ends once both keywords are given"),
+            count: count.expect("The loop only ends once both keywords are given"),
         })
     }
 }
+impl Parse for Arguments {
+    fn parse(input: ParseStream) -> Result<Self,syn::Error> {
+        let shape: Shape = input.parse()?;
+        let mut arguments = Arguments {
+            field_count: shape.count.base10_parse()?,
+            field_type: shape.field_type,
+            capnp: false,
+            soa: None,
+            delta: false,
+            checksum: false,
+            timestamps: false,
+            tombstones: false,
+            companion: None,
+            gen_tests: false,
+            fuzz: false,
+            borrowed: None,
+            cells: None,
+            staged: None,
+            boxed: false,
+            shadow: None,
+            progress: false,
+            const_default: None,
+            accessors: None,
+            serde_feature: None,
+            repr_c: false,
+            deref: false,
+            split_keys: None,
+            hot_cold: None,
+            child_events: false,
+            key_table: false,
+            write_batches: false,
+            init: None,
+            sections: Vec::new(),
+            reserved_keys: Vec::new(),
+            sparse: false,
+            key_digest: false,
+            validate_with: None,
+            validation_error: None,
+            validate: None,
+            tuple_from: None,
+            canonical_json: None,
+            serde_bound: None,
+            assert_send: None,
+            assert_sync: None,
+            assert_copy: false,
+            assert_serialize_stable: false,
+            named: Vec::new(),
+            key_tokens: false,
+            metadata_only: false,
+            key_scheme: None,
+            index_map: false,
+            try_from_iter: false,
+            try_from_vec: false,
+            from_iter: false,
+            serde: true,
+            aliases: Vec::new(),
+            deserialize: false,
+            metrics: None,
+            name_format: None,
+            container_attributes: Vec::new(),
+            predicates: false,
+            start: 0,
+            key_format: None,
+            alphabet: None,
+            no_panic: false,
+            blob: None,
+            key_encoder: None,
+            key_enum: None,
+            index_by_key: false,
+            pad: None,
+            namespace: None,
+            index: false,
+            iter_mut_indexed: false,
+            get: false,
+            heap_size: false,
+            iter: false,
+            allow: None,
+            into_iter: false,
+            array: false,
+            new_with: false,
+            builder: false,
+            swap: false,
+            fill: false,
+            map: None,
+            example_with: None,
+            field_names: false,
+            lossy: false,
+            time_buckets: None,
+            len: false,
+            assemble: false,
+            default: false,
+            identity: Vec::new(),
+            composed: false,
+            field_vis: Visibility::Inherited,
+            field_attributes: Vec::new(),
+            options: Vec::new(),
+        };
+        let mut pad_width: Option<LitInt> = None;
+        let mut given: Vec<Ident> = Vec::new();
+        let mut serde_options: Vec<Ident> = Vec::new();
+        while !input.is_empty() {
+            input.parse::<Token![,]>()?;
+            if input.is_empty() {
+                break;
+            }
+            let option: Ident = input.parse()?;
+            if given.contains(&option) {
+                return Err(syn::Error::new(option.span(),format!("the `{}` option was given more than once",option)));
+            }
+            given.push(option.clone());
+            match option.to_string().as_str() {
+                "accessors" => {
+                    input.parse::<Token![=]>()?;
+                    arguments.accessors = if input.peek(LitBool) {
+                        input.parse::<LitBool>()?.value.then_some(accessors::Scheme::Get)
+                    } else {
+                        Some(input.parse()?)
+                    };
+                },
+                "borrowed" => {
+                    input.parse::<Token![=]>()?;
+                    arguments.borrowed = Some(input.parse()?);
+                },
+                "aliases" => {
+                    require_serde(&option,&mut serde_options)?;
+                    input.parse::<Token![=]>()?;
+                    let content;
+                    syn::bracketed!(content in input);
+                    arguments.aliases.extend(Punctuated::<LitStr,Token![,]>::parse_terminated(&content)?);
+                },
+                "allow" => {
+                    arguments.allow = if input.peek(Token![=]) {
+                        input.parse::<Token![=]>()?;
+                        let content;
+                        syn::bracketed!(content in input);
+                        Some(Punctuated::<syn::Path,Token![,]>::parse_terminated(&content)?.into_iter().collect())
+                    } else {
+                        Some(generated::default_lints())
+                    };
+                },
+                "alphabet" => {
+                    input.parse::<Token![=]>()?;
+                    let alphabet: LitStr = input.parse()?;
+                    encoding::check_alphabet(&alphabet)?;
+                    arguments.alphabet = Some(alphabet.value());
+                },
+                "blob" => {
+                    require_serde(&option,&mut serde_options)?;
+                    arguments.blob = if input.peek(Token![=]) {
+                        input.parse::<Token![=]>()?;
+                        Some(input.parse::<LitStr>()?.value())
+                    } else {
+                        Some(String::from(blob::BLOB_KEY))
+                    };
+                },
+                "array" => arguments.array = parse_flag(input)?,
+                "assert_send" => arguments.assert_send = Some(parse_flag(input)?),
+                "assert_sync" => arguments.assert_sync = Some(parse_flag(input)?),
+                "assert_copy" => arguments.assert_copy = parse_flag(input)?,
+                "assert_serialize_stable" => {
+                    require_serde(&option,&mut serde_options)?;
+                    arguments.assert_serialize_stable = parse_flag(input)?;
+                },
+                "boxed" => arguments.boxed = parse_flag(input)?,
+                "builder" => arguments.builder = parse_flag(input)?,
+                "bound" => {
+                    require_serde(&option,&mut serde_options)?;
+                    input.parse::<Token![=]>()?;
+                    let bounds: LitStr = input.parse()?;
+                    let predicates = bounds.parse_with(Punctuated::<WherePredicate,Token![,]>::parse_terminated)?;
+                    arguments.serde_bound = Some((bounds,predicates.into_iter().collect()));
+                },
+                "capnp" => arguments.capnp = parse_flag(input)?,
+                "canonical_json" => {
+                    require_serde(&option,&mut serde_options)?;
+                    arguments.canonical_json = if input.peek(Token![=]) {
+                        input.parse::<Token![=]>()?;
+                        Some(Some(input.parse()?))
+                    } else {
+                        Some(None)
+                    };
+                },
+                "cells" => {
+                    input.parse::<Token![=]>()?;
+                    arguments.cells = Some(input.parse()?);
+                },
+                "checksum" => {
+                    require_serde(&option,&mut serde_options)?;
+                    input.parse::<Token![=]>()?;
+                    let algorithm: Ident = input.parse()?;
+                    if algorithm != "crc32" {
+                        return Err(syn::Error::new(algorithm.span(),"crc32 is the only supported checksum algorithm"));
+                    }
+                    arguments.checksum = true;
+                },
+                "child_events" => {
+                    require_serde(&option,&mut serde_options)?;
+                    arguments.child_events = parse_flag(input)?;
+                },
+                "companion" => {
+                    let inside;
+                    syn::parenthesized!(inside in input);
+                    arguments.companion = Some(inside.parse()?);
+                },
+                "composed" => return Err(syn::Error::new(option.span(),"`composed` is not a faux_array option. The methods generated for the element type are left out automatically when the attribute is applied more than once")),
+                COMPOSED_MARKER => arguments.composed = true,
+                "container_attrs" => {
+                    input.parse::<Token![=]>()?;
+                    let content;
+                    syn::bracketed!(content in input);
+                    while !content.is_empty() {
+                        arguments.container_attributes.extend(content.call(Attribute::parse_outer)?);
+                        if content.is_empty() {
+                            break;
+                        }
+                        content.parse::<Token![,]>()?;
+                    }
+                },
+                "const_default" => {
+                    input.parse::<Token![=]>()?;
+                    arguments.const_default = Some(input.parse()?);
+                },
+                "deref" => arguments.deref = parse_flag(input)?,
+                "deserialize" => {
+                    require_serde(&option,&mut serde_options)?;
+                    arguments.deserialize = parse_flag(input)?;
+                },
+                "assemble" => arguments.assemble = parse_flag(input)?,
+                "default" => arguments.default = parse_flag(input)?,
+                "delta" => {
+                    require_serde(&option,&mut serde_options)?;
+                    arguments.delta = parse_flag(input)?;
+                },
+                "encoding" => {
+                    input.parse::<Token![=]>()?;
+                    let encoding: encoding::Encoding = input.parse()?;
+                    arguments.alphabet = Some(encoding.alphabet().to_owned());
+                },
+                "flatten_prefix" => {
+                    require_serde(&option,&mut serde_options)?;
+                    input.parse::<Token![=]>()?;
+                    let prefix: LitStr = input.parse()?;
+                    encoding::check_prefix(&prefix)?;
+                    arguments.key_format = Some(encoding::Template::prefixed(prefix.value()));
+                },
+                "fuzz" => {
+                    require_serde(&option,&mut serde_options)?;
+                    require_feature(&option,"fuzz",cfg!(feature = "fuzz"))?;
+                    arguments.fuzz = parse_flag(input)?;
+                },
+                "get" => arguments.get = parse_flag(input)?,
+                "gen_tests" => {
+                    require_serde(&option,&mut serde_options)?;
+                    arguments.gen_tests = parse_flag(input)?;
+                },
+                "split_keys" => {
+                    require_serde(&option,&mut serde_options)?;
+                    input.parse::<Token![=]>()?;
+                    let literal: LitInt = input.parse()?;
+                    let groups = literal.base10_parse()?;
+                    if groups == 0 || groups > arguments.field_count.max(1) {
+                        return Err(syn::Error::new(literal.span(),"the `split_keys` option should be at least 1 and no more than the number of slots"));
+                    }
+                    arguments.split_keys = Some(groups);
+                },
+                "sparse" => {
+                    require_serde(&option,&mut serde_options)?;
+                    arguments.sparse = parse_flag(input)?;
+                },
+                "swap" => arguments.swap = parse_flag(input)?,
+                "start" => {
+                    input.parse::<Token![=]>()?;
+                    let literal: LitInt = input.parse()?;
+                    let start: u32 = literal.base10_parse()?;
+                    if arguments.field_count > 0 && start.checked_add(arguments.field_count - 1).is_none() {
+                        return Err(syn::Error::new(literal.span(),format!("the `start` option leaves no room for {} slots, since the index of the last slot must be stored in a u32",arguments.field_count)));
+                    }
+                    arguments.start = start;
+                },
+                "staged" => {
+                    input.parse::<Token![=]>()?;
+                    arguments.staged = Some(input.parse()?);
+                },
+                "timestamps" => arguments.timestamps = parse_flag(input)?,
+                "tombstones" => {
+                    require_serde(&option,&mut serde_options)?;
+                    arguments.tombstones = parse_flag(input)?;
+                },
+                "heap_size" => arguments.heap_size = parse_flag(input)?,
+                "identity" => {
+                    input.parse::<Token![=]>()?;
+                    let content;
+                    let brackets = syn::bracketed!(content in input);
+                    arguments.identity = Punctuated::<LitInt,Token![,]>::parse_terminated(&content)?.into_iter().collect();
+                    identity::validate(&arguments.identity,brackets.span.join(),arguments.field_count)?;
+                },
+                "hot_cold" => {
+                    input.parse::<Token![=]>()?;
+                    let literal: LitInt = input.parse()?;
+                    let hot = literal.base10_parse()?;
+                    if hot == 0 || hot >= arguments.field_count {
+                        return Err(syn::Error::new(literal.span(),"the `hot_cold` option should be at least 1 and less than the number of slots, so that neither half is empty"));
+                    }
+                    arguments.hot_cold = Some(hot);
+                },
+                "index" => arguments.index = parse_flag(input)?,
+                "index_by_key" => arguments.index_by_key = parse_flag(input)?,
+                "index_map" => {
+                    require_feature(&option,"indexmap",cfg!(feature = "indexmap"))?;
+                    arguments.index_map = parse_flag(input)?;
+                },
+                "init" => {
+                    input.parse::<Token![=]>()?;
+                    arguments.init = Some(input.parse()?);
+                },
+                "into_iter" => arguments.into_iter = parse_flag(input)?,
+                "new_with" => arguments.new_with = parse_flag(input)?,
+                "iter" => arguments.iter = parse_flag(input)?,
+                "iter_mut_indexed" => arguments.iter_mut_indexed = parse_flag(input)?,
+                "key_digest" => arguments.key_digest = parse_flag(input)?,
+                "key_encoder" => {
+                    require_serde(&option,&mut serde_options)?;
+                    input.parse::<Token![=]>()?;
+                    arguments.key_encoder = Some(input.parse::<LitStr>()?.parse()?);
+                },
+                "key_enum" => {
+                    arguments.key_enum = if input.peek(Token![=]) {
+                        input.parse::<Token![=]>()?;
+                        Some(Some(input.parse()?))
+                    } else {
+                        Some(None)
+                    };
+                },
+                "key_table" => arguments.key_table = parse_flag(input)?,
+                "field_names" => arguments.field_names = parse_flag(input)?,
+                "field_attrs" => {
+                    input.parse::<Token![=]>()?;
+                    let content;
+                    syn::parenthesized!(content in input);
+                    while !content.is_empty() {
+                        arguments.field_attributes.extend(content.call(Attribute::parse_outer)?);
+                        if content.is_empty() {
+                            break;
+                        }
+                        content.parse::<Token![,]>()?;
+                    }
+                },
+                "field_vis" => {
+                    input.parse::<Token![=]>()?;
+                    if input.peek(Token![pub]) {
+                        arguments.field_vis = input.parse()?;
+                    } else {
+                        let private: Ident = input.parse()?;
+                        if private != "private" {
+                            return Err(syn::Error::new(private.span(),"the `field_vis` option should be `pub`, a restricted visibility like `pub(crate)`, or `private`"));
+                        }
+                        arguments.field_vis = Visibility::Inherited;
+                    }
+                },
+                "key_tokens" => arguments.key_tokens = parse_flag(input)?,
+                "write_batches" => arguments.write_batches = parse_flag(input)?,
+                "key_scheme" => {
+                    input.parse::<Token![=]>()?;
+                    let scheme: LitInt = input.parse()?;
+                    scheme::check(&scheme)?;
+                    arguments.key_scheme = Some(scheme.base10_parse()?);
+                },
+                "metrics" => {
+                    require_serde(&option,&mut serde_options)?;
+                    require_feature(&option,"metrics",cfg!(feature = "metrics"))?;
+                    input.parse::<Token![=]>()?;
+                    arguments.metrics = Some(input.parse::<LitStr>()?.parse()?);
+                },
+                "lossy" => {
+                    require_serde(&option,&mut serde_options)?;
+                    arguments.lossy = parse_flag(input)?;
+                },
+                "len" => arguments.len = parse_flag(input)?,
+                "map" => {
+                    if input.peek(syn::token::Paren) {
+                        let inside;
+                        syn::parenthesized!(inside in input);
+                        arguments.map = Some(inside.parse()?);
+                    } else if parse_flag(input)? {
+                        arguments.map = Some(mapping::Mapping::Generic(option.span()));
+                    }
+                },
+                "metadata_only" => arguments.metadata_only = parse_flag(input)?,
+                "named" => {
+                    input.parse::<Token![=]>()?;
+                    let content;
+                    syn::braced!(content in input);
+                    arguments.named.extend(Punctuated::<named::Named,Token![,]>::parse_terminated(&content)?);
+                    named::validate(&arguments.named,arguments.field_count)?;
+                },
+                "name_format" => {
+                    input.parse::<Token![=]>()?;
+                    arguments.name_format = Some(encoding::Template::parse(&input.parse()?,"name_format")?);
+                },
+                "namespace" => {
+                    input.parse::<Token![=]>()?;
+                    arguments.namespace = Some(input.parse()?);
+                },
+                "no_panic" => arguments.no_panic = parse_flag(input)?,
+                "pad" => {
+                    input.parse::<Token![=]>()?;
+                    let width: LitInt = input.parse()?;
+                    arguments.pad = Some(width.base10_parse()?);
+                    pad_width = Some(width);
+                },
+                "predicates" => arguments.predicates = parse_flag(input)?,
+                "prefix" => {
+                    input.parse::<Token![=]>()?;
+                    let prefix: LitStr = input.parse()?;
+                    if syn::parse_str::<Ident>(&format!("{}0",prefix.value())).is_err() {
+                        return Err(syn::Error::new(prefix.span(),"the `prefix` option should form an identifier when followed by a key, such as \"slot\""));
+                    }
+                    arguments.name_format = Some(encoding::Template::prefixed(prefix.value()));
+                },
+                "progress" => {
+                    require_serde(&option,&mut serde_options)?;
+                    arguments.progress = parse_flag(input)?;
+                },
+                "rename_format" => {
+                    require_serde(&option,&mut serde_options)?;
+                    input.parse::<Token![=]>()?;
+                    arguments.key_format = Some(encoding::Template::parse(&input.parse()?,"rename_format")?);
+                },
+                "repr_c" => arguments.repr_c = parse_flag(input)?,
+                "reserved_keys" => {
+                    input.parse::<Token![=]>()?;
+                    let content;
+                    syn::bracketed!(content in input);
+                    arguments.reserved_keys.extend(Punctuated::<LitStr,Token![,]>::parse_terminated(&content)?);
+                },
+                "sections" => {
+                    let content;
+                    syn::parenthesized!(content in input);
+                    arguments.sections.extend(Punctuated::<sections::Section,Token![,]>::parse_terminated(&content)?);
+                },
+                "serde" => arguments.serde = parse_flag(input)?,
+                "serde_feature" => {
+                    require_serde(&option,&mut serde_options)?;
+                    input.parse::<Token![=]>()?;
+                    arguments.serde_feature = Some(input.parse()?);
+                },
+                "shadow" => {
+                    input.parse::<Token![=]>()?;
+                    arguments.shadow = Some(input.parse()?);
+                },
+                "soa" => {
+                    input.parse::<Token![=]>()?;
+                    arguments.soa = Some(input.parse()?);
+                },
+                "time_buckets" => {
+                    if arguments.field_count == 0 {
+                        return Err(syn::Error::new(option.span(),"the `time_buckets` option requires at least one slot to hold a bucket"));
+                    }
+                    let inside;
+                    syn::parenthesized!(inside in input);
+                    arguments.time_buckets = Some(inside.parse()?);
+                },
+                "try_from_iter" => arguments.try_from_iter = parse_flag(input)?,
+                "try_from_vec" => arguments.try_from_vec = parse_flag(input)?,
+                "fill" => arguments.fill = parse_flag(input)?,
+                "from_iter" => arguments.from_iter = parse_flag(input)?,
+                "tuple_from" => {
+                    arguments.tuple_from = if input.peek(Token![=]) {
+                        input.parse::<Token![=]>()?;
+                        Some(input.parse::<LitInt>()?.base10_parse()?)
+                    } else {
+                        Some(tuple::DEFAULT_LIMIT)
+                    };
+                },
+                "validate" => {
+                    require_feature(&option,"validator",cfg!(feature = "validator"))?;
+                    let content;
+                    syn::parenthesized!(content in input);
+                    arguments.validate = Some(content.parse()?);
+                },
+                "example_with" => {
+                    input.parse::<Token![=]>()?;
+                    arguments.example_with = Some(input.parse::<LitStr>()?.parse()?);
+                },
+                "validate_with" => {
+                    input.parse::<Token![=]>()?;
+                    arguments.validate_with = Some(input.parse::<LitStr>()?.parse()?);
+                },
+                "validation_error" => {
+                    input.parse::<Token![=]>()?;
+                    arguments.validation_error = Some(input.parse()?);
+                },
+                unknown => return Err(syn::Error::new(option.span(),format!("`{}` is not a faux_array option",unknown))),
+            }
+        }
+        let span_of = |name: &str| given.iter().find(|option| *option == name).map_or(input.span(),|option| option.span());
+        if !arguments.serde {
+            if let Some(option) = serde_options.first() {
+                return Err(syn::Error::new(option.span(),format!("the `{}` option generates code that uses serde, so it can't be combined with `serde = false`",option)));
+            }
+        }
+        if arguments.checksum && arguments.split_keys.is_some() {
+            return Err(syn::Error::new(span_of("checksum"),"the `checksum` and `split_keys` options can't be combined, since both generate serde implementations"));
+        }
+        if arguments.deserialize && (arguments.checksum || arguments.split_keys.is_some()) {
+            return Err(syn::Error::new(span_of("deserialize"),"the `deserialize` option can't be combined with `checksum` or `split_keys`, since they also generate serde implementations"));
+        }
+        if arguments.default && arguments.init.is_some() {
+            return Err(syn::Error::new(span_of("default"),"the `default` option can't be combined with `init`, which also implements `Default`"));
+        }
+        if arguments.accessors.is_some() && !matches!(arguments.field_vis,Visibility::Inherited) {
+            return Err(syn::Error::new(span_of("field_vis"),"the `field_vis` option can't make the fields visible when combined with `accessors`, which keeps the fields private behind the generated accessors"));
+        }
+        if arguments.index_by_key && arguments.key_enum.is_none() {
+            return Err(syn::Error::new(span_of("index_by_key"),"the `index_by_key` option requires the `key_enum` option, which generates the enum it indexes with"));
+        }
+        if arguments.lossy && arguments.split_keys.is_some() {
+            return Err(syn::Error::new(span_of("lossy"),"the `lossy` option can't be combined with `split_keys`, which doesn't serialize each slot under its own top-level key"));
+        }
+        if arguments.blob.is_some() {
+            if arguments.cells.is_some() || !blob::supports(&arguments.field_type) {
+                return Err(syn::Error::new(span_of("blob"),"the `blob` option requires a primitive numeric element type other than `isize` or `usize`, since the slots are written as their little-endian bytes"));
+            }
+            if let Some(option) = given.iter().find(|option| ["aliases","checksum","deserialize","flatten_prefix","lossy","rename_format","sections","serde_feature","sparse","split_keys"].contains(&option.to_string().as_str())) {
+                return Err(syn::Error::new(option.span(),format!("the `{}` option can't be combined with `blob`, which writes every slot under one key",option)));
+            }
+        }
+        if arguments.key_encoder.is_some() {
+            if let Some(option) = given.iter().find(|option| ["aliases","assemble","blob","canonical_json","capnp","checksum","child_events","companion","delta","deserialize","field_names","flatten_prefix","fuzz","gen_tests","hot_cold","index_map","key_digest","key_enum","key_scheme","key_table","key_tokens","lossy","metadata_only","progress","rename_format","reserved_keys","sections","serde_feature","sparse","split_keys","time_buckets","timestamps","tombstones","write_batches"].contains(&option.to_string().as_str())) {
+                return Err(syn::Error::new(option.span(),format!("the `{}` option relies on the built-in keys, so it can't be combined with `key_encoder`",option)));
+            }
+        }
+        if arguments.metrics.is_some() && !arguments.checksum && !arguments.deserialize && arguments.split_keys.is_none() && arguments.blob.is_none() && arguments.key_encoder.is_none() {
+            return Err(syn::Error::new(span_of("metrics"),"the `metrics` option requires an option that generates serde implementations: `blob`, `checksum`, `deserialize`, `key_encoder`, or `split_keys`"));
+        }
+        if given.iter().any(|option| option == "encoding") && given.iter().any(|option| option == "alphabet") {
+            return Err(syn::Error::new(span_of("alphabet"),"the `alphabet` and `encoding` options can't be combined, since both choose the digits of the keys"));
+        }
+        if arguments.key_scheme.is_some() && arguments.alphabet.as_deref().is_some_and(|alphabet| alphabet != structurray_keys::ALPHABET) {
+            return Err(syn::Error::new(span_of("key_scheme"),"the `key_scheme` option pins the default Base62 encoding, so it can't be combined with a different `alphabet` or `encoding`"));
+        }
+        if let Some(width) = &pad_width {
+            if arguments.key_scheme.is_some() {
+                return Err(syn::Error::new(span_of("key_scheme"),"the `key_scheme` option pins the default Base62 encoding, so it can't be combined with `pad`"));
+            }
+            encoding::check_pad(&arguments,width)?;
+        }
+        for (first, second) in [("flatten_prefix","rename_format"),("prefix","name_format")] {
+            if given.iter().any(|option| option == first) && given.iter().any(|option| option == second) {
+                return Err(syn::Error::new(span_of(second),format!("the `{}` and `{}` options can't be combined, since both choose how slots are named",first,second)));
+            }
+        }
+        if let Some(option) = given.iter().find(|option| *option == "name_format") {
+            encoding::check_names(&arguments,option)?;
+        }
+        if let Some(option) = given.iter().find(|option| *option == "flatten_prefix" || *option == "rename_format").filter(|_| arguments.split_keys.is_some()) {
+            return Err(syn::Error::new(option.span(),format!("the `{}` option can't be combined with `split_keys`, which nests the slots in groups",option)));
+        }
+        if !arguments.aliases.is_empty() && arguments.split_keys.is_some() {
+            return Err(syn::Error::new(span_of("aliases"),"the `aliases` option can't be combined with `split_keys`, which doesn't serialize each slot under its own key"));
+        }
+        for template in &arguments.aliases {
+            let pattern = template.value();
+            if !pattern.contains("{}") || pattern == "{}" {
+                return Err(syn::Error::new(template.span(),"every alias should contain `{}`, which is replaced with the key of each slot, along with other text"));
+            }
+            if let Some(alias) = (0..arguments.field_count).map(|position| pattern.replace("{}",&encoding::key(&arguments,position))).find(|alias| reserved::generates(&arguments,alias)) {
+                return Err(syn::Error::new(template.span(),format!("the alias \"{}\" collides with a generated key",alias)));
+            }
+        }
+        if !arguments.sections.is_empty() {
+            sections::validate(&arguments.sections,arguments.field_count)?;
+            if arguments.capnp || arguments.soa.is_some() || arguments.delta || arguments.checksum || arguments.companion.is_some() || arguments.fuzz || arguments.borrowed.is_some() || arguments.cells.is_some()
+                || arguments.staged.is_some() || arguments.boxed || arguments.progress || arguments.accessors.is_some() || arguments.repr_c || arguments.split_keys.is_some() || arguments.child_events || arguments.write_batches || arguments.tuple_from.is_some() || arguments.array || arguments.assemble || arguments.assert_copy || arguments.assert_serialize_stable || arguments.new_with || arguments.builder || arguments.swap || arguments.fill || arguments.map.is_some() || arguments.example_with.is_some() || arguments.lossy || arguments.default || arguments.try_from_iter || arguments.try_from_vec || arguments.from_iter || arguments.deserialize || arguments.predicates || arguments.get || arguments.heap_size || arguments.hot_cold.is_some() || !arguments.identity.is_empty() || arguments.index || arguments.index_by_key || arguments.index_map || arguments.into_iter || arguments.iter || arguments.iter_mut_indexed || !arguments.named.is_empty() {
+                return Err(syn::Error::new(span_of("sections"),"the `sections` option can only be combined with options that don't depend on the element type, such as `const_default`, `init`, `key_table`, `shadow`, `timestamps`, and `tombstones`"));
+            }
+        }
+        reserved::check(&arguments,&arguments.reserved_keys)?;
+        if arguments.metadata_only {
+            if let Some(option) = given.iter().find(|option| !["allow","alphabet","capnp","encoding","field_names","key_digest","key_enum","key_scheme","key_table","len","metadata_only","namespace","pad","reserved_keys","start"].contains(&option.to_string().as_str())) {
+                return Err(syn::Error::new(option.span(),format!("the `{}` option generates code for the slots, so it can't be combined with `metadata_only`",option)));
+            }
+        }
+        if arguments.validation_error.is_some() && arguments.validate_with.is_none() {
+            return Err(syn::Error::new(span_of("validation_error"),"the `validation_error` option requires the `validate_with` option"));
+        }
+        if arguments.sparse {
+            let optional = matches!(&arguments.field_type,Type::Path(path) if path.path.segments.last().is_some_and(|segment| segment.ident == "Option"));
+            if !optional {
+                return Err(syn::Error::new(span_of("sparse"),"the `sparse` option requires an element type of `Option<T>`"));
+            }
+            if arguments.checksum || arguments.deserialize || arguments.split_keys.is_some() || arguments.cells.is_some() || !arguments.sections.is_empty() {
+                return Err(syn::Error::new(span_of("sparse"),"the `sparse` option can't be combined with `cells`, `checksum`, `deserialize`, `sections`, or `split_keys`"));
+            }
+        }
+        if arguments.deref && !arguments.repr_c {
+            return Err(syn::Error::new(span_of("deref"),"the `deref` option requires the `repr_c` option, which lays the slots out like an array"));
+        }
+        if let Some(feature) = &arguments.serde_feature {
+            if arguments.checksum || arguments.deserialize || arguments.child_events || arguments.split_keys.is_some() || arguments.delta || arguments.fuzz || arguments.gen_tests || arguments.lossy || arguments.progress || arguments.tombstones {
+                return Err(syn::Error::new(feature.span(),"the `serde_feature` option can't be combined with options that generate serde implementations"));
+            }
+        }
+        arguments.options = given;
+        Ok(arguments)
+    }
+}
+/// Rejects `option`, which generates code that uses serde, if this crate was compiled without its `serde` feature, and otherwise adds it to `serde_options` so it can be rejected if serde is turned off
+fn require_serde(option: &Ident, serde_options: &mut Vec<Ident>) -> Result<(),syn::Error> {
+    serde_options.push(option.clone());
+    require_feature(option,"serde",cfg!(feature = "serde"))
+}
+/// Rejects `option` unless the crate feature named `feature` is `enabled`
+fn require_feature(option: &Ident, feature: &str, enabled: bool) -> Result<(),syn::Error> {
+    if enabled {
+        Ok(())
+    } else {
+        Err(syn::Error::new(option.span(),format!("the `{}` option requires the `{}` feature of structurray",option,feature)))
+    }
+}
+/// Parses the value of an on/off option, which may be written either as `option = true`/`option = false` or as a bare `option`
+fn parse_flag(input: ParseStream) -> Result<bool,syn::Error> {
+    if input.peek(Token![=]) {
+        input.parse::<Token![=]>()?;
+        let value: LitBool = input.parse()?;
+        Ok(value.value)
+    } else {
+        Ok(true)
+    }
+}
+impl Layout<'_> {
+    /// Creates an attribute from `inner`, such as `serde(borrow)`, that only applies when the crate feature chosen with the `serde_feature` option is enabled, if one was chosen
+    fn serde_attribute(&self, inner: proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+        serde_attribute(self.serde_feature,inner)
+    }
+    /// Adds the bounds chosen with the `bound` option to `generics`, or `default` if no bounds were chosen, for implementations of serde traits
+    fn with_serde_bound(&self, generics: &Generics, default: WherePredicate) -> Generics {
+        match self.serde_bounds {
+            Some(bounds) => {
+                let mut bounded = generics.clone();
+                bounded.make_where_clause().predicates.extend(bounds.iter().cloned());
+                bounded
+            },
+            None => with_bound(generics,default),
+        }
+    }
+    /// Creates the panic of a generated method whose `index` argument isn't the index of a slot
+    fn out_of_range(&self) -> proc_macro2::TokenStream {
+        let count = self.idents.len();
+        quote! { ::std::panic!("index {} is out of range for a psuedo-array with {} slots",index,#count) }
+    }
+    /// Creates a statement that panics with [`out_of_range`](Self::out_of_range) unless the `index` argument of a generated method is the index of a slot
+    fn check_index(&self) -> proc_macro2::TokenStream {
+        let count = self.idents.len();
+        let out_of_range = self.out_of_range();
+        quote! {
+            if index as usize >= #count {
+                #out_of_range
+            }
+        }
+    }
+    /// Unwraps `lookup`, an [`Option`] that is [`None`] when the `index` argument of a generated method isn't the index of a slot, with `?` if the `no_panic` option was given and otherwise by
+    /// panicking with [`out_of_range`](Self::out_of_range)
+    ///
+    /// The result is meant to initialize a `let` binding, so that the rest of the method is written once for both cases.
+    fn unwrap_index(&self, lookup: proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+        if self.no_panic {
+            quote! { #lookup? }
+        } else {
+            let out_of_range = self.out_of_range();
+            quote! { #lookup.unwrap_or_else(|| #out_of_range) }
+        }
+    }
+    /// Creates the return type of a generated method that returns `output` once [`unwrap_index`](Self::unwrap_index) succeeds, which is wrapped in an [`Option`] if the `no_panic` option was given
+    ///
+    /// An empty `output` stands for `()`, and creates no return type at all unless the `no_panic` option was given.
+    fn checked_output(&self, output: proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+        match (self.no_panic, output.is_empty()) {
+            (true, true) => quote! { -> ::std::option::Option<()> },
+            (true, false) => quote! { -> ::std::option::Option<#output> },
+            (false, true) => quote! {},
+            (false, false) => quote! { -> #output },
+        }
+    }
+    /// Creates the value a generated method with the return type from [`checked_output`](Self::checked_output) ends with, where an empty `value` stands for `()`
+    fn checked_return(&self, value: proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+        match (self.no_panic, value.is_empty()) {
+            (true, true) => quote! { ::std::option::Option::Some(()) },
+            (true, false) => quote! { ::std::option::Option::Some(#value) },
+            (false, _) => value,
+        }
+    }
+    /// Documents when a generated method fails, which is when `failure` is true, as the condition under which it returns `None` if the `no_panic` option was given and otherwise as a `# Panics` section
+    fn checked_doc(&self, failure: &str) -> proc_macro2::TokenStream {
+        if self.no_panic {
+            let condition = format!(" Returns `None` if {}.",failure);
+            quote! {
+                #[doc = ""]
+                #[doc = #condition]
+            }
+        } else {
+            let condition = format!(" Panics if {}",failure);
+            quote! {
+                #[doc = ""]
+                #[doc = " # Panics"]
+                #[doc = #condition]
+            }
+        }
+    }
+    /// Wraps `body`, the body of a generated `serialize` or `deserialize` function returning `output`, so that it reports the `operation` to the function chosen with the `metrics` option, if one was chosen
+    fn instrument(&self, operation: &str, output: proc_macro2::TokenStream, body: proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+        let Some(hook) = self.metrics else {
+            return body;
+        };
+        let name = self.structure.ident.to_string();
+        let count = self.idents.len();
+        quote! {
+            let faux_started = ::std::time::Instant::now();
+            let faux_result: #output = (move || { #body })();
+            #hook(#name,#operation,#count,faux_started.elapsed(),faux_result.is_ok());
+            faux_result
+        }
+    }
+}
+/// Copies `attributes`, adding [`COMPOSED_MARKER`] to every other `faux_array` attribute that doesn't have it yet, and reports whether there were any
+///
+/// Every attribute applied to the same [`struct`](https://doc.rust-lang.org/1.58.1/std/keyword.struct.html) adds its own group of slots, so methods generated for the element type of a single group are left out.
+fn compose(attributes: &[Attribute]) -> (Vec<Attribute>,bool) {
+    let mut composing = false;
+    let attributes = attributes.iter().map(|attribute| match &attribute.meta {
+        Meta::List(list) if list.path.segments.last().is_some_and(|segment| segment.ident == "faux_array") => {
+            composing = true;
+            let tokens = &list.tokens;
+            if tokens.clone().into_iter().any(|token| matches!(token,TokenTree::Ident(ident) if ident == COMPOSED_MARKER)) {
+                return attribute.clone();
+            }
+            let marker = Ident::new(COMPOSED_MARKER,Span::call_site());
+            let separator = match tokens.clone().into_iter().last() {
+                Some(TokenTree::Punct(punct)) if punct.as_char() == ',' => quote! {},
+                _ => quote! { , },
+            };
+            let mut marked = attribute.clone();
+            marked.meta = Meta::List(MetaList {
+                tokens: quote! { #tokens #separator #marker },
+                ..list.clone()
+            });
+            marked
+        },
+        _ => attribute.clone(),
+    }).collect();
+    (attributes,composing)
+}
+/// Creates an attribute from `inner` that only applies when `feature` is enabled, or an ordinary attribute if there is no `feature`
+fn serde_attribute(feature: Option<&LitStr>, inner: proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+    let hashtag: Pound = Token![#](Span::call_site());
+    match feature {
+        Some(name) => quote! { #hashtag[cfg_attr(feature = #name,#inner)] },
+        None => quote! { #hashtag[#inner] },
+    }
+}
+/// Returns a copy of `generics` with a new lifetime parameter named `lifetime` placed before all other parameters
+fn with_lifetime(generics: &Generics, lifetime: &str) -> Generics {
+    let mut extended = generics.clone();
+    extended.params.insert(0,GenericParam::Lifetime(LifetimeParam::new(Lifetime::new(lifetime,Span::call_site()))));
+    extended
+}
+/// Creates a where clause requiring `tipe` to implement `bound`, for generated methods that only some element types support, or [`None`] if `tipe` is a boxed trait object
+///
+/// A bound that can never hold fails compilation, and boxed trait objects can't implement the bounds these methods need, such as [`Clone`] and [`Default`], so the methods are left out for them instead.
+fn element_bound(tipe: &Type, bound: proc_macro2::TokenStream) -> Option<proc_macro2::TokenStream> {
+    if objects::trait_object(tipe).is_some() {
+        return None;
+    }
+    Some(quote! { where #tipe: #bound })
+}
+/// Returns a copy of `generics` whose where clause also contains `bound`
+fn with_bound(generics: &Generics, bound: WherePredicate) -> Generics {
+    let mut bounded = generics.clone();
+    bounded.make_where_clause().predicates.push(bound);
+    bounded
+}
+/// Everything about a generated psuedo-array that code generators besides the [`struct`] definition itself need to know
+///
+/// [`struct`]: https://doc.rust-lang.org/1.58.1/std/keyword.struct.html
+struct Layout<'a> {
+    structure: &'a ItemStruct,
+    field_type: &'a Type,
+    names: &'a [String],
+    /// The encoded index of each slot, which its field and the identifiers generated for it are named after
+    suffixes: &'a [String],
+    idents: &'a [Ident],
+    /// The attributes placed before each slot, such as its serde `rename`
+    slot_attributes: &'a [proc_macro2::TokenStream],
+    /// The crate feature that serde attributes depend on, given by the `serde_feature` option
+    serde_feature: Option<&'a LitStr>,
+    /// Fields generated alongside the slots, which code that constructs the [`struct`] from its slots alone should fill using [`Default`]
+    extra_fields: &'a [Ident],
+    /// The fields besides the slots that generated serde implementations that read the whole [`struct`], such as those of `checksum` and `deserialize`, write and read under their own keys
+    carried: &'a [deserialize::Carried],
+    /// The bounds given with the `bound` option, which replace the bounds on the element type in generated serde implementations
+    serde_bounds: Option<&'a [WherePredicate]>,
+    /// The extra keys each slot is read from during deserialization, given by the `aliases` option
+    aliases: &'a [Vec<String>],
+    /// The function given with the `metrics` option, which is called after every generated serialization and deserialization
+    metrics: Option<&'a syn::Path>,
+    /// Whether the `no_panic` option was given, so that generated methods return [`Option`] instead of panicking
+    no_panic: bool,
+    /// Whether the `key_table` option was given, so that generated serde implementations look up the slot of each key with `index_of`
+    key_table: bool,
+}
 #[proc_macro_attribute]
 /// Converts your [`struct`] to a psuedo-array
 ///
 /// # Arguments
 /// This attribute macro should be invoked with two arguments. The first argument should be a type, such as [`u8`] or [`String`]. The second argument should be an [integer](u32) literal.
+/// These two arguments may be followed by any of the named options listed under [Options](#options), each separated by a comma.
+///
+/// The two arguments may also be given by keyword, as `ty = <type>` and `count = <integer>` in either order, which reads better once several options follow them. Both keywords must come before any options.
+/// ```
+/// # use structurray::faux_array;
+/// # use serde::Serialize;
+/// #[faux_array(ty = String, count = 500, key_table)]
+/// #[derive(Serialize)]
+/// struct Labels {}
+///
+/// #[faux_array(count = 2, ty = u8)]
+/// #[derive(Serialize,Default)]
+/// struct Pair {}
+///
+/// assert_eq!(Labels::KEYS.len(),500);
+/// assert_eq!(Pair::default()._1,0);
+/// ```
+///
+/// The arguments are parsed as Rust syntax rather than split on commas, so the first argument may be any type, including generic types whose arguments contain commas, tuples, and types containing function pointers:
+/// ```
+/// # use structurray::faux_array;
+/// # use serde::Serialize;
+/// use std::collections::HashMap;
+///
+/// #[faux_array(HashMap<String, u8>, 10)]
+/// #[derive(Serialize)]
+/// struct Tallies {}
+///
+/// #[faux_array((u8, char), 3)]
+/// #[derive(Serialize)]
+/// struct Pairs {}
+///
+/// #[faux_array(std::marker::PhantomData<fn(u8, u8) -> u8>, 2)]
+/// #[derive(Serialize)]
+/// struct Markers {}
+/// ```
 /// # Requirements
 /// This attribute must be attached to the definition of a [`struct`] that implements [serde::Serialize](https://docs.rs/serde/latest/serde). [`Serialize`] must be implemented because all fields will be `rename`d to their identifier with the leading underscore removed.
-/// This is because the intended use case of creating such a long [`struct`] is to save storage space in online databases, so [`struct`]s with this attribute should already have implemented [`Serialize`]. In a later version of this
-/// library, a third [`bool`] argument will allow for this attribute to apply to [`struct`]s not implementing [`Serialize`]. If you have a use case where it's ideal to have the option to attach this attribute to a [`struct`] not
-/// implementing [`Serialize`], feel free to look at this crate's [Github repository](https://github.com/script-mouse/structurray) and contribute or simply open an issue to let me know that there is demand for such a use case. Note that in order to derive [`Serialize`] on a
+/// This is because the intended use case of creating such a long [`struct`] is to save storage space in online databases, so [`struct`]s with this attribute should already have implemented [`Serialize`]. To attach this
+/// attribute to a [`struct`] that doesn't implement [`Serialize`], such as one that is only used internally, use the [`serde = false`](#serde) option. Note that in order to derive [`Serialize`] on a
 /// user-defined [`struct`], as shown here, requires use of `derive` features from [`serde`](https://docs.rs/serde/latest/serde).
 ///
+/// If this crate's default `serde` feature is disabled, none of the generated code refers to serde, so the [`struct`] doesn't need to implement [`Serialize`]. Options that only make sense alongside
+/// serde are rejected in that case: [`aliases`](#aliases), [`assert_serialize_stable`](#assert_copy-and-assert_serialize_stable), [`blob`](#blob), [`bound`](#bound),
+/// [`canonical_json`](#canonical_json), [`checksum`](#checksum), [`child_events`](#child_events), [`delta`](#delta), [`deserialize`](#deserialize), [`flatten_prefix`](#flatten_prefix),
+/// [`fuzz`](#fuzz), [`gen_tests`](#gen_tests), [`key_encoder`](#key_encoder), [`lossy`](#lossy), [`metrics`](#metrics), [`progress`](#progress), [`rename_format`](#rename_format),
+/// [`serde_feature`](#serde_feature), [`sparse`](#sparse), [`split_keys`](#split_keys), and [`tombstones`](#tombstones).
+///
 /// # Example
 /// Let's imagine you needed to make a [`struct`] with 3 identical fields. If you were feeling particularly lazy that day, you could use this library to quickly generate all the fields you needed. This snippet:
 /// ```
@@ -85,6 +1135,62 @@ impl Parse for Arguments {
 /// }
 /// ```
 /// While `Lazyrray` is a rather trivial example, the `faux_array` attribute can be quite useful when creating longer pseudo-arrays.
+/// # Declared Fields
+/// Fields you declare in the body of your [`struct`] are kept, with all of their attributes, in front of the generated fields. Compilation fails if one of them has the same name as a generated field,
+/// or is serialized under the same key as one. Generated code that builds your [`struct`] from its slots alone, such as the constructors of `init`, fills declared fields using [`Default`].
+/// ```
+/// # use structurray::faux_array;
+/// # use serde::Serialize;
+/// #[faux_array(u32,3)]
+/// #[derive(Serialize)]
+/// struct Scored {
+///     metadata: String,
+/// }
+///
+/// let scored = Scored { metadata: String::from("week 1"), _0: 7, _1: 8, _2: 9 };
+/// assert_eq!(scored.metadata,"week 1");
+/// ```
+/// # Field Groups
+/// Applying the attribute more than once to the same [`struct`] gives it several groups of slots, each with its own element type and number of slots. Each application keeps the slots of the others as declared
+/// fields, so the groups need different field names and keys, which the [`prefix`](#prefix) and [`rename_format`](#rename_format) options can give them. Options that generate a method can only be given to one
+/// of the groups, and the methods generated for [atomic](#atomic-elements), [numeric](#numeric-elements), and [trait object](#trait-object-elements) elements are left out, since they
+/// would otherwise be generated once for every group.
+#[cfg_attr(feature = "serde", doc = " ```")]
+#[cfg_attr(not(feature = "serde"), doc = " ```ignore")]
+/// # use structurray::faux_array;
+/// # use serde::Serialize;
+/// #[faux_array(f32,500,prefix = "reading",rename_format = "r{}")]
+/// #[faux_array(u64,500,prefix = "stamp",rename_format = "t{}",key_table)]
+/// #[derive(Serialize,Default)]
+/// struct Document {
+///     sensor: String,
+/// }
+///
+/// let mut document = Document::default();
+/// document.reading7 = 21.5;
+/// document.stamp7 = 1_700_000_000_000;
+/// assert_eq!(Document::KEYS[7],"t7");
+/// ```
+/// # Tuple Structs
+/// If your [`struct`] is written as a tuple struct without fields, like `struct Positions();`, the slots are generated as unnamed fields instead, in order, so the slot at index `i` is field `i`.
+/// Since a tuple struct doesn't derive the same serialization, a [`Serialize`] implementation that writes each slot under its key is generated for it, so you shouldn't derive [`Serialize`] yourself.
+/// Only options that don't rely on the slots having names can be combined with a tuple struct: `allow`, `alphabet`, `assert_send`, `assert_sync`, `bound`, `capnp`, `container_attrs`, `encoding`,
+/// `field_names`, `field_vis`, `key_digest`, `key_enum`, `key_scheme`, `key_table`, `len`, `namespace`, `pad`, `reserved_keys`, `serde`, and `start`.
+#[cfg_attr(feature = "serde", doc = " ```")]
+#[cfg_attr(not(feature = "serde"), doc = " ```ignore")]
+/// # use structurray::faux_array;
+/// # use serde::Serialize;
+/// #[faux_array(f32,3,field_names)]
+/// #[derive(Default)]
+/// struct Positions();
+///
+/// fn serializable<T: Serialize>(_: &T) {}
+///
+/// let positions = Positions(0.5,1.5,2.5);
+/// serializable(&positions);
+/// assert_eq!(positions.2,2.5);
+/// assert_eq!(Positions::FIELD_NAMES,["0","1","2"]);
+/// ```
 /// # Identifier Generation
 /// Identifiers are generated using a [Base62](https://en.wikipedia.org/wiki/Base62) algorithm described in detail in the documentation of [`ascii_basing`](https://docs.rs/ascii_basing/latest/ascii_basing).
 /// The algorithm uses the following 62 characters, in order from least value (0 = 0) to greatest value (Z = 61):
@@ -93,45 +1199,2328 @@ impl Parse for Arguments {
 /// 0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ
 /// # */
 /// ```
-/// # Panics
-/// Panics if the arguments are out of order or formatted incorrectly (most common cause of incorrect formatting is missing a comma). Panics if the first type can't be parsed to a type. Panics if the second argument cannot be parsed and stored in a [`u32`]. Panics if 
-/// the [`struct`] this attribute is attached to does not implement [`Serialize`].
+/// The same encoding is available at runtime through the [`structurray_keys`](https://docs.rs/structurray_keys) crate, whose `encode_index` and `decode_key` functions are what this attribute uses to name fields,
+/// so keys computed by application code always match the generated ones. A different numbering base can be chosen with the [`encoding`](#encoding) option.
+/// # Atomic Elements
+/// If the element type is one of the atomic types from [`std::sync::atomic`], such as [`AtomicU32`](std::sync::atomic::AtomicU32), two extra methods are generated for psuedo-arrays shared across threads.
+/// `load_all` loads every slot into an array of the underlying primitive type, and `store_all` stores every value of such an array into the slot with the same index. Both take the
+/// [`Ordering`](std::sync::atomic::Ordering) to use for each individual load or store, and access slots in index order.
+/// ```
+/// # use structurray::faux_array;
+/// # use serde::Serialize;
+/// use std::sync::atomic::{AtomicU32,Ordering};
 ///
-/// [`struct`]: https://doc.rust-lang.org/1.58.1/std/keyword.struct.html
-/// [`Serialize`]: https://docs.rs/serde/latest/serde
-pub fn faux_array(args: TokenStream, actual: TokenStream) -> TokenStream {
-    let string_holder = args.to_string();
-    let mut string_args = string_holder.split(',');
-    let first_string = string_args.next().expect(format!("{}. No arguments were found",ARGUMENT_ERROR_MESSAGE).as_str());
-    let mut arguments: Arguments = parse(TokenStream::from_str(first_string).expect("The arguments given could not be converted back to a TokenStream after being converted to a String. Make sure your arguments list is also a valid Rust String and TokenStream")).expect(format!("{}. The first argument was {} , which could not be converted to a type",ARGUMENT_ERROR_MESSAGE,first_string).as_str());
-    arguments.field_count = string_args.next().expect(format!("{}. Only one argument was found",ARGUMENT_ERROR_MESSAGE).as_str()).trim().parse().expect(format!("{}. The second argument could not be parsed to a u32. Make sure the second argument is an integer that can be stored in a u32",ARGUMENT_ERROR_MESSAGE).as_str());
-    let build_length = usize::try_from(arguments.field_count).expect(format!("{}. The second argument was successfully parsed to a u32, but failed conversion to a usize integer. Make sure the second argument is less than or equal to {}",ARGUMENT_ERROR_MESSAGE,usize::MAX).as_str());
-    let structure: ItemStruct = parse(actual).expect("The faux_array attribute should only be attached to struct definitions");
-    let attributes = &structure.attrs;
-    let visibility = &structure.vis;
-    let name = &structure.ident;
-    let generics = &structure.generics;
-    let tipe = arguments.field_type;
-    let mut names: Vec<String> = Vec::with_capacity(build_length);
-    let hashtag: Pound = Token![#](Span::call_site());
-    let mut idents: Vec<Ident> = Vec::with_capacity(build_length);
-    let mut copyscore = String::with_capacity(7);
-    let mut looper: u32 = 0;
-    while looper < arguments.field_count {
-        copyscore.push('_');
-        let new_name = encode(looper,None).expect("An unexpected error occurred. Please try again. If the error persists, contact me at richcreekbenjamin@gmail.com with a description of what is causing the bug");
-        copyscore.push_str(new_name.as_str());
-        names.push(new_name);
-        idents.push(Ident::new(&copyscore,Span::call_site()));
-        looper += 1;
-        copyscore.clear();
-    }
-    quote! {
-        #(#attributes)*
-        #visibility struct #name #generics {
-            #(#hashtag[serde(rename = #names)]
-            #idents : #tipe),*
-        }
-
-    }.into()
-}
\ No newline at end of file
+/// #[faux_array(AtomicU32,3)]
+/// #[derive(Serialize,Default)]
+/// struct Hits {}
+///
+/// let hits = Hits::default();
+/// hits._1.fetch_add(5,Ordering::Relaxed);
+/// assert_eq!(hits.load_all(Ordering::SeqCst),[0,5,0]);
+/// hits.store_all([1,2,3],Ordering::SeqCst);
+/// assert_eq!(hits._2.load(Ordering::SeqCst),3);
+/// ```
+/// # Numeric Elements
+/// If the element type is a primitive integer or floating-point type, `clamp_all(min, max)` is generated to restrict every slot to a range at once, which suits counter-style documents that are normalized periodically.
+/// Integer element types also get `saturating_add_all`, `saturating_sub_all`, and `saturating_mul_all`, which apply the same operation to every slot without overflowing.
+/// ```
+/// # use structurray::faux_array;
+/// # use serde::Serialize;
+/// #[faux_array(u8,3)]
+/// #[derive(Serialize,Default)]
+/// struct Counters {}
+///
+/// let mut counters = Counters::default();
+/// counters._0 = 250;
+/// counters.saturating_add_all(10);
+/// assert_eq!((counters._0,counters._1),(255,10));
+/// counters.clamp_all(20,100);
+/// assert_eq!((counters._0,counters._1),(100,20));
+/// ```
+/// # Trait Object Elements
+/// If the element type is a boxed trait object, such as `Box<dyn Plugin + Send>`, `as_dyn(index)` and `as_dyn_mut(index)` borrow the trait object in a slot, returning [`None`] if the index is out
+/// of range, and `iter_dyn()` borrows every trait object in index order, so slots holding different implementations can be used without unboxing each one by hand. Methods that other options generate
+/// with bounds on the element type that trait objects can't meet, such as `new_boxed_filled` (which needs [`Clone`]), are left out instead of failing compilation.
+/// [`Serialize`] isn't implemented for trait objects, so such psuedo-arrays usually need the [`serde = false`](#serde) option.
+/// ```
+/// # use structurray::faux_array;
+/// trait Plugin {
+///     fn name(&self) -> &str;
+/// }
+/// struct Logger;
+/// impl Plugin for Logger {
+///     fn name(&self) -> &str { "logger" }
+/// }
+/// struct Cache;
+/// impl Plugin for Cache {
+///     fn name(&self) -> &str { "cache" }
+/// }
+///
+/// #[faux_array(Box<dyn Plugin + Send>,2,serde = false,boxed,predicates)]
+/// struct Plugins {}
+///
+/// let plugins = Plugins { _0: Box::new(Logger), _1: Box::new(Cache) };
+/// assert_eq!(plugins.as_dyn(1).map(|plugin| plugin.name()),Some("cache"));
+/// assert_eq!(plugins.iter_dyn().map(|plugin| plugin.name()).collect::<Vec<_>>(),["logger","cache"]);
+/// assert!(plugins.any(|plugin| plugin.name() == "logger"));
+/// ```
+/// # Options
+/// Options are written after the two required arguments in the form `name = value`. Options that can only be turned on or off may also be turned on by writing their name alone, so `capnp` means the same thing as `capnp = true`. Each option can only be given once.
+/// ## `accessors`
+/// Generates accessor methods so that the generated fields, which are private, never need to be exposed by a library, which keeps the layout of the fields free to change. Since teams disagree on
+/// how generated methods should be named, the naming scheme is chosen by the value of this option, where `accessors = true` is the same as `accessors = get`, and `accessors = false` generates nothing:
+/// - `accessors = get` generates `get_<key>`, `get_<key>_mut`, and `set_<key>` for every slot, such as `get_b3`.
+/// - `accessors = key` generates `<key>`, `<key>_mut`, and `set_<key>` for every slot, such as `b3`. Keys starting with a digit are given a leading underscore, since identifiers can't start with digits,
+///   and keys that are Rust keywords become raw identifiers (such as `r#if`).
+/// - `accessors = value_at` generates `value_at`, `value_at_mut`, and `set_value_at`, which take the index of a slot and panic if it is out of range.
+///
+/// Setters return the value they replaced.
+/// ```
+/// # use structurray::faux_array;
+/// # use serde::Serialize;
+/// #[faux_array(char,12,accessors = key)]
+/// #[derive(Serialize,Default)]
+/// struct Letters {}
+///
+/// let mut letters = Letters::default();
+/// letters.set_b('x');
+/// *letters._3_mut() = 'y';
+/// assert_eq!((*letters.b(),*letters._3()),('x','y'));
+/// ```
+/// Outside of the module your [`struct`] is defined in, the slots can only be reached through the accessors:
+/// ```
+/// mod library {
+///     # use structurray::faux_array;
+///     # use serde::Serialize;
+///     #[faux_array(u32,4,accessors = true)]
+///     #[derive(Serialize,Default)]
+///     pub struct Totals {}
+/// }
+///
+/// let mut totals = library::Totals::default();
+/// totals.set_3(12);
+/// assert_eq!(*totals.get_3(),12);
+/// ```
+/// Reaching the field of a slot directly from outside that module fails to compile:
+/// ```compile_fail
+/// mod library {
+///     # use structurray::faux_array;
+///     # use serde::Serialize;
+///     #[faux_array(u32,4,accessors = true)]
+///     #[derive(Serialize,Default)]
+///     pub struct Totals {}
+/// }
+///
+/// let totals = library::Totals::default();
+/// assert_eq!(totals._3,0);
+/// ```
+/// ## `aliases`
+/// Written as `aliases = ["<template>", ...]`. Adds `#[serde(alias = "...")]` to every slot for each template, with `{}` in the template replaced by the slot's key, so a derived [`Deserialize`] also accepts
+/// documents written under other keys. For example, `aliases = ["_{}"]` reads slot 0 from either `"0"` or `"_0"`, which accepts documents written before the keys were `rename`d. Serialization still uses the
+/// slot's key. The serde implementations generated by other options, such as `checksum` and `deserialize`, accept the aliases as well. Compilation fails if an alias collides with a generated key.
+#[cfg_attr(feature = "serde", doc = " ```")]
+#[cfg_attr(not(feature = "serde"), doc = " ```ignore")]
+/// # use structurray::faux_array;
+/// use serde::{Serialize,Deserialize};
+/// use serde::de::value::{MapDeserializer,Error};
+///
+/// #[faux_array(u8,2,aliases = ["_{}","slot{}"])]
+/// #[derive(Serialize,Deserialize)]
+/// struct Legacy {}
+///
+/// let document = vec![("_0",4u8),("slot1",5u8)];
+/// let legacy = Legacy::deserialize(MapDeserializer::<_,Error>::new(document.into_iter())).unwrap();
+/// assert_eq!((legacy._0,legacy._1),(4,5));
+/// ```
+/// ## `allow`
+/// Written as `allow` or `allow = [<lint>, ...]`. Adds `#[allow(...)]` with the given lints (`non_snake_case`, `non_camel_case_types`, and `clippy::too_many_lines` if no list is given) to your [`struct`]
+/// and every item generated for it, and marks every generated implementation with `#[automatically_derived]`. Workspaces that deny lints globally then don't trip over expansions with thousands of
+/// fields and methods, and coverage tools that skip automatically derived code leave the generated implementations out of their reports.
+/// ```
+/// # use structurray::faux_array;
+/// # use serde::Serialize;
+/// #[faux_array(u8,3,name_format = "Slot{}",allow = [non_snake_case,clippy::too_many_lines],iter,index)]
+/// #[derive(Serialize,Default)]
+/// struct Quiet {}
+///
+/// let quiet = Quiet::default();
+/// assert_eq!(quiet.Slot2,quiet[2]);
+/// ```
+/// ## `alphabet`
+/// Written as `alphabet = "<digits>"`. Encodes the keys, and so the generated field names, using your own digits, in order from least value to greatest value, for backends that forbid some characters
+/// or don't distinguish upper and lower case. The number of digits is the numbering base, so `alphabet = "0123456789abcdef"` generates hexadecimal keys. The alphabet must have at least two characters,
+/// none of them repeated, and every character must be able to appear in an identifier. The keys are available at runtime through `structurray_keys::encode_index_with`. It can't be combined with
+/// `encoding` or `key_scheme`.
+/// ```
+/// # use structurray::faux_array;
+/// # use serde::Serialize;
+/// #[faux_array(u8,40,alphabet = "0123456789abcdefghjkmnpqrstvwxyz",key_table)]
+/// #[derive(Serialize,Default)]
+/// struct Crockford {}
+///
+/// let crockford = Crockford::default();
+/// assert_eq!(crockford._17,0);
+/// assert_eq!(Crockford::KEYS[18],"j");
+/// assert_eq!(Crockford::index_of("10"),Some(32));
+/// ```
+/// ## `array`
+/// Written as `array` or `array = <bool>`. Implements [`From`] to convert an array with one element per slot into your [`struct`], moving each element into the slot with the same index, and to convert
+/// your [`struct`] back into such an array, which bridges code written against real arrays and the serialized psuedo-array. Other fields are filled with their [`Default`] values when converting from an array,
+/// and dropped when converting to one.
+/// ```
+/// # use structurray::faux_array;
+/// # use serde::Serialize;
+/// #[faux_array(u8,3,array)]
+/// #[derive(Serialize)]
+/// struct Rgb {}
+///
+/// let teal = Rgb::from([0,128,128]);
+/// assert_eq!(teal._1,128);
+/// let channels: [u8; 3] = teal.into();
+/// assert_eq!(channels,[0,128,128]);
+/// ```
+/// ## `assemble`
+/// Written as `assemble` or `assemble = <bool>`. Generates `assemble(parts)`, which merges several partial maps from keys to values (such as the results of sharded reads) into one instance of your
+/// [`struct`], and an error type named after your [`struct`] with `MissingKeys` appended. When several maps hold the same key, the value from the last of them is kept, and keys that don't belong to
+/// any slot are ignored. If any slot is missing from every map, the error lists exactly which keys were missing, in the order of their slots.
+/// ```
+/// # use structurray::faux_array;
+/// # use serde::Serialize;
+/// use std::collections::HashMap;
+///
+/// #[faux_array(u8,4,assemble)]
+/// #[derive(Serialize,Debug)]
+/// struct Shards {}
+///
+/// let first = HashMap::from([(String::from("0"),1),(String::from("1"),2)]);
+/// let second = HashMap::from([(String::from("3"),4)]);
+/// let missing = Shards::assemble([first.clone(),second.clone()]).unwrap_err();
+/// assert_eq!(missing.keys,vec!["2"]);
+/// assert_eq!(missing.to_string(),"1 keys of Shards were missing: 2");
+/// let third = HashMap::from([(String::from("2"),3)]);
+/// assert_eq!(Shards::assemble([first,second,third]).unwrap()._2,3);
+/// ```
+/// ## `assert_copy` and `assert_serialize_stable`
+/// Written as `assert_copy`, `assert_serialize_stable`, or either with `= <bool>`. Fails compilation at the definition of your [`struct`] unless the element type implements [`Copy`] (for `assert_copy`), or
+/// unless it implements both [`Serialize`](https://docs.rs/serde/latest/serde/trait.Serialize.html) and [`DeserializeOwned`](https://docs.rs/serde/latest/serde/de/trait.DeserializeOwned.html), so that
+/// every slot can be written and read back without borrowing from the input (for `assert_serialize_stable`). These let schema authors record invariants their code relies on, such as a [`Copy`] element
+/// type for `repr_c`, in the attribute itself. If the element type is one of your generic parameters, it must be bounded by the asserted traits.
+#[cfg_attr(feature = "serde", doc = " ```")]
+#[cfg_attr(not(feature = "serde"), doc = " ```ignore")]
+/// # use structurray::faux_array;
+/// # use serde::Serialize;
+/// #[faux_array(f32,16,repr_c,assert_copy,assert_serialize_stable)]
+/// #[derive(Serialize)]
+/// struct Samples {}
+/// ```
+/// A borrowed string can't be deserialized without borrowing from the input, so this fails to compile:
+/// ```compile_fail
+/// # use structurray::faux_array;
+/// # use serde::Serialize;
+/// #[faux_array(&'static str,4,assert_serialize_stable)]
+/// #[derive(Serialize)]
+/// struct Labels {}
+/// ```
+/// ## `assert_send` and `assert_sync`
+/// Written as `assert_send`, `assert_sync`, or either with `= <bool>`. Fails compilation at the definition of your [`struct`] unless it implements [`Send`] (or [`Sync`]) when the value is `true`,
+/// or unless it doesn't implement the trait when the value is `false`. This catches a change to a non-thread-safe element type where the psuedo-array is defined, rather than wherever it is shared across threads.
+/// ```
+/// # use structurray::faux_array;
+/// # use serde::Serialize;
+/// use std::cell::Cell;
+///
+/// #[faux_array(u64,8,assert_send,assert_sync)]
+/// #[derive(Serialize)]
+/// struct Shared {}
+///
+/// #[faux_array(Cell<u64>,8,assert_send,assert_sync = false)]
+/// #[derive(Serialize)]
+/// struct Local {}
+/// ```
+/// A [`Cell`](std::cell::Cell) element type can't be shared across threads, so this fails to compile:
+/// ```compile_fail
+/// # use structurray::faux_array;
+/// # use serde::Serialize;
+/// # use std::cell::Cell;
+/// #[faux_array(Cell<u64>,8,assert_sync)]
+/// #[derive(Serialize)]
+/// struct Local {}
+/// ```
+/// ## `blob`
+/// Written as `blob` or `blob = "key"`. Generates [`Serialize`] and [`Deserialize`] implementations that write every slot as its little-endian bytes, in index order, in a single base64 string under
+/// one key (`"blob"` unless another is given), for documents where the slots are only ever read and written together and even one short key per slot costs too much. Requires a primitive numeric element
+/// type other than [`isize`] or [`usize`], whose sizes depend on the platform. Your [`struct`] shouldn't derive [`Serialize`] or [`Deserialize`], fields besides the slots aren't written, and deserialization
+/// fills them with their [`Default`] values. Options that change how individual slots are written, like `aliases`, `flatten_prefix`, `sparse`, and `split_keys`, can't be combined with it.
+#[cfg_attr(feature = "serde", doc = " ```")]
+#[cfg_attr(not(feature = "serde"), doc = " ```ignore")]
+/// # use structurray::faux_array;
+/// use serde::Deserialize;
+/// use serde::de::value::{MapDeserializer,Error};
+///
+/// #[faux_array(u16,3,blob = "b")]
+/// struct Packed {}
+///
+/// let document = vec![("b","AQACAAMD")];
+/// let packed = Packed::deserialize(MapDeserializer::<_,Error>::new(document.into_iter())).unwrap();
+/// assert_eq!((packed._0,packed._1,packed._2),(1,2,0x0303));
+/// let truncated = Packed::deserialize(MapDeserializer::<_,Error>::new(vec![("b","AQACAA==")].into_iter()));
+/// assert!(truncated.is_err());
+/// ```
+/// ## `borrowed`
+/// Generates a borrowed twin of your [`struct`] with the name given to this option, such as `borrowed = LazyrrayRef`. Each slot of the twin is a [`Cow`](std::borrow::Cow), so it can either borrow a value
+/// from somewhere else or own one, which lets read paths over cached or memory-mapped documents avoid cloning thousands of values. The twin takes a lifetime parameter before the generic parameters
+/// of your [`struct`], requires the element type to implement [`Clone`], and converts back with `into_owned`, which only clones the slots that are still borrowed.
+/// The generated `as_borrowed` method creates a twin borrowing every slot of an existing instance. When serde attributes are generated, the twin also derives [`Serialize`] and [`Deserialize`], borrowing
+/// from the input whenever serde is able to.
+/// ```
+/// # use structurray::faux_array;
+/// # use serde::Serialize;
+/// #[faux_array(String,2,borrowed = NamesRef)]
+/// #[derive(Serialize)]
+/// struct Names {}
+///
+/// let names = Names { _0: String::from("Ada"), _1: String::from("Grace") };
+/// let mut view = names.as_borrowed();
+/// view._1.to_mut().push_str(" Hopper");
+/// let copy: Names = view.into_owned();
+/// assert_eq!(copy._1,"Grace Hopper");
+/// ```
+/// ## `bound`
+/// Written as `bound = "<where predicates>"`. Replaces the bounds serde would infer for your [`struct`] with your own, by adding `#[serde(bound = "...")]` to it, and uses the same bounds instead of
+/// requiring the element type to implement [`Serialize`] or [`Deserialize`] in the serde implementations generated by other options. This is needed when the element type wraps a generic parameter
+/// that doesn't have to be serializable itself, since serde's inferred bounds would require it anyway.
+#[cfg_attr(feature = "serde", doc = " ```")]
+#[cfg_attr(not(feature = "serde"), doc = " ```ignore")]
+/// # use structurray::faux_array;
+/// use serde::{Serialize,Serializer};
+/// use std::marker::PhantomData;
+///
+/// // Serializes only the ID, so T doesn't need to be serializable
+/// struct Handle<T>(u32,PhantomData<T>);
+/// impl<T> Serialize for Handle<T> {
+///     fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok,S::Error> {
+///         serializer.serialize_u32(self.0)
+///     }
+/// }
+///
+/// #[faux_array(Handle<T>,2,bound = "")]
+/// #[derive(Serialize)]
+/// struct Handles<T> {}
+///
+/// struct Opaque;
+/// fn serializable<S: Serialize>() {}
+/// serializable::<Handles<Opaque>>();
+/// ```
+/// ## `boxed`
+/// Generates constructors that build an instance directly on the heap, one slot at a time, for psuedo-arrays too large to construct on the stack and then move into a [`Box`].
+/// `new_boxed_filled` puts a clone of the given value in every slot, and `new_boxed_default` puts the element type's [`Default`] value (or the `init` expression, if given) in every slot.
+/// Fields generated by other options, such as timestamps, are filled using [`Default`]. Like `staged`, the generated code uses `unsafe` internally.
+/// ```
+/// # use structurray::faux_array;
+/// # use serde::Serialize;
+/// #[faux_array(u64,4096,boxed)]
+/// #[derive(Serialize)]
+/// struct Huge {}
+///
+/// let huge: Box<Huge> = Huge::new_boxed_filled(7);
+/// assert_eq!(huge._2Z,7);
+/// ```
+/// ## `builder`
+/// Written as `builder` or `builder = <bool>`. Generates a builder named after your [`struct`] with `Builder` appended, created with `builder()`, which collects the slots one at a time through
+/// `set(index, value)` (which can be chained) and checks whether a slot has been set with `is_set(index)`. Its `build()` method creates your [`struct`], or fails with an error type named after
+/// your [`struct`] with `BuilderError` appended, which lists the indices of every slot that wasn't set. Like the `value_at` accessors, `set` panics if the index is out of range, unless `no_panic`
+/// is given. Fields generated by other options, such as timestamps, are filled using [`Default`].
+/// ```
+/// # use structurray::faux_array;
+/// # use serde::Serialize;
+/// #[faux_array(u16,4,builder)]
+/// #[derive(Serialize,Debug)]
+/// struct Ports {}
+///
+/// let mut builder = Ports::builder();
+/// builder.set(0,80).set(1,443).set(3,8080);
+/// assert!(!builder.is_set(2));
+/// let error = builder.build().unwrap_err();
+/// assert_eq!(error.missing,vec![2]);
+///
+/// let mut builder = Ports::builder();
+/// for index in 0..4 {
+///     builder.set(index,index as u16);
+/// }
+/// assert_eq!(builder.build().unwrap()._3,3);
+/// ```
+/// ## `canonical_json`
+/// Written as `canonical_json` or `canonical_json = "feature"`. Generates `to_canonical_json()`, which writes your [`struct`] as pretty-printed JSON with the keys of every object sorted, so snapshot tests of
+/// psuedo-array documents stay stable no matter how serde or a map type orders keys. When a feature name is given, the method only exists when that feature of your crate is enabled, which keeps it out of release builds.
+/// The method uses [`serde_json`](https://docs.rs/serde_json), so it must be a dependency of your crate (an optional one if a feature name is given), and your [`struct`] must implement [`Serialize`].
+/// It panics if your [`struct`] can't be represented as JSON, unless `no_panic` is given, in which case it returns the `serde_json` error instead.
+/// ```no_run
+/// # /*
+/// #[faux_array(u8,3,canonical_json = "snapshots")]
+/// #[derive(Serialize,Default)]
+/// struct Snapshot {}
+///
+/// assert_eq!(Snapshot::default().to_canonical_json(),"{\n  \"0\": 0,\n  \"1\": 0,\n  \"2\": 0\n}");
+/// # */
+/// ```
+/// ## `capnp`
+/// Adds an associated constant, `CAPNP_SCHEMA`, containing a [Cap'n Proto](https://capnproto.org/language.html) definition of your [`struct`] for services that exchange psuedo-arrays over Cap'n Proto RPC.
+/// The ordinal of each field is the index it was generated from, so the schema stays compatible with older versions of itself as long as the field count only grows.
+/// Since Cap'n Proto doesn't allow underscores in field names, each field is named `slot` followed by its serde key. Primitive types are translated to their Cap'n Proto equivalents, and any other type
+/// (including generic parameters) is written using its own name, so it should match a type or generic parameter declared in the same schema file.
+/// ```
+/// # use structurray::faux_array;
+/// # use serde::Serialize;
+/// #[faux_array(u16,2,capnp)]
+/// #[derive(Serialize)]
+/// struct Ports {}
+///
+/// assert_eq!(Ports::CAPNP_SCHEMA,"struct Ports {\n  slot0 @0 :UInt16;\n  slot1 @1 :UInt16;\n}\n");
+/// ```
+/// ## `cells`
+/// Written as `cells = cell` or `cells = refcell`. Wraps every slot in a [`Cell`](std::cell::Cell) or [`RefCell`](std::cell::RefCell) so that single-threaded code can update many slots through a shared reference,
+/// without threading a mutable reference through the whole call graph. Every other option treats the wrapped type as the type of each slot. Slots are accessed by index with the generated `set_value` method,
+/// which replaces a slot's value and returns the old one, and either `get_value` (for `cell`, when the element type implements [`Copy`]) or `borrow_slot` and `borrow_slot_mut` (for `refcell`).
+/// Each of these methods panics if the index is out of range.
+/// ```
+/// # use structurray::faux_array;
+/// # use serde::Serialize;
+/// #[faux_array(u32,3,cells = cell)]
+/// #[derive(Serialize,Default)]
+/// struct Population {}
+///
+/// let population = Population::default();
+/// let shared = &population;
+/// shared.set_value(2,40);
+/// assert_eq!(population.get_value(2),40);
+/// ```
+/// ## `checksum`
+/// Written as `checksum = crc32`. Instead of relying on a derived [`Serialize`], the attribute implements [`Serialize`] and [`Deserialize`] itself, writing one extra `"checksum"` key holding a
+/// [CRC-32](https://en.wikipedia.org/wiki/Cyclic_redundancy_check) of every slot and refusing to deserialize documents whose slots don't match it. This catches documents that were truncated or
+/// corrupted by an unreliable sync layer. Each slot is fed to the checksum through its [`Serialize`] implementation in a fixed encoding (numbers as their little-endian bytes, strings and sequences
+/// after their length as a `u64`), so the checksum doesn't depend on the platform that computed it. Declared fields are written and read under their names, and are covered by the checksum after
+/// the slots. The checksum is also available through the generated `checksum` method, which panics if a field fails to serialize. Because the implementations are generated, a [`struct`] using this option must **not** derive [`Serialize`] or [`Deserialize`],
+/// and compilation fails if a slot would also be serialized under `"checksum"`, such as with `rename_format`.
+#[cfg_attr(feature = "serde", doc = " ```")]
+#[cfg_attr(not(feature = "serde"), doc = " ```ignore")]
+/// # use structurray::faux_array;
+/// #[faux_array(u32,2,checksum = crc32)]
+/// struct Guarded {}
+///
+/// let guarded = Guarded { _0: 7, _1: 11 };
+/// let tampered = Guarded { _0: 7, _1: 12 };
+/// assert_ne!(guarded.checksum(),tampered.checksum());
+/// // The CRC-32 of the little-endian bytes of 7 and 11, on every platform
+/// assert_eq!(guarded.checksum(),0xb8e6_5171);
+///
+/// #[faux_array(u32,2,checksum = crc32)]
+/// struct Noted {
+///     note: String,
+/// }
+///
+/// let noted = Noted { note: String::from("first"), _0: 7, _1: 11 };
+/// let renoted = Noted { note: String::from("second"), _0: 7, _1: 11 };
+/// assert_ne!(noted.checksum(),renoted.checksum());
+/// ```
+/// ## `child_events`
+/// Written as `child_events` or `child_events = <bool>`. Generates helpers for real-time listeners (like Firebase's child-added, child-changed, and child-removed events), which report a single key and value at a time.
+/// `child_update(key, value)` translates an event into the index of the targeted slot and its deserialized value, `apply_child_changed(key, value)` writes that value into the slot (for both added and changed children),
+/// and `apply_child_removed(key)` resets the slot to its default value. Each returns the index of the slot and its old value, or nothing if `key` isn't the key of any slot.
+#[cfg_attr(feature = "serde", doc = " ```")]
+#[cfg_attr(not(feature = "serde"), doc = " ```ignore")]
+/// # use structurray::faux_array;
+/// use serde::de::IntoDeserializer;
+/// use serde::de::value::U32Deserializer;
+/// use serde::de::value::Error;
+/// use serde::{Serialize,Deserialize};
+///
+/// #[faux_array(u32,12,child_events)]
+/// #[derive(Default,Serialize,Deserialize)]
+/// struct Live {}
+///
+/// let mut live = Live::default();
+/// let value: U32Deserializer<Error> = 7u32.into_deserializer();
+/// assert_eq!(live.apply_child_changed("b",value).unwrap(),Some((11,0)));
+/// assert_eq!(live._b,7);
+/// assert_eq!(live.apply_child_removed("b"),Some((11,7)));
+/// assert_eq!(live.apply_child_removed("unknown"),None);
+/// ```
+/// ## `companion`
+/// Written as `companion(MetaType)` or `companion(MetaType, suffix = "Meta")`. Generates a second [`struct`] with exactly the same fields and keys as yours, but holding `MetaType` in every slot, for
+/// storing information about each slot (such as where its value came from) that should stay in step with the slots themselves. The second [`struct`] is named after yours with the suffix appended
+/// (`Meta` if no suffix is given), and is given the same attributes as yours, so it derives the same traits. `MetaType` may not use the generic parameters of your [`struct`].
+/// The generated `zip` and `zip_mut` methods walk both [`struct`]s at once, in index order, and like the iterators of arrays they can be reversed and report their length.
+/// ```
+/// # use structurray::faux_array;
+/// # use serde::Serialize;
+/// #[faux_array(f32,2,companion(&'static str, suffix = "Sources"))]
+/// #[derive(Serialize)]
+/// struct Readings {}
+///
+/// let readings = Readings { _0: 20.5, _1: 21.0 };
+/// let sources = ReadingsSources { _0: "probe", _1: "estimate" };
+/// let pairs: Vec<(&f32,&&str)> = readings.zip(&sources).collect();
+/// assert_eq!(pairs[1],(&21.0,&"estimate"));
+/// ```
+/// ## `container_attrs`
+/// Written as `container_attrs = [#[...], ...]`. Adds the given attributes to the definition of your [`struct`], after its own attributes, so attributes this macro doesn't model (such as
+/// `#[serde(deny_unknown_fields)]` or `#[repr(align(64))]`) can be kept in one place alongside the rest of the configuration. Attributes given this way aren't copied to other generated types, such as companions.
+/// ```
+/// # use structurray::faux_array;
+/// # use serde::{Serialize,Deserialize};
+/// #[faux_array(u64,8,container_attrs = [#[serde(deny_unknown_fields)], #[repr(align(64))]])]
+/// #[derive(Serialize,Deserialize)]
+/// struct Line {}
+///
+/// assert_eq!(std::mem::align_of::<Line>(),64);
+/// ```
+/// ## `const_default`
+/// Written as `const_default = <expression>`. Generates an associated constant, `DEFAULT`, built at compile time with the value of the given constant expression in every slot, so that static initial
+/// documents can be stored in read-only memory instead of being constructed when your program starts. This option can't be combined with options that add other fields, such as `timestamps`.
+/// ```
+/// # use structurray::faux_array;
+/// # use serde::Serialize;
+/// #[faux_array(i16,3,const_default = -1)]
+/// #[derive(Serialize)]
+/// struct Scores {}
+///
+/// static INITIAL: Scores = Scores::DEFAULT;
+/// assert_eq!((INITIAL._0,INITIAL._2),(-1,-1));
+/// ```
+/// ## `default`
+/// Written as `default` or `default = <bool>`. Implements [`Default`] when the element type does, filling every slot with the default value of the element type and every other field of your [`struct`]
+/// with its own default value. Unlike deriving [`Default`], this works the same way at any number of slots, and doesn't ask the fields you declared yourself to be filled in separately. Since the
+/// implementation is generated, your [`struct`] must **not** also derive [`Default`].
+/// ```
+/// # use structurray::faux_array;
+/// # use serde::Serialize;
+/// #[faux_array(u16,40,default)]
+/// #[derive(Serialize)]
+/// struct Histogram {
+///     label: String,
+/// }
+///
+/// let histogram = Histogram::default();
+/// assert_eq!(histogram._0,0);
+/// assert!(histogram.label.is_empty());
+/// ```
+/// ## `delta`
+/// Generates a `delta` method and an `apply_delta` method for documents that change a few slots at a time. `delta` takes a baseline instance and returns a wrapper (named after your [`struct`] with `Delta` appended)
+/// whose [`Serialize`] implementation writes only the slots that differ from the baseline, skipping every slot that is unchanged. `apply_delta` reads such a map of keys to values
+/// back from any [`Deserializer`](https://docs.rs/serde/latest/serde/trait.Deserializer.html) and overwrites only the slots it contains. Serializing a delta requires the element type to implement [`PartialEq`].
+#[cfg_attr(feature = "serde", doc = " ```")]
+#[cfg_attr(not(feature = "serde"), doc = " ```ignore")]
+/// # use structurray::faux_array;
+/// # use serde::Serialize;
+/// use serde::de::value::{MapDeserializer,Error};
+///
+/// #[faux_array(u8,3,delta)]
+/// #[derive(Serialize)]
+/// struct Scores {}
+///
+/// let mut scores = Scores { _0: 1, _1: 2, _2: 3 };
+/// let changes = MapDeserializer::<_,Error>::new(vec![("1",20u8)].into_iter());
+/// scores.apply_delta(changes).unwrap();
+/// assert_eq!((scores._0,scores._1,scores._2),(1,20,3));
+/// ```
+/// ## `deref`
+/// Requires the `repr_c` option. Since the slots are then laid out exactly like an array, this implements [`Deref`](std::ops::Deref) and [`DerefMut`](std::ops::DerefMut) with an array of every slot,
+/// such as `[T; 4]`, as the target, so the entire standard array and slice API is available on your [`struct`].
+/// ```
+/// # use structurray::faux_array;
+/// # use serde::Serialize;
+/// #[faux_array(u8,4,repr_c,deref)]
+/// #[derive(Serialize,Default)]
+/// struct Bytes {}
+///
+/// let mut bytes = Bytes::default();
+/// bytes[2] = 9;
+/// bytes.reverse();
+/// assert_eq!(*bytes,[0,9,0,0]);
+/// assert_eq!(bytes.len(),4);
+/// ```
+/// ## `deserialize`
+/// Written as `deserialize` or `deserialize = <bool>`. Implements [`Deserialize`] with a visitor that reads every slot from a map, so documents written with the derived [`Serialize`] can be read back
+/// without also deriving [`Deserialize`]. Keys that aren't the key (or an alias) of a slot are ignored, and a missing or repeated slot is an error. Declared fields are read under their own keys like a derived
+/// implementation would, following their `rename`, `alias`, `skip`, and `default` serde attributes (compilation fails if a declared field has any other serde attribute), and a missing declared field is an error
+/// unless its type accepts a missing value, like [`Option`]. Because the implementation is generated, a [`struct`] using this option must **not** derive [`Deserialize`].
+#[cfg_attr(feature = "serde", doc = " ```")]
+#[cfg_attr(not(feature = "serde"), doc = " ```ignore")]
+/// # use structurray::faux_array;
+/// use serde::{Serialize,Deserialize};
+/// use serde::de::value::{MapDeserializer,Error};
+///
+/// #[faux_array(u16,3,deserialize,aliases = ["_{}"])]
+/// #[derive(Serialize)]
+/// struct Document {}
+///
+/// let fields = vec![("0",1u16),("_1",2),("2",3),("unknown",4)];
+/// let document = Document::deserialize(MapDeserializer::<_,Error>::new(fields.into_iter())).unwrap();
+/// assert_eq!((document._0,document._1,document._2),(1,2,3));
+/// let missing = Document::deserialize(MapDeserializer::<_,Error>::new(vec![("0",1u16)].into_iter()));
+/// assert!(missing.is_err());
+///
+/// #[faux_array(String,2,deserialize)]
+/// #[derive(Serialize)]
+/// struct Labeled {
+///     #[serde(rename = "title")]
+///     label: String,
+///     subtitle: Option<String>,
+/// }
+///
+/// let fields = vec![("0","a"),("1","b"),("title","Scores")];
+/// let labeled = Labeled::deserialize(MapDeserializer::<_,Error>::new(fields.into_iter())).unwrap();
+/// assert_eq!((labeled.label.as_str(),labeled.subtitle),("Scores",None));
+/// ```
+/// ## `encoding`
+/// Written as `encoding = decimal`, `encoding = hex`, `encoding = base36`, or `encoding = base62` (the default). Changes the numbering base of the keys, and so of the generated field names, for backends
+/// and teams that prefer plain decimal or hexadecimal keys over the compact Base62 ones. The keys are available at runtime through `structurray_keys::encode_index_with` and the alphabet constants
+/// of the [`structurray_keys`](https://docs.rs/structurray_keys) crate. It can't be combined with `alphabet` or a `key_scheme`, which pins the Base62 encoding.
+/// ```
+/// # use structurray::faux_array;
+/// # use serde::Serialize;
+/// #[faux_array(u8,256,encoding = hex,key_table)]
+/// #[derive(Serialize,Default)]
+/// struct Bytes {}
+///
+/// let mut bytes = Bytes::default();
+/// bytes._ff = 1;
+/// assert_eq!(Bytes::KEYS[255],"ff");
+/// assert_eq!(Bytes::index_of("a"),Some(10));
+/// ```
+/// ## `example_with`
+/// Written as `example_with = "path::to::function"`. Generates `example()`, which creates a canonical fixture by calling the given function with the index of each slot (as a [`u32`]) and putting the
+/// result in that slot, so doctests, API examples, and seed scripts can share one instance instead of each writing out a large literal. Fields generated by other options, such as timestamps, are filled
+/// using [`Default`].
+/// ```
+/// # use structurray::faux_array;
+/// # use serde::Serialize;
+/// mod fixtures {
+///     pub fn label(index: u32) -> String {
+///         format!("label {}",index)
+///     }
+/// }
+///
+/// #[faux_array(String,300,example_with = "fixtures::label")]
+/// #[derive(Serialize)]
+/// struct Labels {}
+///
+/// assert_eq!(Labels::example()._0,"label 0");
+/// ```
+/// ## `field_attrs`
+/// Written as `field_attrs = (#[...] ...)`. Adds the given attributes to every slot, after the attributes this macro generates for it (such as its serde `rename`), so attributes this macro doesn't model
+/// can be placed on the slots without declaring them by hand. Fields generated alongside the slots by other options, such as `timestamps`, don't get the attributes. It can't be used on a
+/// [tuple struct](#tuple-structs), whose generated [`Serialize`] implementation wouldn't follow serde attributes on the slots.
+/// ```
+/// # use structurray::faux_array;
+/// # use serde::Serialize;
+/// #[faux_array(Option<u8>,4,field_attrs = (#[serde(skip_serializing_if = "Option::is_none")] #[cfg_attr(test, deprecated)]))]
+/// #[derive(Serialize,Default)]
+/// struct Sparse {}
+///
+/// let mut sparse = Sparse::default();
+/// sparse._3 = Some(9);
+/// assert_eq!(sparse._3,Some(9));
+/// ```
+/// ## `field_names`
+/// Written as `field_names` or `field_names = <bool>`. Generates `FIELD_NAMES`, an associated constant holding the exact name every slot is serialized under, in order, after any `rename_format`,
+/// `flatten_prefix`, or `encoding` is applied. This lets query projections and document paths be built without encoding keys in application code. Unlike `key_table`, no other helpers are generated.
+#[cfg_attr(feature = "serde", doc = " ```")]
+#[cfg_attr(not(feature = "serde"), doc = " ```ignore")]
+/// # use structurray::faux_array;
+/// # use serde::Serialize;
+/// #[faux_array(u8,64,field_names,rename_format = "scores.{}")]
+/// #[derive(Serialize)]
+/// struct Scores {}
+///
+/// assert_eq!(Scores::FIELD_NAMES[0],"scores.0");
+/// assert_eq!(Scores::FIELD_NAMES[63],"scores.11");
+/// ```
+/// ## `field_vis`
+/// Written as `field_vis = pub`, `field_vis = pub(crate)` (or any other restricted visibility), or `field_vis = private`. Gives every generated field, including the fields other options generate alongside the slots,
+/// the chosen visibility. The default is `private`, which leaves the fields visible only within the module of your [`struct`], so a library can expose its slots directly without writing wrapper methods.
+/// Since `accessors` keeps the fields private behind its methods, only `field_vis = private` can be combined with it.
+/// ```
+/// # use structurray::faux_array;
+/// # use serde::Serialize;
+/// mod readings {
+/// #   use structurray::faux_array;
+/// #   use serde::Serialize;
+///     #[faux_array(u8,3,field_vis = pub)]
+///     #[derive(Serialize,Default)]
+///     pub struct Readings {}
+/// }
+///
+/// let mut readings = readings::Readings::default();
+/// readings._2 = 4;
+/// assert_eq!(readings._2,4);
+/// ```
+/// ## `fill`
+/// Written as `fill` or `fill = <bool>`. Generates `fill(value)`, which puts a clone of the value in every slot, like [`slice::fill`]. It can only be used if the element type implements [`Clone`].
+/// ```
+/// # use structurray::faux_array;
+/// # use serde::Serialize;
+/// #[faux_array(String,4,fill)]
+/// #[derive(Serialize,Default)]
+/// struct Names {}
+///
+/// let mut names = Names::default();
+/// names.fill(String::from("unknown"));
+/// assert_eq!(names._3,"unknown");
+/// ```
+/// ## `flatten_prefix`
+/// Written as `flatten_prefix = "<text>"`. Serializes every slot under its key with the given text in front of it, so `flatten_prefix = "slots."` writes slot 0 under `"slots.0"`. This lets the slots be flattened
+/// (with `#[serde(flatten)]`) straight into a parent document as dotted field paths, such as those of Firestore, instead of nesting them one level deeper. Every generated key, such as the entries of
+/// `KEYS`, includes the prefix, but identifiers named after keys (the generated fields, the methods of `accessors = key`, and the fields of `capnp`) are not. It can't be combined with `split_keys`.
+#[cfg_attr(feature = "serde", doc = " ```")]
+#[cfg_attr(not(feature = "serde"), doc = " ```ignore")]
+/// # use structurray::faux_array;
+/// use serde::Serialize;
+///
+/// #[faux_array(u8,2,flatten_prefix = "slots.",key_table)]
+/// #[derive(Serialize,Default)]
+/// struct Slots {}
+///
+/// #[derive(Serialize,Default)]
+/// struct Parent {
+///     owner: u32,
+///     #[serde(flatten)]
+///     slots: Slots,
+/// }
+///
+/// assert_eq!(Slots::KEYS,["slots.0","slots.1"]);
+/// assert_eq!(Slots::index_of("slots.1"),Some(1));
+/// ```
+/// ## `from_iter`
+/// Written as `from_iter` or `from_iter = <bool>`. Implements [`FromIterator`], which puts each item of an iterator in the slot with the same index, so your [`struct`] can be built with
+/// [`collect`](Iterator::collect). Like collecting into an array would, this panics unless the iterator yields exactly one item per slot. Use `try_from_iter`
+/// to handle iterators of the wrong length as an error instead, which is what this option generates in place of [`FromIterator`] when `no_panic` is given.
+/// ```
+/// # use structurray::faux_array;
+/// # use serde::Serialize;
+/// #[faux_array(u32,100,from_iter)]
+/// #[derive(Serialize)]
+/// struct Doubles {}
+///
+/// let doubles: Doubles = (0..100).map(|value| value * 2).collect();
+/// assert_eq!(doubles._1,2);
+/// ```
+/// ## `fuzz`
+/// Requires this crate's `fuzz` feature. Generates an associated function, `fuzz_round_trip`, that only exists when compiling with `--cfg fuzzing` (as [`cargo fuzz`](https://github.com/rust-fuzz/cargo-fuzz) does).
+/// It tries to read arbitrary bytes as a JSON document of your [`struct`], and if that succeeds, checks that serializing the result and reading it back produces the same document, so a fuzz target for
+/// each psuedo-array schema only needs to call it:
+/// ```no_run
+/// # /*
+/// fuzz_target!(|data: &[u8]| Scores::fuzz_round_trip(data));
+/// # */
+/// ```
+/// The harness uses [`serde_json`](https://docs.rs/serde_json), so it must be a dependency of your fuzzing crate, and your [`struct`] must implement [`Serialize`] and [`Deserialize`].
+/// When `no_panic` is given, it returns `false` for a document that doesn't survive the round trip instead of panicking, so the fuzz target decides how to fail.
+/// ## `gen_tests`
+/// Generates a `#[cfg(test)]` module, named after your [`struct`] with `_faux_array_tests` appended, containing two tests: one that serializes a [`Default`] instance to JSON, reads it back, and checks that
+/// writing it again produces the same document, and one that checks every key appears in the serialized document in index order. This gives every schema baseline coverage for free.
+/// The tests use [`serde_json`](https://docs.rs/serde_json), so it must be one of your dev-dependencies, and your [`struct`] must implement [`Default`], [`Serialize`], and [`Deserialize`] and may not be generic.
+#[cfg_attr(feature = "serde", doc = " ```")]
+#[cfg_attr(not(feature = "serde"), doc = " ```ignore")]
+/// # use structurray::faux_array;
+/// # use serde::{Serialize,Deserialize};
+/// #[faux_array(u64,100,gen_tests)]
+/// #[derive(Serialize,Deserialize,Default)]
+/// struct Counters {}
+/// ```
+/// ## `get`
+/// Written as `get` or `get = <bool>`. Generates `get(index)` and `get_mut(index)`, which borrow the slot at a [`usize`] index like the methods of the same name on slices, returning [`None`] instead of
+/// panicking if the index is out of range. This is the bounds-checked counterpart of the `index` option, for indices that come from user input.
+/// ```
+/// # use structurray::faux_array;
+/// # use serde::Serialize;
+/// #[faux_array(u8,40,get,index)]
+/// #[derive(Serialize,Default)]
+/// struct Readings {}
+///
+/// let mut readings = Readings::default();
+/// if let Some(reading) = readings.get_mut(12) {
+///     *reading = 7;
+/// }
+/// assert_eq!((readings.get(12),readings.get(40)),(Some(&7),None));
+/// assert_eq!(readings[12],7);
+/// ```
+/// ## `heap_size`
+/// Written as `heap_size` or `heap_size = <bool>`. Generates `INLINE_SIZE`, an associated constant holding the size of your [`struct`] itself, and `heap_size_estimate(per_elem)`, which adds up the
+/// closure's estimate of the memory each slot owns elsewhere, such as the buffer of a [`String`]. Their sum estimates the total memory an instance uses, for capacity planning of caches that hold many
+/// large psuedo-arrays.
+/// ```
+/// # use structurray::faux_array;
+/// # use serde::Serialize;
+/// #[faux_array(String,3,heap_size)]
+/// #[derive(Serialize)]
+/// struct Labels {}
+///
+/// let labels = Labels { _0: String::from("alpha"), _1: String::new(), _2: String::from("gamma") };
+/// assert_eq!(Labels::INLINE_SIZE,3 * std::mem::size_of::<String>());
+/// assert_eq!(labels.heap_size_estimate(|label| label.capacity()),10);
+/// ```
+/// ## `hot_cold`
+/// Written as `hot_cold = <count>`. Generates two more [`struct`]s, named after yours with `Hot` and `Cold` appended, which hold the first `count` slots and the remaining slots respectively, so frequently
+/// updated slots can live in a small document that is cheap to write while the rest stay in a large one that is rarely touched. Both keep the keys of their slots, and are given the same attributes as yours,
+/// so they derive the same traits. A facade named after yours with `HotCold` appended holds one of each in its `hot` and `cold` fields, reaches every slot by its index through `value_at`, `value_at_mut`,
+/// and `set_value_at`, and converts to and from your [`struct`]. Like the `value_at` accessors, these panic if the index is out of range, unless `no_panic` is given.
+/// ```
+/// # use structurray::faux_array;
+/// # use serde::Serialize;
+/// #[faux_array(u32,4,hot_cold = 1)]
+/// #[derive(Serialize,Default)]
+/// struct Counters {}
+///
+/// let mut counters = CountersHotCold::from(Counters::default());
+/// counters.set_value_at(0,5);
+/// *counters.value_at_mut(3) += 2;
+/// assert_eq!((counters.hot._0,counters.cold._3),(5,2));
+/// assert!(CountersHotCold::is_hot(0) && !CountersHotCold::is_hot(1));
+/// let joined = Counters::from(counters);
+/// assert_eq!(joined._3,2);
+/// ```
+/// ## `identity`
+/// Written as `identity = [<index>, ...]`. Implements [`PartialEq`], [`Eq`], and [`Hash`] by comparing and hashing only the slots at the listed indices, for psuedo-arrays identified by a few of their
+/// slots, such as documents deduplicated by a short prefix, where comparing every slot would be wasteful. Since the implementations are generated, your [`struct`] must **not** derive these traits. [`Eq`]
+/// and [`Hash`] can only be used if the element type implements them. Each index must belong to a slot and may only be listed once.
+/// ```
+/// # use structurray::faux_array;
+/// # use serde::Serialize;
+/// use std::collections::HashSet;
+///
+/// #[faux_array(u32,8,identity = [0,1,2])]
+/// #[derive(Serialize)]
+/// struct Document {}
+///
+/// let first = Document { _0: 1, _1: 2, _2: 3, _3: 4, _4: 5, _5: 6, _6: 7, _7: 8 };
+/// let second = Document { _7: 0, ..first };
+/// assert!(first == second);
+/// let unique: HashSet<Document> = [first,second].into_iter().collect();
+/// assert_eq!(unique.len(),1);
+/// ```
+/// ## `index`
+/// Written as `index` or `index = <bool>`. Implements [`Index<usize>`](std::ops::Index) and [`IndexMut<usize>`](std::ops::IndexMut) by matching the index against every slot, so slots can be read and
+/// written with the usual `values[37]` syntax. Like indexing a slice, indexing out of range panics, with a message that includes the index. When `no_panic` is given, this option generates
+/// the `get` and `get_mut` methods of the `get` option instead, since an [`Index`](std::ops::Index) implementation has no way to report an out of range index without panicking.
+/// ```
+/// # use structurray::faux_array;
+/// # use serde::Serialize;
+/// #[faux_array(u8,40,index)]
+/// #[derive(Serialize,Default)]
+/// struct Readings {}
+///
+/// let mut readings = Readings::default();
+/// readings[37] = 9;
+/// assert_eq!((readings[37],readings._B),(9,9));
+/// ```
+/// ```should_panic
+/// # use structurray::faux_array;
+/// # use serde::Serialize;
+/// # #[faux_array(u8,40,index)]
+/// # #[derive(Serialize,Default)]
+/// # struct Readings {}
+/// let readings = Readings::default();
+/// let _ = readings[40];
+/// ```
+/// ## `index_by_key`
+/// Written as `index_by_key` or `index_by_key = <bool>`, and requires `key_enum`. Implements [`Index`](std::ops::Index) and [`IndexMut`](std::ops::IndexMut) for the variants of the key enum, so slots can be
+/// read and written as `values[ValuesKey::K37]`. Since every variant belongs to a slot, indexing this way can never be out of range, and never panics.
+/// ```
+/// # use structurray::faux_array;
+/// # use serde::Serialize;
+/// #[faux_array(u32,40,key_enum,index_by_key)]
+/// #[derive(Serialize,Default)]
+/// struct Values {}
+///
+/// let mut values = Values::default();
+/// values[ValuesKey::Kb] = 7;
+/// values[ValuesKey::Kb] += 1;
+/// assert_eq!(values._b,8);
+/// ```
+/// ## `index_map`
+/// Requires this crate's `indexmap` feature. Generates `to_index_map()`, which copies every slot into an [`IndexMap`](https://docs.rs/indexmap) from keys to values that iterates in index order, and `from_index_map(map)`,
+/// which moves the values back into a new instance, returning [`None`] if a slot's key is missing. Order-sensitive consumers, like diffs and exports, get keys in index order without re-sorting them.
+/// The generated code uses [`indexmap`](https://docs.rs/indexmap), so it must be a dependency of your crate.
+/// ```no_run
+/// # /*
+/// #[faux_array(u32,100,index_map)]
+/// #[derive(Serialize,Default)]
+/// struct Ordered {}
+///
+/// let map = Ordered::default().to_index_map();
+/// assert_eq!(map.get_index(10),Some((&"a",&0)));
+/// assert!(Ordered::from_index_map(map).is_some());
+/// # */
+/// ```
+/// ## `init`
+/// Written as `init = <expression>`. Generates `new()`, which creates an instance with the expression in every slot, `clear()`, which puts the expression back into every slot, and a [`Default`] implementation
+/// that calls `new()`, so your [`struct`] must **not** derive [`Default`]. The expression is evaluated separately for each slot, and also replaces the element type's default value in `new_boxed_default` when `boxed` is used.
+/// This is useful for sentinel values, such as `-1` for slots that haven't been scored yet.
+/// ```
+/// # use structurray::faux_array;
+/// # use serde::Serialize;
+/// #[faux_array(i32,8,init = -1)]
+/// #[derive(Serialize)]
+/// struct Scores {}
+///
+/// let mut scores = Scores::default();
+/// assert_eq!(scores._7,-1);
+/// scores._7 = 42;
+/// scores.clear();
+/// assert_eq!(scores._7,-1);
+/// ```
+/// ## `into_iter`
+/// Written as `into_iter` or `into_iter = <bool>`. Implements [`IntoIterator`] for your [`struct`], for shared references to it, and for mutable references to it, yielding the slots (or references to
+/// them) in index order, so psuedo-arrays can be used directly in `for` loops and iterator chains like real arrays. Iterating by value moves every slot out, so any other fields are dropped.
+/// ```
+/// # use structurray::faux_array;
+/// # use serde::Serialize;
+/// #[faux_array(String,3,into_iter)]
+/// #[derive(Serialize,Default)]
+/// struct Words {}
+///
+/// let mut words = Words::default();
+/// for word in &mut words {
+///     word.push('!');
+/// }
+/// assert_eq!((&words).into_iter().map(String::len).sum::<usize>(),3);
+/// let owned: Vec<String> = words.into_iter().collect();
+/// assert_eq!(owned,["!","!","!"]);
+/// ```
+/// ## `iter`
+/// Written as `iter` or `iter = <bool>`. Generates `iter()` and `iter_mut()`, which borrow every slot in index order, so code that treats the slots as a sequence doesn't have to name thousands of fields
+/// by hand. Like the iterators of arrays, they can be reversed and report their length.
+/// ```
+/// # use structurray::faux_array;
+/// # use serde::Serialize;
+/// #[faux_array(u32,5000,boxed,iter)]
+/// #[derive(Serialize)]
+/// struct Totals {}
+///
+/// let mut totals = Totals::new_boxed_default();
+/// for total in totals.iter_mut() {
+///     *total += 2;
+/// }
+/// assert_eq!(totals.iter().sum::<u32>(),10_000);
+/// assert_eq!(totals.iter().len(),5000);
+/// ```
+/// ## `iter_mut_indexed`
+/// Written as `iter_mut_indexed` or `iter_mut_indexed = <bool>`. Generates `iter_mut_indexed()`, which mutably borrows every slot along with its index, in index order, so in-place transformations that
+/// depend on the position of a slot don't need to zip the slots with a separate counter and trust that both follow the same order. Like the iterators of arrays, it can be reversed and reports its length.
+/// ```
+/// # use structurray::faux_array;
+/// # use serde::Serialize;
+/// #[faux_array(f64,4,iter_mut_indexed)]
+/// #[derive(Serialize)]
+/// struct Weights {}
+///
+/// let mut weights = Weights { _0: 1.0, _1: 1.0, _2: 1.0, _3: 1.0 };
+/// for (index, weight) in weights.iter_mut_indexed() {
+///     *weight /= f64::from(index + 1);
+/// }
+/// assert_eq!((weights._0,weights._3),(1.0,0.25));
+/// assert_eq!(weights.iter_mut_indexed().next_back().map(|(index, _)| index),Some(3));
+/// ```
+/// ## `key_digest`
+/// Written as `key_digest` or `key_digest = <bool>`. Generates `KEY_DIGEST`, an associated constant holding the SHA-256 digest of the key of every slot in order (as computed by `structurray_keys::digest_keys`, which gives the same digest as `structurray_keys::key_digest` unless the keys are customized).
+/// Pass it to [`assert_key_digest!`](macro@assert_key_digest) along with a pinned value so that any change to the keys breaks the build instead of silently corrupting the mapping to stored documents.
+/// ```
+/// # use structurray::faux_array;
+/// # use serde::Serialize;
+/// #[faux_array(u8,16,key_digest)]
+/// #[derive(Serialize)]
+/// struct Pinned {}
+///
+/// assert_eq!(Pinned::KEY_DIGEST,structurray_keys::key_digest(16));
+/// ```
+/// ## `key_encoder`
+/// Written as `key_encoder = "path::to::Type"`. Serializes and deserializes every slot under the key that the given type, which must implement
+/// [`structurray_keys::KeyEncoder`](https://docs.rs/structurray_keys), creates for its index, for organizations with their own key schemes. Since the macro can't run your code while it expands, the
+/// generated fields are still named after the built-in keys, and [`Serialize`](https://docs.rs/serde/latest/serde/trait.Serialize.html) and [`Deserialize`](https://docs.rs/serde/latest/serde/trait.Deserialize.html)
+/// are implemented for you, so your [`struct`] must **not** derive them. Keys that the encoder can't decode are ignored when deserializing. Options that rely on the built-in keys, such as `key_table`,
+/// `rename_format`, and `timestamps`, can't be combined with this option.
+#[cfg_attr(feature = "serde", doc = " ```")]
+#[cfg_attr(not(feature = "serde"), doc = " ```ignore")]
+/// # use structurray::faux_array;
+/// use structurray_keys::KeyEncoder;
+/// use serde::Deserialize;
+/// use serde::de::value::{MapDeserializer,Error};
+///
+/// struct Tagged;
+/// impl KeyEncoder for Tagged {
+///     fn encode(index: u32) -> String {
+///         format!("f{:03}",index)
+///     }
+///     fn decode(key: &str) -> Option<u32> {
+///         key.strip_prefix('f')?.parse().ok()
+///     }
+/// }
+///
+/// #[faux_array(u8,3,key_encoder = "Tagged")]
+/// struct Flags {}
+///
+/// let document = vec![("f000",1u8),("f001",2),("f002",3),("other",4)];
+/// let flags = Flags::deserialize(MapDeserializer::<_,Error>::new(document.into_iter())).unwrap();
+/// assert_eq!((flags._0,flags._2),(1,3));
+/// let missing = Flags::deserialize(MapDeserializer::<_,Error>::new(vec![("f000",1u8)].into_iter()));
+/// assert_eq!(missing.err().unwrap().to_string(),"missing key `f001`");
+/// ```
+/// ## `key_enum`
+/// Written as `key_enum` or `key_enum = <name>`. Generates an enum named after your [`struct`] with `Key` appended (or with the given name), with one variant per slot, named after the slot's encoded index
+/// with `K` in front (such as `K0` through `Kz`), for passing the keys of individual slots around without strings. The enum implements [`Display`](std::fmt::Display), which writes the key each variant
+/// stands for, and [`FromStr`](std::str::FromStr), which parses a key back into its variant and otherwise fails with an error type named after the enum with `Error` appended. The `key` and `index`
+/// methods return the key and the index of the slot of a variant, and `ALL` holds every variant in the order of the slots.
+#[cfg_attr(feature = "serde", doc = " ```")]
+#[cfg_attr(not(feature = "serde"), doc = " ```ignore")]
+/// # use structurray::faux_array;
+/// # use serde::Serialize;
+/// #[faux_array(u8,64,key_enum,rename_format = "v.{}")]
+/// #[derive(Serialize)]
+/// struct Values {}
+///
+/// assert_eq!(ValuesKey::K11.to_string(),"v.11");
+/// assert_eq!("v.a".parse::<ValuesKey>(),Ok(ValuesKey::Ka));
+/// assert_eq!(ValuesKey::Ka.index(),10);
+/// assert!("v.12".parse::<ValuesKey>().is_err());
+/// assert_eq!(ValuesKey::ALL.len(),64);
+/// ```
+/// ## `key_scheme`
+/// Written as `key_scheme = <integer>`. Pins the exact key encoding your [`struct`] was written with, so that upgrading this crate can never silently change the keys of stored documents: if a version of this
+/// crate doesn't know the scheme, or would generate different keys than the scheme promises, compilation fails instead. Scheme `1` is the Base62 encoding described under "Identifier Generation".
+/// ```
+/// # use structurray::faux_array;
+/// # use serde::Serialize;
+/// #[faux_array(u8,10,key_scheme = 1)]
+/// #[derive(Serialize)]
+/// struct LongLived {}
+/// ```
+/// ## `key_table`
+/// Written as `key_table` or `key_table = <bool>`. Generates `KEYS`, an associated constant holding the key of every slot in order, along with `keys_range(range)` and `keys_of(indices)`,
+/// which iterate over the keys of a range of slots or of specific slots. These are meant for building projections and select lists for partial reads, so that application code never encodes keys itself.
+/// The reverse direction is covered by `KEY_INDICES`, a table of every key and its index sorted by key, and `index_of(key)`, which binary searches that table instead of decoding the key.
+/// The serde implementations generated by `checksum`, `deserialize`, and `progress` look up the slot of each key they read through the same table.
+/// ```
+/// # use structurray::faux_array;
+/// # use serde::Serialize;
+/// #[faux_array(u8,64,key_table)]
+/// #[derive(Serialize)]
+/// struct Wide {}
+///
+/// assert_eq!(Wide::KEYS[63],"11");
+/// assert_eq!(Wide::keys_range(9..12).collect::<Vec<_>>(),["9","a","b"]);
+/// assert_eq!(Wide::keys_of([36,0]).collect::<Vec<_>>(),["A","0"]);
+/// assert_eq!(Wide::index_of("11"),Some(63));
+/// assert_eq!(Wide::index_of("12"),None);
+/// ```
+#[cfg_attr(feature = "serde", doc = " ```")]
+#[cfg_attr(not(feature = "serde"), doc = " ```ignore")]
+/// # use structurray::faux_array;
+/// use serde::{Serialize,Deserialize};
+/// use serde::de::value::{MapDeserializer,Error};
+///
+/// #[faux_array(u8,64,key_table,deserialize)]
+/// #[derive(Serialize)]
+/// struct Looked {}
+///
+/// let document = (0..64u8).map(|index| (Looked::KEYS[index as usize],index));
+/// let looked = Looked::deserialize(MapDeserializer::<_,Error>::new(document)).unwrap();
+/// assert_eq!((looked._0,looked._a,looked._11),(0,10,63));
+/// ```
+/// ## `key_tokens`
+/// Written as `key_tokens` or `key_tokens = <bool>`, and requires the [`structurray_keys`](https://docs.rs/structurray_keys) crate as a dependency. Generates a module named after your [`struct`] with `_keys` appended,
+/// holding a zero-sized type for every slot (named after the slot) that implements `structurray_keys::KeyToken` with the slot's `KEY` and `INDEX`. The tokens are declared in submodules of 256 tokens each,
+/// which are all re-exported from that module. Since every token names your [`struct`] as its `Owner`, query builders bounded by `KeyToken<Owner = ...>` refuse keys from other psuedo-arrays at compile time.
+/// The module has the same visibility as your [`struct`], which must not be generic.
+/// ```
+/// # use structurray::faux_array;
+/// # use serde::Serialize;
+/// use structurray_keys::KeyToken;
+/// #[faux_array(u8,300,key_tokens)]
+/// #[derive(Serialize)]
+/// struct Orders {}
+///
+/// fn select<K: KeyToken<Owner = Orders>>(_: K) -> (&'static str,u32) {
+///     (K::KEY,K::INDEX)
+/// }
+/// # fn main() {
+/// assert_eq!(select(Orders_keys::_10),("10",62));
+/// assert_eq!(select(Orders_keys::chunk_1::_4q),("4q",274));
+/// # }
+/// ```
+/// Keys from another psuedo-array don't fit:
+/// ```compile_fail
+/// # use structurray::faux_array;
+/// # use serde::Serialize;
+/// # use structurray_keys::KeyToken;
+/// #[faux_array(u8,2,key_tokens)]
+/// #[derive(Serialize)]
+/// struct Orders {}
+/// #[faux_array(u8,2,key_tokens)]
+/// #[derive(Serialize)]
+/// struct Users {}
+///
+/// fn select<K: KeyToken<Owner = Orders>>(_: K) -> &'static str {
+///     K::KEY
+/// }
+/// # fn main() {
+/// select(Users_keys::_0);
+/// # }
+/// ```
+/// ## `len`
+/// Written as `len` or `len = <bool>`. Generates `LEN`, an associated constant holding the number of slots, and `len()`, a `const` method returning it, so buffers and loop bounds can be sized at
+/// compile time without repeating the count given to this attribute.
+/// ```
+/// # use structurray::faux_array;
+/// # use serde::Serialize;
+/// #[faux_array(u8,24,len)]
+/// #[derive(Serialize,Default)]
+/// struct Hours {}
+///
+/// let buffer = [0u8; Hours::LEN];
+/// assert_eq!(buffer.len(),Hours::default().len());
+/// ```
+/// ## `lossy`
+/// Written as `lossy` or `lossy = <bool>`. Generates `from_value_lossy(entries)`, which reads each slot from an iterator of keys paired with values that implement
+/// [`Deserializer`](https://docs.rs/serde/latest/serde/trait.Deserializer.html) (such as the entries of a `serde_json::Map`), and an error type named after your [`struct`] with `SlotError` appended.
+/// Since every value is deserialized separately, one malformed value doesn't prevent the rest of the document from being read. Instead, slots whose value is missing or malformed are left with their default
+/// value, and are reported in index order with their index, key, and error message. This option requires an element type that implements [`Default`], and can't be combined with `blob` or `split_keys`.
+#[cfg_attr(feature = "serde", doc = " ```")]
+#[cfg_attr(not(feature = "serde"), doc = " ```ignore")]
+/// # use structurray::faux_array;
+/// # use serde::Serialize;
+/// use serde::de::value::{Error,U64Deserializer};
+///
+/// #[faux_array(u8,3,lossy)]
+/// #[derive(Serialize)]
+/// struct Levels {}
+///
+/// let entries = vec![("0",7u64),("1",300)].into_iter().map(|(key,value)| (key,U64Deserializer::<Error>::new(value)));
+/// let (levels,errors) = Levels::from_value_lossy(entries);
+/// assert_eq!((levels._0,levels._1,levels._2),(7,0,0));
+/// assert_eq!(errors.iter().map(|error| (error.index,error.key)).collect::<Vec<_>>(),vec![(1,"1"),(2,"2")]);
+/// assert_eq!(errors[1].message,"missing key");
+/// ```
+/// ## `map`
+/// Written as `map` or `map(Target: Element)`. Generates `map(f)`, which creates a psuedo-array with the same shape by calling a closure on each slot, once per slot in index order, and moving the
+/// result into the slot with the same index, such as to parse a psuedo-array of raw strings into one of numbers. Without a target, the element type must be one of the generic parameters of your
+/// [`struct`], which `map` replaces with the type the closure returns, and fields besides the slots are moved unchanged. With a target, `map` creates a `Target`, which must have the same slots as your
+/// [`struct`] holding `Element`, and no other fields, such as a psuedo-array with the same count.
+/// ```
+/// # use structurray::faux_array;
+/// # use serde::Serialize;
+/// #[faux_array(T,3,map)]
+/// #[derive(Serialize)]
+/// struct Triple<T> {}
+///
+/// #[faux_array(u32,2,map(Parsed: u32))]
+/// #[derive(Serialize)]
+/// struct Percentages {}
+///
+/// #[faux_array(u32,2)]
+/// #[derive(Serialize)]
+/// struct Parsed {}
+///
+/// let raw = Triple { _0: "1", _1: "20", _2: "300" };
+/// let parsed: Triple<u32> = raw.map(|text| text.parse().unwrap());
+/// assert_eq!(parsed._2,300);
+/// let halves = Percentages { _0: 50, _1: 100 }.map(|percent| percent / 2);
+/// assert_eq!((halves._0,halves._1),(25,50));
+/// ```
+/// ## `metadata_only`
+/// Written as `metadata_only` or `metadata_only = <bool>`. Leaves your [`struct`] exactly as written instead of adding slots to it, and only generates metadata about the psuedo-array the other arguments describe:
+/// everything generated by `key_table` and `key_digest`, along with the schema of `capnp`, the membership of `namespace`, the enum of `key_enum`, and the constants of `field_names` and `len` if those options are also given. This is meant for tooling crates that need the key
+/// scheme of a layout defined elsewhere, but not another copy of the data type. Only `allow`, `alphabet`, `capnp`, `encoding`, `field_names`, `key_digest`, `key_enum`, `key_scheme`, `key_table`, `len`, `namespace`, `pad`, `reserved_keys`, and `start`
+/// can be combined with this option.
+/// ```
+/// # use structurray::faux_array;
+/// #[faux_array(f64,100,metadata_only,capnp)]
+/// struct ReadingsSchema;
+///
+/// assert_eq!(std::mem::size_of::<ReadingsSchema>(),0);
+/// assert_eq!(ReadingsSchema::index_of("1B"),Some(99));
+/// assert!(ReadingsSchema::CAPNP_SCHEMA.contains("slot1B @99 :Float64;"));
+/// ```
+/// ## `metrics`
+/// Requires this crate's `metrics` feature. Written as `metrics = "path::to::function"`, and instruments the [`Serialize`] and [`Deserialize`] implementations generated by `blob`, `checksum`, `deserialize`, or `split_keys`
+/// (at least one of which must be given), so the cost of reading and writing documents can be observed in production without wrapping every call site. After each serialization or deserialization,
+/// the function is called with the name of your [`struct`], either `"serialize"` or `"deserialize"`, the number of slots, the elapsed [`Duration`](std::time::Duration), and whether the operation succeeded.
+/// Its signature should be `fn(&str, &str, usize, Duration, bool)`, and it can forward the measurements to a metrics or tracing library.
+/// ```no_run
+/// # /*
+/// fn record(name: &str, operation: &str, slots: usize, elapsed: Duration, succeeded: bool) {
+///     tracing::info!(name,operation,slots,?elapsed,succeeded,"psuedo-array (de)serialized");
+/// }
+///
+/// #[faux_array(u32,1000,deserialize,metrics = "record")]
+/// #[derive(Serialize)]
+/// struct Observed {}
+/// # */
+/// ```
+/// ## `name_format`
+/// Written as `name_format = "<template>"`. Names every generated field by replacing the `{}` in the template with the slot's encoded index, so `name_format = "item_{}"` generates `item_0`, `item_1`, and so
+/// on, for layouts whose field names should follow a project's own conventions rather than `_<index>`. Like `prefix`, which it generalizes and can't be combined with, it doesn't change the keys slots are
+/// serialized under, and fields generated for each slot by other options are named after the renamed slots. Compilation fails if the template doesn't contain `{}` exactly once, or doesn't form an
+/// identifier for every slot.
+/// ```
+/// # use structurray::faux_array;
+/// # use serde::Serialize;
+/// #[faux_array(u8,12,name_format = "item_{}_v1")]
+/// #[derive(Serialize,Default)]
+/// struct Items {}
+///
+/// let mut items = Items::default();
+/// items.item_b_v1 = 3;
+/// assert_eq!(items.item_b_v1,3);
+/// ```
+/// ## `named`
+/// Written as `named = { name = <index>, ... }`. Generates `name()`, `name_mut()`, and `set_name(value)` for each listed slot, so the few slots of a layout with special meaning can be reached by name while
+/// the rest stay anonymous. Each name may only be used once, and each index must belong to a slot.
+/// ```
+/// # use structurray::faux_array;
+/// # use serde::Serialize;
+/// #[faux_array(u16,4096,boxed,named = { header = 0, footer = 4095 })]
+/// #[derive(Serialize)]
+/// struct Page {}
+///
+/// let mut page = Page::new_boxed_default();
+/// page.set_footer(7);
+/// assert_eq!((*page.header(),*page.footer()),(0,7));
+/// ```
+/// ## `namespace`
+/// Written as `namespace = <type>`, and requires the [`structurray_keys`](https://docs.rs/structurray_keys) crate as a dependency. Declares that the keys of your [`struct`] share a namespace, named by any
+/// type such as the document they're flattened into, with the keys of other psuedo-arrays, by implementing `structurray_keys::NamespaceMember` for that type. Passing the namespace and its members to
+/// [`assert_namespace!`](macro@assert_namespace) then fails compilation if any two members have overlapping index ranges, which would make them write the same keys. Give each member its own range with
+/// the `start` option, and the same key options, such as `encoding`, so that distinct indices mean distinct keys.
+/// ```
+/// # use structurray::{faux_array,assert_namespace};
+/// # use serde::Serialize;
+/// #[faux_array(u8,100,namespace = Node)]
+/// #[derive(Serialize,Default)]
+/// struct Header {}
+///
+/// #[faux_array(u32,500,start = 100,namespace = Node)]
+/// #[derive(Serialize,Default)]
+/// struct Body {}
+///
+/// #[derive(Serialize,Default)]
+/// struct Node {
+///     #[serde(flatten)]
+///     header: Header,
+///     #[serde(flatten)]
+///     body: Body,
+/// }
+///
+/// assert_namespace!(Node: Header, Body);
+/// ```
+/// Overlapping ranges fail to compile:
+/// ```compile_fail
+/// # use structurray::{faux_array,assert_namespace};
+/// # use serde::Serialize;
+/// #[faux_array(u8,100,namespace = Node)]
+/// #[derive(Serialize)]
+/// struct Header {}
+///
+/// #[faux_array(u32,500,start = 99,namespace = Node)]
+/// #[derive(Serialize)]
+/// struct Body {}
+///
+/// struct Node;
+///
+/// assert_namespace!(Node: Header, Body);
+/// ```
+/// ## `new_with`
+/// Written as `new_with` or `new_with = <bool>`. Generates `new_with(f)`, which creates an instance by calling a closure with the index of each slot, once per slot in index order, and putting the result in
+/// that slot. Fields generated by other options, such as timestamps, are filled using [`Default`].
+/// ```
+/// # use structurray::faux_array;
+/// # use serde::Serialize;
+/// #[faux_array(u32,10,new_with)]
+/// #[derive(Serialize)]
+/// struct Squares {}
+///
+/// let squares = Squares::new_with(|index| index * index);
+/// assert_eq!(squares._9,81);
+/// let mut calls = Vec::new();
+/// Squares::new_with(|index| { calls.push(index); 0 });
+/// assert_eq!(calls,(0..10).collect::<Vec<u32>>());
+/// ```
+/// ## `no_panic`
+/// Written as `no_panic` or `no_panic = <bool>`. Guarantees that no generated method panics, so the generated code can live in crates that deny `clippy::panic`. Methods that would panic on bad input
+/// return an [`Option`] instead, which is `None` when they would have panicked:
+/// - Methods that take the index of a slot, like those of `accessors = value_at`, `cells`, `shadow`, `timestamps`, and `tombstones`, along with `chunk` and `chunk_mut`, return `None` if the index is out of range.
+///   Methods that had no return value return `Option<()>`. The methods of `cells = refcell` also return `None` instead of panicking when the slot is already borrowed.
+/// - `keys_range` returns `None` if the range extends past the last slot, and `keys_of` yields `None` for each index that is out of range.
+/// - `clamp_all` returns `None` without changing any slots if `min` is greater than `max`, or if either is NaN.
+/// - `checksum` returns `None` if a field fails to serialize.
+/// - `write_batches` takes a [`NonZeroUsize`](std::num::NonZeroUsize) batch size.
+/// - `into_records` of `soa` returns `None` if the columns aren't aligned, and the [`From`] conversion from the companion to a [`Vec`] is left out.
+/// - `set` of `staged` returns `None` if the index is out of range, and only `try_finish` is generated, not `finish`.
+/// - `canonical_json` returns a [`Result`] holding the `serde_json` error, and `fuzz_round_trip` of `fuzz` returns whether the document survived the round trip.
+///
+/// Options that can only be expressed with panics generate fallible alternatives instead: `index` generates `get` and `get_mut` in place of [`Index`](std::ops::Index), and `from_iter` generates
+/// `try_from_iter` in place of [`FromIterator`].
+#[cfg_attr(feature = "serde", doc = " ```")]
+#[cfg_attr(not(feature = "serde"), doc = " ```ignore")]
+/// # use structurray::faux_array;
+/// # use serde::Serialize;
+/// #[faux_array(u8,4,no_panic,accessors = value_at,tombstones)]
+/// #[derive(Serialize,Default)]
+/// struct Checked {}
+///
+/// let mut checked = Checked::default();
+/// assert_eq!(checked.set_value_at(3,9),Some(0));
+/// assert_eq!(checked.set_value_at(4,9),None);
+/// assert_eq!(checked.mark_deleted(1),Some(()));
+/// assert_eq!(checked.is_deleted(1),Some(true));
+/// assert_eq!(checked.is_deleted(4),None);
+/// assert_eq!(checked.clamp_all(5,1),None);
+/// ```
+/// ```
+/// # use structurray::faux_array;
+/// # use serde::Serialize;
+/// #[faux_array(u8,3,no_panic,index,from_iter,staged = CheckedStaging)]
+/// #[derive(Serialize)]
+/// struct Checked {}
+///
+/// let mut checked = Checked::try_from_iter([1,2,3]).unwrap();
+/// assert_eq!(checked.get(2),Some(&3));
+/// assert_eq!(checked.get_mut(3),None);
+/// assert!(Checked::try_from_iter([1,2]).is_err());
+///
+/// let mut staging = CheckedStaging::uninit();
+/// assert_eq!(staging.set(0,7),Some(()));
+/// assert_eq!(staging.set(3,7),None);
+/// assert!(staging.try_finish().is_err());
+/// ```
+/// ## `pad`
+/// Written as `pad = <width>`. Left-pads every key, and so every generated field name, to the given number of characters with the digit worth zero, so that every key has the same width. Combined with an
+/// encoding whose digits are in ASCII order, such as `encoding = decimal`, `hex`, or `base36`, sorting the keys as strings, as many exports and document viewers do, then also sorts the slots by index.
+/// The default Base62 digits put lowercase letters before uppercase ones, so padded Base62 keys don't sort by index, and neither do the keys of a custom `alphabet` whose digits are out of ASCII order.
+/// Compilation fails if the width is less than the width of the key of the last slot. It can't be combined with `key_scheme`.
+/// ```
+/// # use structurray::faux_array;
+/// # use serde::Serialize;
+/// #[faux_array(u8,100,encoding = base36,pad = 2,key_table)]
+/// #[derive(Serialize,Default)]
+/// struct Sorted {}
+///
+/// assert_eq!(Sorted::default()._09,0);
+/// assert_eq!((Sorted::KEYS[9],Sorted::KEYS[10],Sorted::KEYS[99]),("09","0a","2r"));
+/// let mut keys = Sorted::KEYS.to_vec();
+/// keys.sort();
+/// assert_eq!(keys,Sorted::KEYS);
+/// ```
+/// ## `predicates`
+/// Written as `predicates` or `predicates = <bool>`. Generates `all(predicate)` and `any(predicate)`, which check a predicate against every slot in index order, and `is_uniform()`, which checks whether
+/// every slot holds the same value and requires the element type to implement [`PartialEq`]. Each stops at the first slot that decides its result, which keeps validation before committing a document cheap.
+/// ```
+/// # use structurray::faux_array;
+/// # use serde::Serialize;
+/// #[faux_array(u8,100,predicates)]
+/// #[derive(Serialize,Default)]
+/// struct Levels {}
+///
+/// let mut levels = Levels::default();
+/// assert!(levels.is_uniform());
+/// levels._1B = 7;
+/// assert!(!levels.is_uniform());
+/// assert!(levels.all(|level| *level < 10));
+/// assert!(levels.any(|level| *level == 7));
+/// ```
+/// ## `prefix`
+/// Written as `prefix = "<text>"`. Starts the name of every generated field with the given text instead of an underscore, so `prefix = "slot"` generates `slot0`, `slot1`, and so on. This reads better in
+/// application code and avoids the convention that a leading underscore marks something unused. Fields generated for each slot by other options, such as `timestamps`, are named after the renamed slots,
+/// and the keys slots are serialized under don't change. Compilation fails if the prefix followed by a key isn't an identifier.
+/// ```
+/// # use structurray::faux_array;
+/// # use serde::Serialize;
+/// #[faux_array(u8,3,prefix = "slot",timestamps)]
+/// #[derive(Serialize,Default)]
+/// struct Named {}
+///
+/// let mut named = Named::default();
+/// named.slot2 = 4;
+/// named.touch_at(2,1_700_000_000_000);
+/// assert_eq!((named.slot2,named.slot2_t),(4,1_700_000_000_000));
+/// ```
+/// ## `progress`
+/// Generates an associated function, `deserialize_with_progress`, that reads an instance from any [`Deserializer`](https://docs.rs/serde/latest/serde/trait.Deserializer.html) while calling a callback with the index
+/// and key of every `every`th slot it reads, so interfaces loading very large documents can show progress. If the callback returns `false`, deserialization stops with an error, so the read can be cancelled.
+#[cfg_attr(feature = "serde", doc = " ```")]
+#[cfg_attr(not(feature = "serde"), doc = " ```ignore")]
+/// # use structurray::faux_array;
+/// # use serde::Serialize;
+/// use serde::de::value::{MapDeserializer,Error};
+///
+/// #[faux_array(u8,4,progress)]
+/// #[derive(Serialize)]
+/// struct Pixels {}
+///
+/// let document = vec![("0",1u8),("1",2),("2",3),("3",4)];
+/// let mut reported = Vec::new();
+/// let pixels = Pixels::deserialize_with_progress(MapDeserializer::<_,Error>::new(document.clone().into_iter()),2,|index,_key| {
+///     reported.push(index);
+///     true
+/// }).unwrap();
+/// assert_eq!((pixels._3,reported),(4,vec![1,3]));
+/// assert!(Pixels::deserialize_with_progress(MapDeserializer::<_,Error>::new(document.into_iter()),1,|index,_key| index < 2).is_err());
+/// ```
+/// ## `rename_format`
+/// Written as `rename_format = "<template>"`. Serializes every slot under the template with its `{}` replaced by the slot's key, so `rename_format = "items/{}"` writes slot 0 under `"items/0"`, for
+/// backends whose documents follow a naming scheme of their own. Like `flatten_prefix`, which it generalizes and can't be combined with, it changes every generated key, such as the entries of `KEYS`,
+/// but not the identifiers named after keys. Compilation fails if the template doesn't contain `{}` exactly once. It can't be combined with `split_keys`.
+#[cfg_attr(feature = "serde", doc = " ```")]
+#[cfg_attr(not(feature = "serde"), doc = " ```ignore")]
+/// # use structurray::faux_array;
+/// # use serde::Serialize;
+/// #[faux_array(u8,3,rename_format = "items/{}.v2",key_table)]
+/// #[derive(Serialize,Default)]
+/// struct Items {}
+///
+/// assert_eq!(Items::KEYS,["items/0.v2","items/1.v2","items/2.v2"]);
+/// assert_eq!(Items::index_of("items/2.v2"),Some(2));
+/// assert_eq!(Items::index_of("2"),None);
+/// ```
+/// ## `repr_c`
+/// Adds `#[repr(C)]` to your [`struct`], which guarantees that its slots are laid out in memory exactly like an array of the element type, and uses that guarantee to implement [`AsRef<[T]>`](AsRef),
+/// [`AsMut<[T]>`](AsMut), [`Borrow<[T]>`](std::borrow::Borrow), and [`BorrowMut<[T]>`](std::borrow::BorrowMut), where `T` is the element type. This lets the psuedo-array be passed to any function taking a slice.
+/// Fields generated by other options are placed after the slots, and fields declared in the body of your [`struct`] are placed before them, so neither is part of the slice. The implementations use `unsafe` internally.
+///
+/// If you use your [`struct`] as a key of a map while looking it up by slice, its [`Hash`], [`Eq`], and [`Ord`] implementations must agree with those of the slice, as [`Borrow`](std::borrow::Borrow) requires.
+/// Derived implementations don't, since slices also hash their length, so implement them by comparing and hashing `as_ref()` instead.
+/// ```
+/// # use structurray::faux_array;
+/// # use serde::Serialize;
+/// #[faux_array(u32,4,repr_c)]
+/// #[derive(Serialize,Default)]
+/// struct Lanes {}
+///
+/// let mut lanes = Lanes::default();
+/// lanes.as_mut().copy_from_slice(&[4,3,2,1]);
+/// lanes.as_mut().sort();
+/// assert_eq!(lanes._0,1);
+/// assert_eq!(lanes.as_ref().iter().sum::<u32>(),10);
+/// ```
+/// ## `reserved_keys`
+/// Written as `reserved_keys = ["key", ...]`. Declares keys that belong to other code, such as hand-written fields of a document your [`struct`] is flattened into, and fails compilation if the attribute would generate
+/// any of them. Slot keys, timestamp keys, the tombstone and checksum keys, and the group keys of `split_keys` are all checked.
+/// ```
+/// # use structurray::faux_array;
+/// # use serde::Serialize;
+/// #[faux_array(u8,4,reserved_keys = ["meta","owner"])]
+/// #[derive(Serialize)]
+/// struct Flattened {}
+/// ```
+/// A key that collides is rejected:
+/// ```compile_fail
+/// # use structurray::faux_array;
+/// # use serde::Serialize;
+/// #[faux_array(u8,4,timestamps,reserved_keys = ["2_t"])]
+/// #[derive(Serialize)]
+/// struct Flattened {}
+/// ```
+/// ## `sections`
+/// Written as `sections(name: Type = start..end, ...)`. Gives the slots from index `start` up to (but not including) `end` the element type `Type` instead of the first argument, so one document can hold
+/// logically distinct regions. For each section, `name()` borrows its slots as an array of references, and `name_mut()` borrows them mutably. Slots outside of every section keep the first argument as their type.
+/// Sections can't overlap, and since the slots no longer share one type, this option can only be combined with options that don't depend on the element type, such as `const_default`, `init`, `key_table`, `shadow`, `timestamps`, and `tombstones`.
+/// ```
+/// # use structurray::faux_array;
+/// # use serde::Serialize;
+/// #[faux_array(u8,6,sections(scores: u32 = 0..3, names: String = 3..6))]
+/// #[derive(Serialize,Default)]
+/// struct Roster {}
+///
+/// let mut roster = Roster::default();
+/// *roster.scores_mut()[1] = 90;
+/// roster.names_mut()[0].push_str("Ada");
+/// assert_eq!(roster.scores(),[&0,&90,&0]);
+/// assert_eq!(roster._3,"Ada");
+/// ```
+/// ## `serde`
+/// Written as `serde = false`. Generates plain fields without any serde attributes, so this attribute can be used on [`struct`]s that never implement [`Serialize`], such as ones that are only used internally.
+/// Options that generate code using serde, such as `delta`, can't be combined with it. Unlike disabling this crate's `serde` feature, this only affects the [`struct`] it is given to.
+/// ```
+/// # use structurray::faux_array;
+/// #[faux_array(u8,100,serde = false)]
+/// #[derive(Default)]
+/// struct Internal {}
+///
+/// assert_eq!(Internal::default()._1B,0);
+/// ```
+/// ## `serde_feature`
+/// Written as `serde_feature = "serde"`. Wraps every generated serde attribute in `#[cfg_attr(feature = "serde", ...)]`, using the given feature name, so that library authors can make serde an optional
+/// feature of their own crates while still using this attribute. Your [`struct`] should then only derive [`Serialize`] when that feature is enabled, using `cfg_attr` the same way. This option can't be
+/// combined with options that generate serde implementations, such as `delta`.
+#[cfg_attr(feature = "serde", doc = " ```")]
+#[cfg_attr(not(feature = "serde"), doc = " ```ignore")]
+/// # use structurray::faux_array;
+/// #[faux_array(u8,2,serde_feature = "serde")]
+/// #[cfg_attr(feature = "serde",derive(serde::Serialize))]
+/// struct Optional {}
+/// ```
+/// ## `shadow`
+/// Written as `shadow = Type`. Generates a second field for every slot, named after the slot with `_shadow` appended, holding a value of the given type that is only used at runtime and is never
+/// written to the database, such as a dirty flag or a cache entry. Shadow fields are marked `#[serde(skip)]`, so they are filled using [`Default`] when deserializing, and are accessed by index with
+/// the generated `shadow` and `shadow_mut` methods, which panic if the index is out of range.
+/// ```
+/// # use structurray::faux_array;
+/// # use serde::Serialize;
+/// #[faux_array(i32,2,shadow = bool)]
+/// #[derive(Serialize,Default)]
+/// struct Balances {}
+///
+/// let mut balances = Balances::default();
+/// balances._1 = 50;
+/// *balances.shadow_mut(1) = true;
+/// assert!(*balances.shadow(1));
+/// assert!(!balances._0_shadow);
+/// ```
+/// ## `soa`
+/// Generates a struct-of-arrays companion with the name given to this option, such as `soa = LazyrrayColumns`. The companion has one public [`Vec`] per slot, so slot `i` of every record
+/// sits in one contiguous column and can be processed column-wise without skipping over the other slots of each record. The companion has the same generics and visibility as your [`struct`], and
+/// provides `new`, `len`, `is_empty`, `is_aligned`, `push`, and `into_records` methods along with [`From`] conversions to and from a [`Vec`] of records. Since the columns are public, they can be given
+/// different lengths, which `is_aligned` detects and which makes `into_records` panic, or return `None` if `no_panic` is given. The companion only has columns for the slots, so compilation fails if your [`struct`] has declared fields or is
+/// combined with an option that adds fields, like `timestamps`, `tombstones`, or `shadow`.
+/// ```
+/// # use structurray::faux_array;
+/// # use serde::Serialize;
+/// #[faux_array(u8,2,soa = PairColumns)]
+/// #[derive(Serialize)]
+/// struct Pair {}
+///
+/// let columns = PairColumns::from(vec![Pair { _0: 1, _1: 2 }, Pair { _0: 3, _1: 4 }]);
+/// assert_eq!(columns._1,vec![2,4]);
+/// assert!(columns.is_aligned());
+/// let records: Vec<Pair> = columns.into();
+/// assert_eq!(records[1]._0,3);
+/// ```
+/// ## `sparse`
+/// Written as `sparse` or `sparse = <bool>`, and only allowed when the element type is an [`Option`]. Slots holding [`None`] are left out of the serialized map, and slots missing from a serialized map are
+/// read as [`None`], which keeps mostly-empty documents small on the wire. In memory, pairing this option with an element type that has a niche, like `Option<NonZeroU32>`, keeps every slot as small as the
+/// bare number, since [`None`] is stored as the zero the number can never be.
+#[cfg_attr(feature = "serde", doc = " ```")]
+#[cfg_attr(not(feature = "serde"), doc = " ```ignore")]
+/// # use structurray::faux_array;
+/// use serde::{Serialize,Deserialize};
+/// use serde::de::value::{MapDeserializer,Error};
+/// use std::num::NonZeroU32;
+///
+/// #[faux_array(Option<NonZeroU32>,100,sparse)]
+/// #[derive(Serialize,Deserialize)]
+/// struct Seats {}
+///
+/// assert_eq!(std::mem::size_of::<Seats>(),100 * 4);
+/// // A document without any keys is read as a psuedo-array of empty slots
+/// let document = MapDeserializer::<_,Error>::new(Vec::<(&str,u32)>::new().into_iter());
+/// let seats = Seats::deserialize(document).unwrap();
+/// assert_eq!(seats._1B,None);
+/// ```
+/// ## `split_keys`
+/// Written as `split_keys = <integer>`. Instead of relying on derived serde implementations, the attribute implements [`Serialize`] and [`Deserialize`] itself, nesting the slots under the given number of
+/// top-level keys instead of writing them all into one flat map. This matters for databases like Firebase, where shallow queries and listeners work one top-level key at a time. The slots are divided into
+/// contiguous groups whose sizes differ by at most one, each group is written under the key of its own index (`"0"`, `"1"`, and so on), and each slot keeps its usual key inside its group.
+/// Because the implementations are generated, a [`struct`] using this option must **not** derive [`Serialize`] or [`Deserialize`], and this option can't be combined with `checksum`.
+/// When combined with `repr_c`, each group can also be handled on its own as a chunk: `chunk(index)` and `chunk_mut(index)` borrow the slots of one group as a slice, and `chunks()` iterates over every group,
+/// which makes loading or flushing part of a huge document a matter of working with one top-level key.
+#[cfg_attr(feature = "serde", doc = " ```")]
+#[cfg_attr(not(feature = "serde"), doc = " ```ignore")]
+/// # use structurray::faux_array;
+/// use serde::Deserialize;
+/// use serde::de::value::{MapDeserializer,Error};
+///
+/// // Slots 0 and 1 are written under "0", and slots 2 and 3 are written under "1"
+/// #[faux_array(u8,4,split_keys = 2)]
+/// struct Sharded {}
+///
+/// let first = MapDeserializer::<_,Error>::new(vec![("0",1u8),("1",2)].into_iter());
+/// let second = MapDeserializer::<_,Error>::new(vec![("2",3u8),("3",4)].into_iter());
+/// let document = MapDeserializer::<_,Error>::new(vec![("0",first),("1",second)].into_iter());
+/// let sharded = Sharded::deserialize(document).unwrap();
+/// assert_eq!(sharded._3,4);
+///
+/// // Slots 0 and 1 form the first chunk, and slots 2, 3, and 4 form the second
+/// #[faux_array(u8,5,split_keys = 2,repr_c)]
+/// #[derive(Default)]
+/// struct Chunked {}
+///
+/// let mut chunked = Chunked::default();
+/// chunked.chunk_mut(1).fill(9);
+/// assert_eq!(chunked.chunks().map(|chunk| chunk.len()).collect::<Vec<_>>(),[2,3]);
+/// assert_eq!(chunked.chunk(1),[9,9,9]);
+/// ```
+/// ## `staged`
+/// Generates a staging type with the name given to this option, such as `staged = LazyrrayStaging`, for building very large instances without constructing them on the stack or filling every slot twice.
+/// `uninit` allocates an instance on the heap without initializing its slots, `set` initializes one slot by index (panicking if the index is out of range, or returning `None` if `no_panic` is given),
+/// and `finish` returns the boxed instance once every slot has been set, panicking with a list of the missing indices otherwise. `finish` isn't generated when `no_panic` is given. `try_finish`, `is_complete`, and `missing` allow checking for completeness without panicking.
+/// Fields generated by other options, such as timestamps, are filled using [`Default`]. The generated code uses `unsafe` internally, so it can't be used in crates that forbid `unsafe` code.
+/// ```
+/// # use structurray::faux_array;
+/// # use serde::Serialize;
+/// #[faux_array(String,2,staged = LabelsStaging)]
+/// #[derive(Serialize)]
+/// struct Labels {}
+///
+/// let mut staging = LabelsStaging::uninit();
+/// staging.set(1,String::from("second"));
+/// assert_eq!(staging.missing(),vec![0]);
+/// staging.set(0,String::from("first"));
+/// let labels: Box<Labels> = staging.finish();
+/// assert_eq!(labels._0,"first");
+/// ```
+/// ## `start`
+/// Written as `start = <integer>`. Generates the keys and field names of the slots from consecutive indices beginning at the given index rather than at `0`, so data sharded across several [`struct`]s can
+/// give each shard its own range of keys. With `start = 1000`, the first slot is named `_g8` and serialized under `"g8"`. Methods that take or return the index of a slot, such as `index_of` and the
+/// methods of `timestamps`, still count from `0` at the first slot of your [`struct`]. Compilation fails if the index of the last slot wouldn't fit in a [`u32`].
+/// ```
+/// # use structurray::faux_array;
+/// # use serde::Serialize;
+/// #[faux_array(u8,1000,key_table)]
+/// #[derive(Serialize,Default)]
+/// struct First {}
+///
+/// #[faux_array(u8,1000,start = 1000,key_table)]
+/// #[derive(Serialize,Default)]
+/// struct Second {}
+///
+/// assert_eq!(Second::default()._g8,0);
+/// assert_eq!(Second::KEYS[0],"g8");
+/// assert_eq!(Second::index_of("g8"),Some(0));
+/// assert!(First::KEYS.iter().all(|key| !Second::KEYS.contains(key)));
+/// ```
+/// ## `swap`
+/// Written as `swap` or `swap = <bool>`. Generates `swap(a, b)`, which exchanges the values of the slots at two indices, like [`slice::swap`]. Like that method, it panics if either index is out of range,
+/// unless `no_panic` is given, in which case it returns `None` without changing any slots instead.
+/// ```
+/// # use structurray::faux_array;
+/// # use serde::Serialize;
+/// #[faux_array(char,3,swap)]
+/// #[derive(Serialize)]
+/// struct Letters {}
+///
+/// let mut letters = Letters { _0: 'a', _1: 'b', _2: 'c' };
+/// letters.swap(0,2);
+/// assert_eq!((letters._0,letters._2),('c','a'));
+/// ```
+/// ## `time_buckets`
+/// Written as `time_buckets(width = <width>)` or `time_buckets(epoch = <millis>, width = <width>)`. Treats the slots as a ring of time buckets stored in one document, such as the last 24 hours of a counter,
+/// by generating `slot_for(millis)`, which returns the index of the slot holding the bucket that contains a moment given in milliseconds since the [Unix epoch](std::time::UNIX_EPOCH), and `key_for(millis)`,
+/// which returns the key of that slot. The width of each bucket is either `minute`, `hour`, `day`, `week`, or a number of milliseconds. The first slot holds the bucket starting at `epoch` (the Unix epoch
+/// unless one is given), and buckets wrap around after the last slot, so each slot is reused once every full turn of the ring. The epoch and width are also generated as `BUCKET_EPOCH` and `BUCKET_WIDTH`.
+/// ```
+/// # use structurray::faux_array;
+/// # use serde::Serialize;
+/// #[faux_array(u32,24,time_buckets(width = hour))]
+/// #[derive(Serialize,Default)]
+/// struct Hourly {}
+///
+/// let noon = 12 * Hourly::BUCKET_WIDTH;
+/// assert_eq!(Hourly::slot_for(noon + 59 * 60_000),12);
+/// assert_eq!(Hourly::slot_for(noon + 24 * Hourly::BUCKET_WIDTH),12);
+/// assert_eq!(Hourly::key_for(noon),"c");
+/// ```
+/// ## `timestamps`
+/// Generates a second field for every slot, named after the slot with `_t` appended (and serialized under the slot's key with `_t` appended), which records when that slot was last updated
+/// in milliseconds since the [Unix epoch](std::time::UNIX_EPOCH). The timestamps are updated with the generated `touch` method, which records the current time, or `touch_at`, which records a time you provide,
+/// and read with `touched_at`. All three methods take the index of a slot and panic if the index is out of range. The serde implementations generated by `checksum`, `deserialize`, and `progress` write and
+/// read the timestamps under their keys as well, and `checksum` covers them with the checksum.
+/// ```
+/// # use structurray::faux_array;
+/// # use serde::Serialize;
+/// #[faux_array(String,2,timestamps)]
+/// #[derive(Serialize)]
+/// struct Names {}
+///
+/// let mut names = Names { _0: String::from("Ada"), _1: String::new(), _0_t: 0, _1_t: 0 };
+/// names._1 = String::from("Grace");
+/// names.touch_at(1,1_700_000_000_000);
+/// assert_eq!(names.touched_at(1),1_700_000_000_000);
+/// assert_eq!(names.touched_at(0),0);
+/// ```
+#[cfg_attr(feature = "serde", doc = " ```")]
+#[cfg_attr(not(feature = "serde"), doc = " ```ignore")]
+/// # use structurray::faux_array;
+/// #[faux_array(u32,2,timestamps,checksum = crc32)]
+/// struct Stamped {}
+///
+/// let mut stamped = Stamped { _0: 1, _1: 2, _0_t: 0, _1_t: 0 };
+/// let untouched = stamped.checksum();
+/// stamped.touch_at(0,1_700_000_000_000);
+/// assert_ne!(stamped.checksum(),untouched);
+/// ```
+#[cfg_attr(feature = "serde", doc = " ```")]
+#[cfg_attr(not(feature = "serde"), doc = " ```ignore")]
+/// # use structurray::faux_array;
+/// use serde::{Serialize,Deserialize};
+/// use serde::de::value::{MapDeserializer,Error};
+///
+/// #[faux_array(u64,2,timestamps,deserialize)]
+/// #[derive(Serialize)]
+/// struct Counters {}
+///
+/// let fields = vec![("0",4u64),("1",9),("0_t",1_700_000_000_000),("1_t",1_700_000_000_500)];
+/// let counters = Counters::deserialize(MapDeserializer::<_,Error>::new(fields.into_iter())).unwrap();
+/// assert_eq!(counters.touched_at(1),1_700_000_000_500);
+/// ```
+/// ## `tombstones`
+/// Generates one extra field, `_deleted`, holding a bitset with one bit per slot, so slots can be logically deleted without adding a [`bool`] field for each of them. The bitset is serialized as a single
+/// base64 string under the `"deleted"` key, and is managed with the generated `mark_deleted`, `restore`, and `is_deleted` methods, which each take the index of a slot and panic if it is out of range.
+/// The type of the bitset is named after your [`struct`] with `Tombstones` appended, and implements [`Default`] with no slots deleted. The serde implementations generated by `checksum`, `deserialize`,
+/// and `progress` write and read the bitset under its key as well, so deleted slots stay deleted. Compilation fails if a slot would also be serialized under `"deleted"`, such as with `rename_format`.
+#[cfg_attr(feature = "serde", doc = " ```")]
+#[cfg_attr(not(feature = "serde"), doc = " ```ignore")]
+/// # use structurray::faux_array;
+/// # use serde::Serialize;
+/// #[faux_array(u8,10,tombstones)]
+/// #[derive(Serialize,Default)]
+/// struct Inventory {}
+///
+/// let mut inventory = Inventory::default();
+/// inventory.mark_deleted(9);
+/// assert!(inventory.is_deleted(9));
+/// assert!(!inventory.is_deleted(8));
+/// assert_eq!(inventory._deleted.to_base64(),"AAI=");
+/// assert_eq!(InventoryTombstones::from_base64("AAI="),Some(inventory._deleted));
+/// assert!(InventoryTombstones::from_base64("AAI=junk").is_none());
+/// assert!(InventoryTombstones::from_base64("AAJ=").is_none());
+/// assert!(InventoryTombstones::from_base64("AAI").is_none());
+/// ```
+#[cfg_attr(feature = "serde", doc = " ```")]
+#[cfg_attr(not(feature = "serde"), doc = " ```ignore")]
+/// # use structurray::faux_array;
+/// #[faux_array(u8,2,tombstones,checksum = crc32)]
+/// #[derive(Default)]
+/// struct Shelf {}
+///
+/// let mut shelf = Shelf::default();
+/// let untouched = shelf.checksum();
+/// shelf.mark_deleted(1);
+/// assert_ne!(shelf.checksum(),untouched);
+/// ```
+#[cfg_attr(feature = "serde", doc = " ```")]
+#[cfg_attr(not(feature = "serde"), doc = " ```ignore")]
+/// # use structurray::faux_array;
+/// use serde::{Serialize,Deserialize};
+/// use serde::de::value::{MapDeserializer,Error};
+///
+/// #[faux_array(String,2,tombstones,deserialize)]
+/// #[derive(Serialize)]
+/// struct Names {}
+///
+/// let fields = vec![("0","Ada"),("1","Grace"),("deleted","AQ==")];
+/// let names = Names::deserialize(MapDeserializer::<_,Error>::new(fields.into_iter())).unwrap();
+/// assert!(names.is_deleted(0));
+/// assert!(!names.is_deleted(1));
+/// ```
+/// ## `try_from_iter`
+/// Written as `try_from_iter` or `try_from_iter = <bool>`. Generates `try_from_iter(iter)`, which puts each item of an iterator in the slot with the same index, and an error type named after your [`struct`]
+/// with `LengthError` appended. Unlike collecting through [`structinator`](https://crates.io/crates/structinator), an iterator yielding too few or too many items is rejected with an error holding both the
+/// number of slots (`expected`) and the number of items (`received`). An iterator with too many items is consumed to the end so every item is counted, so it shouldn't be endless.
+/// ```
+/// # use structurray::faux_array;
+/// # use serde::Serialize;
+/// #[faux_array(u8,3,try_from_iter)]
+/// #[derive(Serialize,Debug)]
+/// struct Rgb {}
+///
+/// let teal = Rgb::try_from_iter([0,128,128]).unwrap();
+/// assert_eq!(teal._2,128);
+/// let short = Rgb::try_from_iter(vec![0,128]).unwrap_err();
+/// assert_eq!((short.expected,short.received),(3,2));
+/// assert_eq!(Rgb::try_from_iter(0..5).unwrap_err().to_string(),"expected 3 items for Rgb, but received 5");
+/// ```
+/// ## `try_from_vec`
+/// Written as `try_from_vec` or `try_from_vec = <bool>`. Implements [`TryFrom<Vec<T>>`](TryFrom), which moves each element of a [`Vec`] into the slot with the same index, for data that arrives as a vector
+/// from a query layer. A vector whose length differs from the number of slots is rejected with the same error type as `try_from_iter`, which holds both the number of slots (`expected`) and the length of the
+/// vector (`received`).
+/// ```
+/// # use structurray::faux_array;
+/// # use serde::Serialize;
+/// #[faux_array(u8,3,try_from_vec)]
+/// #[derive(Serialize,Debug)]
+/// struct Rgb {}
+///
+/// let teal = Rgb::try_from(vec![0,128,128]).unwrap();
+/// assert_eq!(teal._2,128);
+/// let long = Rgb::try_from(vec![0,128,128,255]).unwrap_err();
+/// assert_eq!((long.expected,long.received),(3,4));
+/// assert_eq!(long.to_string(),"expected 3 items for Rgb, but received 4");
+/// ```
+/// ## `tuple_from`
+/// Written as `tuple_from` or `tuple_from = <limit>`. If your [`struct`] has no more slots than the limit (16 unless one is given), implements [`From`] for a tuple with one element per slot, which puts each element
+/// in the slot with the same index. This lets small psuedo-arrays be written literally in tests and fixtures. Larger psuedo-arrays don't get the implementation, so the same options can be shared by [`struct`]s of every size.
+/// ```
+/// # use structurray::faux_array;
+/// # use serde::Serialize;
+/// #[faux_array(u8,3,tuple_from)]
+/// #[derive(Serialize)]
+/// struct Rgb {}
+///
+/// let teal = Rgb::from((0,128,128));
+/// assert_eq!(teal._1,128);
+/// ```
+/// ## `validate`
+/// Requires this crate's `validator` feature. Written as `validate(<rules>)`, and puts `#[validate(<rules>)]` on every slot, so deriving [`validator::Validate`](https://docs.rs/validator) on your [`struct`]
+/// checks every slot against the same rules. Fields generated by other options aren't given the attribute.
+/// ```no_run
+/// # /*
+/// #[faux_array(String,10,validate(length(min = 1, max = 64)))]
+/// #[derive(Serialize,Validate)]
+/// struct Names {}
+/// # */
+/// ```
+/// ## `validate_with`
+/// Written as `validate_with = "path::to::function"`. Generates `validate()`, which passes a reference to every slot to the function, in index order, and returns the index and error of every slot
+/// the function rejected. The function should return `Result<(), String>`, unless the error type is changed with `validation_error = <type>`.
+/// ```
+/// # use structurray::faux_array;
+/// # use serde::Serialize;
+/// fn in_range(score: &i32) -> Result<(),String> {
+///     if (0..=100).contains(score) {
+///         Ok(())
+///     } else {
+///         Err(format!("{} is not a valid score",score))
+///     }
+/// }
+///
+/// #[faux_array(i32,4,validate_with = "in_range")]
+/// #[derive(Serialize,Default)]
+/// struct Scores {}
+///
+/// let mut scores = Scores::default();
+/// assert!(scores.validate().is_ok());
+/// scores._3 = 101;
+/// assert_eq!(scores.validate(),Err(vec![(3,String::from("101 is not a valid score"))]));
+/// ```
+/// ## `write_batches`
+/// Written as `write_batches` or `write_batches = <bool>`. Generates `write_batches(batch_size)`, which iterates over [`HashMap`](std::collections::HashMap)s from keys to slots holding at most `batch_size` slots each,
+/// in the order of the slots. Like every iterator the attribute generates, it can be reversed and reports its length. This fits writes to databases that limit the size of a batch (Firestore allows 500 writes per batch) or that sit behind a rate limiter.
+/// ```
+/// # use structurray::faux_array;
+/// # use serde::Serialize;
+/// #[faux_array(u16,1200,write_batches)]
+/// #[derive(Serialize,Default)]
+/// struct Scores {}
+///
+/// let scores = Scores::default();
+/// let sizes: Vec<usize> = scores.write_batches(500).map(|batch| batch.len()).collect();
+/// assert_eq!(sizes,[500,500,200]);
+/// assert_eq!(scores.write_batches(500).rev().next().unwrap().len(),200);
+/// ```
+/// # Errors
+/// Misuse of this attribute fails compilation with an error pointing at the offending argument, option, or field, rather than at the whole attribute. Compilation fails if the arguments are out of order
+/// or formatted incorrectly (most common cause of incorrect formatting is missing a comma), if the first argument can't be parsed to a type, or if the second argument cannot be parsed and stored in a [`u32`].
+/// It also fails if an option is unknown, given an invalid value, or combined with an option it conflicts with, if the `gen_tests` or `key_tokens` option is used on a generic [`struct`], if the `const_default` option is combined
+/// with declared fields or an option that adds other fields, or if a declared field collides with a generated one. Unless the `serde` feature is disabled or the `serde = false` option is given, the [`struct`] this attribute is attached to must
+/// also implement [`Serialize`], or the generated `rename` attributes won't compile.
+///
+/// [`struct`]: https://doc.rust-lang.org/1.58.1/std/keyword.struct.html
+/// [`Serialize`]: https://docs.rs/serde/latest/serde
+/// [`Deserialize`]: https://docs.rs/serde/latest/serde/trait.Deserialize.html
+pub fn faux_array(args: TokenStream, actual: TokenStream) -> TokenStream {
+    expand(args,actual).unwrap_or_else(|problem| problem.to_compile_error()).into()
+}
+/// Generates the psuedo-array described by `args` from the [`struct`](https://doc.rust-lang.org/1.58.1/std/keyword.struct.html) `actual`, or the first problem found with either
+fn expand(args: TokenStream, actual: TokenStream) -> Result<proc_macro2::TokenStream,syn::Error> {
+    let arguments: Arguments = parse(args)?;
+    let build_length = usize::try_from(arguments.field_count).map_err(|_| syn::Error::new(Span::call_site(),format!("{}. The second argument was successfully parsed to a u32, but failed conversion to a usize integer. Make sure the second argument is less than or equal to {}",ARGUMENT_ERROR_MESSAGE,usize::MAX)))?;
+    let structure: ItemStruct = parse(actual).map_err(|problem| syn::Error::new(problem.span(),"The faux_array attribute should only be attached to struct definitions"))?;
+    if arguments.checksum && encoding::position(&arguments,checksum::CHECKSUM_KEY).is_some() {
+        return Err(syn::Error::new(Span::call_site(),format!("the checksum is serialized under \"{}\", which is also the key of a slot",checksum::CHECKSUM_KEY)));
+    }
+    if arguments.tombstones && encoding::position(&arguments,tombstones::TOMBSTONE_KEY).is_some() {
+        return Err(syn::Error::new(Span::call_site(),format!("the bitset of the `tombstones` option is serialized under \"{}\", which is also the key of a slot",tombstones::TOMBSTONE_KEY)));
+    }
+    let tuple = unnamed::requested(&structure);
+    if tuple {
+        unnamed::check(&arguments.options)?;
+    }
+    let (attributes, composing) = compose(&structure.attrs);
+    let visibility = &structure.vis;
+    let name = &structure.ident;
+    let generics = &structure.generics;
+    let element = &arguments.field_type;
+    let stored = arguments.cells.map(|cells| cells.wrap(element));
+    let tipe = stored.as_ref().unwrap_or(element);
+    let mut names: Vec<String> = Vec::with_capacity(build_length);
+    let mut suffixes: Vec<String> = Vec::with_capacity(build_length);
+    let mut idents: Vec<Ident> = Vec::with_capacity(build_length);
+    let mut looper: u32 = 0;
+    while looper < arguments.field_count {
+        names.push(encoding::key(&arguments,looper));
+        suffixes.push(encoding::suffix(&arguments,looper));
+        idents.push(Ident::new(&encoding::field_name(&arguments,looper),Span::call_site()));
+        looper += 1;
+    }
+    let serde_attributes = arguments.serde && !arguments.checksum && arguments.split_keys.is_none() && arguments.blob.is_none() && arguments.key_encoder.is_none() && cfg!(feature = "serde");
+    let serde_rename = |key: &str| {
+        if serde_attributes {
+            serde_attribute(arguments.serde_feature.as_ref(),quote! { serde(rename = #key) })
+        } else {
+            quote! {}
+        }
+    };
+    let aliases: Vec<Vec<String>> = names.iter().map(|key| arguments.aliases.iter().map(|template| template.value().replace("{}",key)).collect()).collect();
+    let slot_attributes: Vec<proc_macro2::TokenStream> = names.iter().zip(&aliases).map(|(key, aliases)| {
+        if serde_attributes && !aliases.is_empty() {
+            serde_attribute(arguments.serde_feature.as_ref(),quote! { serde(rename = #key #(, alias = #aliases)*) })
+        } else {
+            serde_rename(key)
+        }
+    }).collect();
+    let mut slot_types: Vec<&Type> = vec![tipe; build_length];
+    for section in &arguments.sections {
+        for index in section.range() {
+            slot_types[index as usize] = &section.element;
+        }
+    }
+    let skip_empty = if arguments.sparse {
+        serde_attribute(arguments.serde_feature.as_ref(),quote! { serde(default, skip_serializing_if = "Option::is_none") })
+    } else {
+        quote! {}
+    };
+    let validation = match &arguments.validate {
+        Some(rules) => quote! { #[validate(#rules)] },
+        None => quote! {},
+    };
+    let field_visibility = &arguments.field_vis;
+    let field_attributes = &arguments.field_attributes;
+    let declared = declared::fields(&structure)?;
+    let mut fields: Vec<proc_macro2::TokenStream> = declared.iter().map(|field| quote! { #field }).collect();
+    fields.extend(slot_attributes.iter().zip(&idents).zip(&slot_types).map(|((rename, ident), slot_type)| {
+        quote! { #rename #skip_empty #validation #(#field_attributes)* #field_visibility #ident : #slot_type }
+    }));
+    let mut extra_fields: Vec<Ident> = Vec::new();
+    if arguments.timestamps {
+        for (key, stamp) in names.iter().zip(timestamps::idents(&idents)) {
+            let rename = serde_rename(&format!("{}_t",key));
+            fields.push(quote! { #rename #field_visibility #stamp : u64 });
+            extra_fields.push(stamp);
+        }
+    }
+    if arguments.tombstones {
+        let rename = serde_rename(tombstones::TOMBSTONE_KEY);
+        let field = tombstones::ident();
+        let bitset = tombstones::type_name(name);
+        fields.push(quote! { #rename #field_visibility #field : #bitset });
+        extra_fields.push(field);
+    }
+    if let Some(shadow) = &arguments.shadow {
+        let skip = if serde_attributes {
+            serde_attribute(arguments.serde_feature.as_ref(),quote! { serde(skip) })
+        } else {
+            quote! {}
+        };
+        for field in shadow::idents(&idents) {
+            fields.push(quote! { #skip #field_visibility #field : #shadow });
+            extra_fields.push(field);
+        }
+    }
+    let generated: Vec<&Ident> = idents.iter().chain(&extra_fields).collect();
+    declared::check(&declared,&generated,&arguments)?;
+    if let Some(columns) = &arguments.soa {
+        if !declared.is_empty() || !extra_fields.is_empty() {
+            return Err(syn::Error::new(columns.span(),"the `soa` option only has columns for the slots, so it can't be used on a struct with declared fields or combined with options that add fields, such as `timestamps`"));
+        }
+    }
+    extra_fields.splice(0..0,declared.iter().filter_map(|field| field.ident.clone()));
+    let mut carried: Vec<deserialize::Carried> = Vec::new();
+    if arguments.checksum || arguments.deserialize || arguments.progress {
+        for field in &declared {
+            carried.push(declared::carried(field)?);
+        }
+        if arguments.timestamps {
+            for (key, stamp) in names.iter().zip(timestamps::idents(&idents)) {
+                carried.push(deserialize::Carried::keyed(stamp,syn::parse_quote!(u64),format!("{}_t",key)));
+            }
+        }
+        if arguments.tombstones {
+            let bitset = tombstones::type_name(name);
+            carried.push(deserialize::Carried::keyed(tombstones::ident(),syn::parse_quote!(#bitset),tombstones::TOMBSTONE_KEY.to_owned()));
+        }
+    }
+    let layout = Layout {
+        structure: &structure,
+        field_type: tipe,
+        names: &names,
+        suffixes: &suffixes,
+        idents: &idents,
+        slot_attributes: &slot_attributes,
+        serde_feature: arguments.serde_feature.as_ref(),
+        extra_fields: &extra_fields,
+        carried: &carried,
+        serde_bounds: arguments.serde_bound.as_ref().map(|(_, bounds)| bounds.as_slice()),
+        aliases: &aliases,
+        metrics: arguments.metrics.as_ref(),
+        no_panic: arguments.no_panic,
+        key_table: arguments.key_table,
+    };
+    let mut additions: Vec<proc_macro2::TokenStream> = Vec::new();
+    if arguments.metadata_only {
+        additions.push(keys::generate(&layout));
+        additions.push(digest::generate(&layout));
+        if arguments.capnp {
+            additions.push(capnp::generate(&layout));
+        }
+        if let Some(namespace) = &arguments.namespace {
+            additions.push(namespace::generate(&layout,namespace,arguments.start));
+        }
+        if arguments.len {
+            additions.push(len::generate(&layout));
+        }
+        if arguments.field_names {
+            additions.push(keys::generate_field_names(&layout));
+        }
+        if let Some(enumeration) = &arguments.key_enum {
+            additions.push(enumeration::generate(&layout,&enumeration.clone().unwrap_or_else(|| enumeration::default_name(&structure.ident))));
+        }
+        let additions = match &arguments.allow {
+            Some(lints) => generated::mark(quote! { #(#additions)* },lints)?,
+            None => quote! { #(#additions)* },
+        };
+        return Ok(quote! {
+            #structure
+            #additions
+        });
+    }
+    if tuple {
+        if arguments.key_table {
+            additions.push(keys::generate(&layout));
+        }
+        if arguments.key_digest {
+            additions.push(digest::generate(&layout));
+        }
+        if arguments.capnp {
+            additions.push(capnp::generate(&layout));
+        }
+        if let Some(namespace) = &arguments.namespace {
+            additions.push(namespace::generate(&layout,namespace,arguments.start));
+        }
+        if arguments.len {
+            additions.push(len::generate(&layout));
+        }
+        if arguments.field_names {
+            additions.push(keys::generate_field_names(&layout));
+        }
+        if let Some(enumeration) = &arguments.key_enum {
+            additions.push(enumeration::generate(&layout,&enumeration.clone().unwrap_or_else(|| enumeration::default_name(&structure.ident))));
+        }
+        if let Some(implemented) = arguments.assert_send {
+            additions.push(threads::generate(&layout,"Send",implemented));
+        }
+        if let Some(implemented) = arguments.assert_sync {
+            additions.push(threads::generate(&layout,"Sync",implemented));
+        }
+        if arguments.serde && cfg!(feature = "serde") {
+            additions.push(unnamed::generate(&layout));
+        }
+        let container_attributes = &arguments.container_attributes;
+        let (allowed, additions) = match &arguments.allow {
+            Some(lints) => (quote! { #[allow(#(#lints),*)] },generated::mark(quote! { #(#additions)* },lints)?),
+            None => (quote! {},quote! { #(#additions)* }),
+        };
+        let where_clause = &generics.where_clause;
+        let fields = slot_types.iter().map(|slot_type| quote! { #field_visibility #slot_type });
+        return Ok(quote! {
+            #(#attributes)*
+            #(#container_attributes)*
+            #allowed
+            #visibility struct #name #generics (#(#fields),*) #where_clause;
+            #additions
+        });
+    }
+    if arguments.capnp {
+        additions.push(capnp::generate(&layout));
+    }
+    if let Some(columns) = &arguments.soa {
+        additions.push(soa::generate(&layout,columns));
+    }
+    if arguments.delta {
+        additions.push(delta::generate(&layout));
+    }
+    if arguments.checksum {
+        additions.push(checksum::generate(&layout));
+    }
+    if arguments.deserialize {
+        additions.push(visitor::generate(&layout));
+    }
+    if arguments.timestamps {
+        additions.push(timestamps::generate(&layout));
+    }
+    if arguments.tombstones {
+        additions.push(tombstones::generate(&layout));
+    }
+    if let Some(mirror) = &arguments.companion {
+        additions.push(companion::generate(&layout,mirror));
+    }
+    if arguments.gen_tests {
+        additions.push(tests::generate(&layout)?);
+    }
+    if arguments.fuzz {
+        additions.push(fuzz::generate(&layout));
+    }
+    if let Some(twin) = &arguments.borrowed {
+        additions.push(borrowed::generate(&layout,twin));
+    }
+    if let Some(cells) = arguments.cells {
+        additions.push(cells::generate(&layout,cells,element));
+    }
+    if arguments.boxed {
+        additions.push(boxed::generate(&layout,arguments.init.as_ref()));
+    }
+    if let Some(shadow) = &arguments.shadow {
+        additions.push(shadow::generate(&layout,shadow));
+    }
+    if arguments.progress {
+        additions.push(progress::generate(&layout));
+    }
+    if arguments.child_events {
+        additions.push(events::generate(&layout));
+    }
+    if arguments.key_table {
+        additions.push(keys::generate(&layout));
+    }
+    if arguments.key_tokens {
+        additions.push(tokens::generate(&layout)?);
+    }
+    if arguments.write_batches {
+        additions.push(batches::generate(&layout));
+    }
+    if arguments.new_with {
+        additions.push(init::generate_with(&layout));
+    }
+    if arguments.builder {
+        additions.push(builder::generate(&layout));
+    }
+    if arguments.swap {
+        additions.push(mutation::generate_swap(&layout));
+    }
+    if arguments.fill {
+        additions.push(mutation::generate_fill(&layout));
+    }
+    if let Some(mapping) = &arguments.map {
+        additions.push(mapping::generate(&layout,mapping)?);
+    }
+    if let Some(function) = &arguments.example_with {
+        additions.push(init::generate_example(&layout,function));
+    }
+    if let Some(value) = &arguments.init {
+        additions.push(init::generate(&layout,value));
+    }
+    if arguments.key_digest {
+        additions.push(digest::generate(&layout));
+    }
+    if let Some(namespace) = &arguments.namespace {
+        additions.push(namespace::generate(&layout,namespace,arguments.start));
+    }
+    if arguments.get || (arguments.index && arguments.no_panic) {
+        additions.push(index::generate_get(&layout));
+    }
+    if arguments.heap_size {
+        additions.push(memory::generate(&layout));
+    }
+    if let Some(hot) = arguments.hot_cold {
+        additions.push(tiers::generate(&layout,hot));
+    }
+    if !arguments.identity.is_empty() {
+        additions.push(identity::generate(&layout,&arguments.identity));
+    }
+    if arguments.index && !arguments.no_panic {
+        additions.push(index::generate(&layout));
+    }
+    if arguments.index_map {
+        additions.push(indexed::generate(&layout));
+    }
+    if arguments.into_iter {
+        additions.push(iterators::generate_into_iter(&layout));
+    }
+    if arguments.iter {
+        additions.push(iterators::generate(&layout));
+    }
+    if arguments.iter_mut_indexed {
+        additions.push(iterators::generate_indexed(&layout));
+    }
+    if !arguments.named.is_empty() {
+        additions.push(named::generate(&layout,&arguments.named));
+    }
+    if let Some(feature) = &arguments.canonical_json {
+        additions.push(canonical::generate(&layout,feature.as_ref()));
+    }
+    let try_from_iter = arguments.try_from_iter || (arguments.from_iter && arguments.no_panic);
+    if try_from_iter || arguments.try_from_vec {
+        additions.push(length::generate_error(&layout));
+    }
+    if try_from_iter {
+        additions.push(length::generate(&layout));
+    }
+    if arguments.try_from_vec {
+        additions.push(length::generate_vec(&layout));
+    }
+    if arguments.from_iter && !arguments.no_panic {
+        additions.push(length::generate_from_iter(&layout));
+    }
+    if arguments.predicates {
+        additions.push(predicates::generate(&layout));
+    }
+    if arguments.len {
+        additions.push(len::generate(&layout));
+    }
+    if arguments.field_names {
+        additions.push(keys::generate_field_names(&layout));
+    }
+    if let Some(enumeration) = &arguments.key_enum {
+        let enumeration = enumeration.clone().unwrap_or_else(|| enumeration::default_name(&structure.ident));
+        additions.push(enumeration::generate(&layout,&enumeration));
+        if arguments.index_by_key {
+            additions.push(enumeration::generate_index(&layout,&enumeration));
+        }
+    }
+    if arguments.lossy {
+        additions.push(lossy::generate(&layout));
+    }
+    if let Some(buckets) = &arguments.time_buckets {
+        additions.push(buckets::generate(&layout,buckets));
+    }
+    if arguments.assemble {
+        additions.push(assemble::generate(&layout));
+    }
+    if arguments.default {
+        additions.push(defaults::generate(&layout));
+    }
+    if arguments.array {
+        additions.push(array::generate(&layout));
+    }
+    if arguments.tuple_from.is_some_and(|limit| arguments.field_count <= limit) {
+        additions.push(tuple::generate(&layout));
+    }
+    if let Some(function) = &arguments.validate_with {
+        let error = arguments.validation_error.clone().unwrap_or_else(|| syn::parse_quote!(::std::string::String));
+        additions.push(validate::generate(&layout,function,&error));
+    }
+    if let Some(value) = &arguments.const_default {
+        additions.push(constant::generate(&layout,value)?);
+    }
+    if let Some(scheme) = arguments.accessors {
+        additions.push(accessors::generate(&layout,scheme));
+    }
+    if let Some(groups) = arguments.split_keys {
+        additions.push(split::generate(&layout,groups));
+    }
+    if let Some(key) = &arguments.blob {
+        additions.push(blob::generate(&layout,key));
+    }
+    if let Some(encoder) = &arguments.key_encoder {
+        additions.push(encoder::generate(&layout,encoder,arguments.start));
+    }
+    let representation = if arguments.repr_c {
+        additions.push(slice::generate(&layout));
+        if arguments.deref {
+            additions.push(slice::generate_deref(&layout));
+        }
+        if let Some(groups) = arguments.split_keys {
+            additions.push(split::generate_chunks(&layout,groups));
+        }
+        quote! { #[repr(C)] }
+    } else {
+        quote! {}
+    };
+    if let Some(staging) = &arguments.staged {
+        additions.push(staged::generate(&layout,staging));
+    }
+    if !arguments.sections.is_empty() {
+        additions.push(sections::generate(&layout,&arguments.sections));
+    } else if !arguments.composed && !composing {
+        if let Some(primitive) = atomics::primitive(tipe) {
+            additions.push(atomics::generate(&layout,&primitive));
+        } else if let Some(kind) = numeric::kind(tipe) {
+            additions.push(numeric::generate(&layout,kind));
+        } else if let Some(object) = objects::trait_object(tipe) {
+            additions.push(objects::generate(&layout,object));
+        }
+    }
+    if let Some(implemented) = arguments.assert_send {
+        additions.push(threads::generate(&layout,"Send",implemented));
+    }
+    if let Some(implemented) = arguments.assert_sync {
+        additions.push(threads::generate(&layout,"Sync",implemented));
+    }
+    if arguments.assert_copy {
+        additions.push(assertions::generate(&layout,quote!(::std::marker::Copy)));
+    }
+    if arguments.assert_serialize_stable {
+        additions.push(assertions::generate(&layout,quote!(::serde::Serialize + ::serde::de::DeserializeOwned)));
+    }
+    let container_bound = match &arguments.serde_bound {
+        Some((bounds, _)) if serde_attributes => serde_attribute(arguments.serde_feature.as_ref(),quote! { serde(bound = #bounds) }),
+        _ => quote! {},
+    };
+    let container_attributes = &arguments.container_attributes;
+    let (allowed, additions) = match &arguments.allow {
+        Some(lints) => (quote! { #[allow(#(#lints),*)] },generated::mark(quote! { #(#additions)* },lints)?),
+        None => (quote! {},quote! { #(#additions)* }),
+    };
+    Ok(quote! {
+        #(#attributes)*
+        #(#container_attributes)*
+        #allowed
+        #container_bound
+        #representation
+        #visibility struct #name #generics {
+            #(#fields),*
+        }
+        #additions
+    })
+}
+/// Fails compilation unless the `KEY_DIGEST` of a psuedo-array equals a pinned value
+///
+/// Written as `assert_key_digest!(Type, [u8; 32])`, where `Type` was generated with the `key_digest` option of [`macro@faux_array`]. Pinning the digest turns any change to the keys, such as a new
+/// version of the key encoding or a changed option, into a build error instead of a silently broken mapping to stored documents.
+/// ```
+/// # use structurray::{faux_array,assert_key_digest};
+/// # use serde::Serialize;
+/// #[faux_array(u8,0,key_digest)]
+/// #[derive(Serialize)]
+/// struct Empty {}
+///
+/// assert_key_digest!(Empty,[
+///     0xe3,0xb0,0xc4,0x42,0x98,0xfc,0x1c,0x14,0x9a,0xfb,0xf4,0xc8,0x99,0x6f,0xb9,0x24,
+///     0x27,0xae,0x41,0xe4,0x64,0x9b,0x93,0x4c,0xa4,0x95,0x99,0x1b,0x78,0x52,0xb8,0x55,
+/// ]);
+/// ```
+/// # Errors
+/// Fails compilation if the arguments can't be parsed as a type followed by an expression.
+#[proc_macro]
+pub fn assert_key_digest(input: TokenStream) -> TokenStream {
+    match parse::<digest::Pinned>(input) {
+        Ok(pinned) => digest::assert(&pinned).into(),
+        Err(problem) => syn::Error::new(problem.span(),format!("assert_key_digest! expects a type and a [u8; 32] digest. {}",problem)).to_compile_error().into(),
+    }
+}
+/// Fails compilation unless every listed psuedo-array belongs to a key namespace, and no two of them have overlapping index ranges
+///
+/// Written as `assert_namespace!(Namespace: Member, ...)`, where each `Member` was generated with `namespace = Namespace` by [`macro@faux_array`]. Checking every member of a namespace in one place turns
+/// a change to the `start` or slot count of one member that would make it write another member's keys into a build error, instead of documents where one member silently overwrites another.
+/// ```
+/// # use structurray::{faux_array,assert_namespace};
+/// # use serde::Serialize;
+/// struct Settings;
+///
+/// #[faux_array(bool,10,namespace = Settings)]
+/// #[derive(Serialize)]
+/// struct Flags {}
+///
+/// #[faux_array(u16,0,start = 5,namespace = Settings)]
+/// #[derive(Serialize)]
+/// struct Unused {}
+///
+/// assert_namespace!(Settings: Flags, Unused);
+/// ```
+/// # Errors
+/// Fails compilation if the arguments can't be parsed as a type followed by a colon and a list of types, or if a listed type wasn't generated with `namespace` set to that type.
+#[proc_macro]
+pub fn assert_namespace(input: TokenStream) -> TokenStream {
+    match parse::<namespace::Members>(input) {
+        Ok(members) => namespace::assert(&members).into(),
+        Err(problem) => syn::Error::new(problem.span(),format!("assert_namespace! expects a namespace type, a colon, and a list of member types. {}",problem)).to_compile_error().into(),
+    }
+}
@@ -0,0 +1,90 @@
+/*
+Copyright 2024 Benjamin Richcreek
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+//! Generation of a deserialization entry point that recovers from malformed slots
+use proc_macro2::TokenStream;
+use quote::{quote,format_ident};
+use syn::Ident;
+use crate::{Layout,element_bound};
+/// Names the error type generated for the [`struct`](https://doc.rust-lang.org/1.58.1/std/keyword.struct.html) named `name`
+pub(crate) fn type_name(name: &Ident) -> Ident {
+    format_ident!("{}SlotError",name)
+}
+/// Creates the slot error type and the `from_value_lossy` associated function
+pub(crate) fn generate(layout: &Layout) -> TokenStream {
+    let name = &layout.structure.ident;
+    let visibility = &layout.structure.vis;
+    let (impl_generics, type_generics, where_clause) = layout.structure.generics.split_for_impl();
+    let error = type_name(name);
+    let tipe = layout.field_type;
+    let idents = layout.idents;
+    let names = layout.names;
+    let extra_fields = layout.extra_fields;
+    let indices: Vec<u32> = (0..idents.len() as u32).collect();
+    let aliases = layout.aliases.iter().map(|aliases| quote! { #(| #aliases)* });
+    let from_value_lossy = element_bound(tipe,quote!(::std::default::Default)).map(|default_bound| quote! {
+        /// Reads each slot from the entry with its key in `entries`, deserializing every value separately so that a malformed value only affects its own slot
+        ///
+        /// Slots whose value is missing or can't be deserialized are left with their default value, and are reported in index order alongside the result. When several entries have the same key, the
+        /// last of them is kept. Entries with keys that don't belong to any slot are ignored.
+        pub fn from_value_lossy<'__de, __I, __K, __V>(entries: __I) -> (Self,::std::vec::Vec<#error>) #default_bound, __I: ::std::iter::IntoIterator<Item = (__K,__V)>, __K: ::std::convert::AsRef<str>, __V: ::serde::Deserializer<'__de>, #tipe: ::serde::Deserialize<'__de> {
+            #(let mut #idents: ::std::option::Option<::std::result::Result<#tipe,::std::string::String>> = ::std::option::Option::None;)*
+            for (key,value) in entries {
+                let value = <#tipe as ::serde::Deserialize>::deserialize(value).map_err(|error| ::std::string::ToString::to_string(&error));
+                match key.as_ref() {
+                    #(#names #aliases => #idents = ::std::option::Option::Some(value),)*
+                    _ => {},
+                }
+            }
+            let mut errors = ::std::vec::Vec::new();
+            let value = #name {
+                #(#idents: match #idents {
+                    ::std::option::Option::Some(::std::result::Result::Ok(value)) => value,
+                    ::std::option::Option::Some(::std::result::Result::Err(message)) => {
+                        errors.push(#error { index: #indices, key: #names, message });
+                        ::std::default::Default::default()
+                    },
+                    ::std::option::Option::None => {
+                        errors.push(#error { index: #indices, key: #names, message: ::std::string::String::from("missing key") });
+                        ::std::default::Default::default()
+                    },
+                },)*
+                #(#extra_fields: ::std::default::Default::default(),)*
+            };
+            (value,errors)
+        }
+    });
+    quote! {
+        /// A slot that `from_value_lossy` couldn't read, and so left with its default value
+        #[derive(Clone,PartialEq,Eq,Hash,Debug)]
+        #visibility struct #error {
+            /// The index of the slot
+            pub index: u32,
+            /// The key of the slot
+            pub key: &'static str,
+            /// Why the slot couldn't be read
+            pub message: ::std::string::String,
+        }
+        impl ::std::fmt::Display for #error {
+            fn fmt(&self, formatter: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                ::std::write!(formatter,"slot {} (key \"{}\"): {}",self.index,self.key,self.message)
+            }
+        }
+        impl ::std::error::Error for #error {}
+        impl #impl_generics #name #type_generics #where_clause {
+            #from_value_lossy
+        }
+    }
+}
@@ -0,0 +1,113 @@
+/*
+Copyright 2024 Benjamin Richcreek
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+//! Generation of `map`, which transforms every slot into a psuedo-array of another element type
+use proc_macro2::{TokenStream,Span};
+use quote::quote;
+use syn::{Type,Token,Ident,GenericParam,WherePredicate};
+use syn::parse::{Parse,ParseStream};
+use crate::Layout;
+/// The psuedo-array that `map` creates
+pub(crate) enum Mapping {
+    /// Your [`struct`](https://doc.rust-lang.org/1.58.1/std/keyword.struct.html) itself, with the generic parameter that is the element type replaced, chosen by giving the option without a target
+    Generic(Span),
+    /// Another [`struct`](https://doc.rust-lang.org/1.58.1/std/keyword.struct.html) with the same fields, holding `element` in every slot, written as `Target: Element`
+    Target {
+        target: Box<Type>,
+        element: Box<Type>,
+    },
+}
+impl Parse for Mapping {
+    fn parse(input: ParseStream) -> Result<Self,syn::Error> {
+        let target = Box::new(input.parse()?);
+        input.parse::<Token![:]>()?;
+        let element = Box::new(input.parse()?);
+        Ok(Mapping::Target {
+            target,
+            element,
+        })
+    }
+}
+/// Creates the `map` method
+///
+/// # Errors
+/// Returns an error if no target was given and the element type isn't one of the generic parameters of the struct
+pub(crate) fn generate(layout: &Layout, mapping: &Mapping) -> Result<TokenStream,syn::Error> {
+    let name = &layout.structure.ident;
+    let generics = &layout.structure.generics;
+    let (impl_generics, type_generics, where_clause) = generics.split_for_impl();
+    let tipe = layout.field_type;
+    let idents = layout.idents;
+    let extra_fields = layout.extra_fields;
+    let method = match mapping {
+        Mapping::Target { target, element } => {
+            let doc = format!("Moves the result of calling `f` on each slot into the slot with the same index of a `{}`, calling `f` once per slot in index order",quote!(#target));
+            quote! {
+                #[doc = #doc]
+                pub fn map<F: ::std::ops::FnMut(#tipe) -> #element>(self, mut f: F) -> #target {
+                    #target {
+                        #(#idents: f(self.#idents),)*
+                    }
+                }
+            }
+        },
+        Mapping::Generic(span) => {
+            let Some(parameter) = generics.type_params().find(|parameter| matches!(tipe,Type::Path(path) if path.qself.is_none() && path.path.is_ident(&parameter.ident))) else {
+                return Err(syn::Error::new(*span,format!("the `map` option needs a target, written as `map(Target: Element)`, unless the element type is a generic parameter of {}",name)));
+            };
+            let mapped = Ident::new("FauxMapped",Span::call_site());
+            let arguments = generics.params.iter().map(|generic| match generic {
+                GenericParam::Type(other) if other.ident == parameter.ident => quote!(#mapped),
+                GenericParam::Type(other) => {
+                    let ident = &other.ident;
+                    quote!(#ident)
+                },
+                GenericParam::Lifetime(lifetime) => {
+                    let lifetime = &lifetime.lifetime;
+                    quote!(#lifetime)
+                },
+                GenericParam::Const(constant) => {
+                    let ident = &constant.ident;
+                    quote!(#ident)
+                },
+            });
+            let inline_bounds = &parameter.bounds;
+            let colon = (!inline_bounds.is_empty()).then(|| quote!(:));
+            let where_bounds = where_clause.iter().flat_map(|clause| &clause.predicates).filter_map(|predicate| match predicate {
+                WherePredicate::Type(bounded) if matches!(&bounded.bounded_ty,Type::Path(path) if path.qself.is_none() && path.path.is_ident(&parameter.ident)) => {
+                    let bounds = &bounded.bounds;
+                    Some(quote! { #mapped: #bounds })
+                },
+                _ => None,
+            });
+            quote! {
+                /// Moves the result of calling `f` on each slot into the slot with the same index of a psuedo-array holding the results, calling `f` once per slot in index order
+                ///
+                /// Fields besides the slots are moved unchanged.
+                pub fn map<#mapped #colon #inline_bounds, F: ::std::ops::FnMut(#tipe) -> #mapped>(self, mut f: F) -> #name<#(#arguments),*> where #(#where_bounds,)* {
+                    #name {
+                        #(#idents: f(self.#idents),)*
+                        #(#extra_fields: self.#extra_fields,)*
+                    }
+                }
+            }
+        },
+    };
+    Ok(quote! {
+        impl #impl_generics #name #type_generics #where_clause {
+            #method
+        }
+    })
+}
@@ -0,0 +1,40 @@
+/*
+Copyright 2024 Benjamin Richcreek
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+//! Generation of memory usage estimates
+use proc_macro2::TokenStream;
+use quote::quote;
+use crate::Layout;
+/// Creates the `INLINE_SIZE` constant and `heap_size_estimate`
+pub(crate) fn generate(layout: &Layout) -> TokenStream {
+    let name = &layout.structure.ident;
+    let (impl_generics, type_generics, where_clause) = layout.structure.generics.split_for_impl();
+    let tipe = layout.field_type;
+    let idents = layout.idents;
+    quote! {
+        impl #impl_generics #name #type_generics #where_clause {
+            /// The number of bytes an instance takes up itself, not counting memory its slots own elsewhere, such as the contents of a [`String`]
+            pub const INLINE_SIZE: usize = ::std::mem::size_of::<Self>();
+            /// Adds up `per_elem` for every slot, in index order, which should estimate the bytes each slot owns outside of the instance
+            ///
+            /// Adding [`INLINE_SIZE`](Self::INLINE_SIZE) gives an estimate of the total memory used by an instance.
+            pub fn heap_size_estimate(&self, per_elem: impl ::std::ops::Fn(&#tipe) -> usize) -> usize {
+                let mut total: usize = 0;
+                #(total += per_elem(&self.#idents);)*
+                total
+            }
+        }
+    }
+}
@@ -0,0 +1,81 @@
+/*
+Copyright 2024 Benjamin Richcreek
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+//! Generation of array-style mutations across slots
+use proc_macro2::TokenStream;
+use quote::quote;
+use crate::{Layout,element_bound};
+/// Creates `swap`, which exchanges the values of two slots chosen by index
+pub(crate) fn generate_swap(layout: &Layout) -> TokenStream {
+    let name = &layout.structure.ident;
+    let (impl_generics, type_generics, where_clause) = layout.structure.generics.split_for_impl();
+    let tipe = layout.field_type;
+    let idents = layout.idents;
+    let count = idents.len();
+    let exchange = quote! {
+        if a != b {
+            let mut slots: [&mut #tipe; #count] = [#(&mut self.#idents),*];
+            let (low, high) = (a.min(b),a.max(b));
+            let (before, after) = slots.split_at_mut(high);
+            ::std::mem::swap(&mut *before[low],&mut *after[0]);
+        }
+    };
+    if layout.no_panic {
+        return quote! {
+            impl #impl_generics #name #type_generics #where_clause {
+                /// Exchanges the values of the slots at `a` and `b`
+                ///
+                /// Returns `None`, leaving every slot untouched, if either index is out of range.
+                pub fn swap(&mut self, a: usize, b: usize) -> ::std::option::Option<()> {
+                    if a >= #count || b >= #count {
+                        return ::std::option::Option::None;
+                    }
+                    #exchange
+                    ::std::option::Option::Some(())
+                }
+            }
+        };
+    }
+    quote! {
+        impl #impl_generics #name #type_generics #where_clause {
+            /// Exchanges the values of the slots at `a` and `b`
+            ///
+            /// # Panics
+            /// Panics if either index is out of range
+            pub fn swap(&mut self, a: usize, b: usize) {
+                assert!(a < #count && b < #count,"indices {} and {} aren't both in range for a psuedo-array with {} slots",a,b,#count);
+                #exchange
+            }
+        }
+    }
+}
+/// Creates `fill`, which puts a clone of one value in every slot
+pub(crate) fn generate_fill(layout: &Layout) -> TokenStream {
+    let name = &layout.structure.ident;
+    let (impl_generics, type_generics, where_clause) = layout.structure.generics.split_for_impl();
+    let tipe = layout.field_type;
+    let idents = layout.idents;
+    let fill = element_bound(tipe,quote!(::std::clone::Clone)).map(|clone_bound| quote! {
+        /// Puts a clone of `value` in every slot
+        pub fn fill(&mut self, value: #tipe) #clone_bound {
+            #(self.#idents = ::std::clone::Clone::clone(&value);)*
+        }
+    });
+    quote! {
+        impl #impl_generics #name #type_generics #where_clause {
+            #fill
+        }
+    }
+}
@@ -0,0 +1,88 @@
+/*
+Copyright 2024 Benjamin Richcreek
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+//! Generation of accessors for a few slots given names of their own
+use proc_macro2::TokenStream;
+use quote::{quote,format_ident};
+use syn::{Ident,LitInt,Token};
+use syn::parse::{Parse,ParseStream};
+use crate::Layout;
+/// One entry of the `named` option, written as `name = index`
+pub(crate) struct Named {
+    name: Ident,
+    index: LitInt,
+}
+impl Parse for Named {
+    fn parse(input: ParseStream) -> Result<Self,syn::Error> {
+        let name: Ident = input.parse()?;
+        input.parse::<Token![=]>()?;
+        let index: LitInt = input.parse()?;
+        Ok(Named {
+            name,
+            index,
+        })
+    }
+}
+/// Rejects names given to indices past the last of `count` slots, and names used more than once
+pub(crate) fn validate(named: &[Named], count: u32) -> Result<(),syn::Error> {
+    let mut looper = 0;
+    while looper < named.len() {
+        let entry = &named[looper];
+        if entry.index.base10_parse::<u32>()? >= count {
+            return Err(syn::Error::new(entry.index.span(),format!("there is no slot at index {}, since there are only {} slots",entry.index,count)));
+        }
+        if named[..looper].iter().any(|other| other.name == entry.name) {
+            return Err(syn::Error::new(entry.name.span(),format!("the name `{}` is given to more than one slot",entry.name)));
+        }
+        looper += 1;
+    }
+    Ok(())
+}
+/// Creates `name`, `name_mut`, and `set_name` for each named slot
+pub(crate) fn generate(layout: &Layout, named: &[Named]) -> TokenStream {
+    let name = &layout.structure.ident;
+    let (impl_generics, type_generics, where_clause) = layout.structure.generics.split_for_impl();
+    let tipe = layout.field_type;
+    let accessors = named.iter().map(|entry| {
+        let getter = &entry.name;
+        let mutable_getter = format_ident!("{}_mut",entry.name);
+        let setter = format_ident!("set_{}",entry.name);
+        let index: usize = entry.index.base10_parse().expect("The indices of named slots were already parsed successfully");
+        let slot = &layout.idents[index];
+        let key = &layout.names[index];
+        let documentation = format!("Borrows the `{}` slot, which is the slot at index {} with the key `{}`",getter,index,key);
+        let mutable_documentation = format!("Mutably borrows the `{}` slot, which is the slot at index {} with the key `{}`",getter,index,key);
+        let setter_documentation = format!("Replaces the value of the `{}` slot, which is the slot at index {} with the key `{}`, returning the old value",getter,index,key);
+        quote! {
+            #[doc = #documentation]
+            pub fn #getter(&self) -> &#tipe {
+                &self.#slot
+            }
+            #[doc = #mutable_documentation]
+            pub fn #mutable_getter(&mut self) -> &mut #tipe {
+                &mut self.#slot
+            }
+            #[doc = #setter_documentation]
+            pub fn #setter(&mut self, value: #tipe) -> #tipe {
+                ::std::mem::replace(&mut self.#slot,value)
+            }
+        }
+    });
+    quote! {
+        impl #impl_generics #name #type_generics #where_clause {
+            #(#accessors)*
+        }
+    }
+}
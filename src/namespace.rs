@@ -0,0 +1,74 @@
+/*
+Copyright 2024 Benjamin Richcreek
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+//! Generation of key namespace membership and of assertions that members don't overlap
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Type,Token};
+use syn::punctuated::Punctuated;
+use syn::parse::{Parse,ParseStream};
+use crate::Layout;
+/// The arguments of `assert_namespace!`, written as `Namespace: Member, ...`
+pub(crate) struct Members {
+    namespace: Type,
+    members: Vec<Type>,
+}
+impl Parse for Members {
+    fn parse(input: ParseStream) -> Result<Self,syn::Error> {
+        let namespace: Type = input.parse()?;
+        input.parse::<Token![:]>()?;
+        let members = Punctuated::<Type,Token![,]>::parse_terminated(input)?;
+        Ok(Members {
+            namespace,
+            members: members.into_iter().collect(),
+        })
+    }
+}
+/// Creates the implementation of `NamespaceMember` that places the slots, starting from index `start`, in `namespace`
+pub(crate) fn generate(layout: &Layout, namespace: &Type, start: u32) -> TokenStream {
+    let name = &layout.structure.ident;
+    let (impl_generics, type_generics, where_clause) = layout.structure.generics.split_for_impl();
+    let count = layout.idents.len() as u32;
+    quote! {
+        impl #impl_generics ::structurray_keys::NamespaceMember<#namespace> for #name #type_generics #where_clause {
+            const FIRST_INDEX: u32 = #start;
+            const SLOTS: u32 = #count;
+        }
+    }
+}
+/// Creates a constant item that fails to compile unless every member belongs to the namespace, and no two members have overlapping index ranges
+pub(crate) fn assert(members: &Members) -> TokenStream {
+    let namespace = &members.namespace;
+    let member = |tipe: &Type| quote! { <#tipe as ::structurray_keys::NamespaceMember<#namespace>> };
+    let memberships = members.members.iter().map(member);
+    let checks = members.members.iter().enumerate().flat_map(|(position, first)| members.members[position + 1..].iter().map(move |second| (first,second))).map(|(first, second)| {
+        let message = format!("the index ranges of {} and {} overlap in the key namespace {}",quote!(#first),quote!(#second),quote!(#namespace));
+        let (first, second) = (member(first),member(second));
+        quote! {
+            assert!(
+                #first::SLOTS == 0 || #second::SLOTS == 0
+                    || #first::FIRST_INDEX + (#first::SLOTS - 1) < #second::FIRST_INDEX
+                    || #second::FIRST_INDEX + (#second::SLOTS - 1) < #first::FIRST_INDEX,
+                #message
+            );
+        }
+    });
+    quote! {
+        const _: () = {
+            #(let _: u32 = #memberships::SLOTS;)*
+            #(#checks)*
+        };
+    }
+}
@@ -0,0 +1,95 @@
+/*
+Copyright 2024 Benjamin Richcreek
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+//! Generation of bulk arithmetic helpers for primitive numeric element types
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::Type;
+use crate::Layout;
+/// The kinds of primitive numbers that get bulk helpers
+#[derive(Clone,Copy,PartialEq)]
+pub(crate) enum Numeric {
+    /// One of the primitive integer types, which get saturating arithmetic
+    Integer,
+    /// [`f32`] or [`f64`]
+    Float,
+}
+/// Finds whether `tipe` is written as a primitive integer or floating-point type
+pub(crate) fn kind(tipe: &Type) -> Option<Numeric> {
+    let Type::Path(path) = tipe else {
+        return None;
+    };
+    match path.path.get_ident()?.to_string().as_str() {
+        "i8" | "i16" | "i32" | "i64" | "i128" | "isize" | "u8" | "u16" | "u32" | "u64" | "u128" | "usize" => Some(Numeric::Integer),
+        "f32" | "f64" => Some(Numeric::Float),
+        _ => None,
+    }
+}
+/// Creates `clamp_all`, along with `saturating_add_all`, `saturating_sub_all`, and `saturating_mul_all` if the slots hold integers
+pub(crate) fn generate(layout: &Layout, kind: Numeric) -> TokenStream {
+    let name = &layout.structure.ident;
+    let (impl_generics, type_generics, where_clause) = layout.structure.generics.split_for_impl();
+    let tipe = layout.field_type;
+    let idents = layout.idents;
+    let saturating = if kind == Numeric::Integer {
+        quote! {
+            /// Adds `value` to every slot, stopping at the numeric bounds of the element type instead of overflowing
+            pub fn saturating_add_all(&mut self, value: #tipe) {
+                #(self.#idents = self.#idents.saturating_add(value);)*
+            }
+            /// Subtracts `value` from every slot, stopping at the numeric bounds of the element type instead of overflowing
+            pub fn saturating_sub_all(&mut self, value: #tipe) {
+                #(self.#idents = self.#idents.saturating_sub(value);)*
+            }
+            /// Multiplies every slot by `value`, stopping at the numeric bounds of the element type instead of overflowing
+            pub fn saturating_mul_all(&mut self, value: #tipe) {
+                #(self.#idents = self.#idents.saturating_mul(value);)*
+            }
+        }
+    } else {
+        quote! {}
+    };
+    let clamp = if layout.no_panic {
+        quote! {
+            /// Restricts every slot to the range from `min` to `max`, inclusive
+            ///
+            /// Returns `None`, leaving every slot untouched, if `min` is greater than `max`, or if either is NaN.
+            pub fn clamp_all(&mut self, min: #tipe, max: #tipe) -> ::std::option::Option<()> {
+                if min <= max {
+                    #(self.#idents = self.#idents.clamp(min,max);)*
+                    ::std::option::Option::Some(())
+                } else {
+                    ::std::option::Option::None
+                }
+            }
+        }
+    } else {
+        quote! {
+            /// Restricts every slot to the range from `min` to `max`, inclusive
+            ///
+            /// # Panics
+            /// Panics if `min` is greater than `max`, or if either is NaN
+            pub fn clamp_all(&mut self, min: #tipe, max: #tipe) {
+                #(self.#idents = self.#idents.clamp(min,max);)*
+            }
+        }
+    };
+    quote! {
+        impl #impl_generics #name #type_generics #where_clause {
+            #clamp
+            #saturating
+        }
+    }
+}
@@ -0,0 +1,64 @@
+/*
+Copyright 2024 Benjamin Richcreek
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+//! Generation of helpers for boxed trait-object element types
+use proc_macro2::{TokenStream,Span};
+use quote::quote;
+use syn::{Type,TypeTraitObject,GenericArgument,PathArguments,Lifetime};
+use crate::Layout;
+/// Finds the trait object inside `tipe` if it is written as `Box<dyn Trait>`, which may include other bounds such as `Send`
+pub(crate) fn trait_object(tipe: &Type) -> Option<&TypeTraitObject> {
+    let Type::Path(path) = tipe else {
+        return None;
+    };
+    let segment = path.path.segments.last().filter(|segment| segment.ident == "Box")?;
+    let PathArguments::AngleBracketed(arguments) = &segment.arguments else {
+        return None;
+    };
+    match arguments.args.first() {
+        Some(GenericArgument::Type(Type::TraitObject(object))) if arguments.args.len() == 1 => Some(object),
+        _ => None,
+    }
+}
+/// Creates `as_dyn`, `as_dyn_mut`, and `iter_dyn`, which borrow the trait objects behind the boxes in the slots
+pub(crate) fn generate(layout: &Layout, object: &TypeTraitObject) -> TokenStream {
+    let name = &layout.structure.ident;
+    let (impl_generics, type_generics, where_clause) = layout.structure.generics.split_for_impl();
+    let idents = layout.idents;
+    let indices: Vec<u32> = (0..idents.len() as u32).collect();
+    let lifetime = Lifetime::new("'faux_iter",Span::call_site());
+    quote! {
+        impl #impl_generics #name #type_generics #where_clause {
+            /// Borrows the trait object in the slot at `index`, or returns `None` if `index` is out of range
+            pub fn as_dyn(&self, index: u32) -> ::std::option::Option<&(#object)> {
+                match index {
+                    #(#indices => ::std::option::Option::Some(&*self.#idents),)*
+                    _ => ::std::option::Option::None,
+                }
+            }
+            /// Mutably borrows the trait object in the slot at `index`, or returns `None` if `index` is out of range
+            pub fn as_dyn_mut(&mut self, index: u32) -> ::std::option::Option<&mut (#object)> {
+                match index {
+                    #(#indices => ::std::option::Option::Some(&mut *self.#idents),)*
+                    _ => ::std::option::Option::None,
+                }
+            }
+            /// Borrows the trait object in every slot, in index order
+            pub fn iter_dyn<#lifetime>(&#lifetime self) -> impl ::std::iter::DoubleEndedIterator<Item = &#lifetime (#object)> + ::std::iter::ExactSizeIterator {
+                ::std::vec![#(&*self.#idents as &#lifetime (#object)),*].into_iter()
+            }
+        }
+    }
+}
@@ -0,0 +1,47 @@
+/*
+Copyright 2024 Benjamin Richcreek
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+//! Generation of predicates checked against every slot
+use proc_macro2::TokenStream;
+use quote::quote;
+use crate::{Layout,element_bound};
+/// Creates the `all`, `any`, and `is_uniform` methods, which stop at the first slot that decides the result
+pub(crate) fn generate(layout: &Layout) -> TokenStream {
+    let name = &layout.structure.ident;
+    let (impl_generics, type_generics, where_clause) = layout.structure.generics.split_for_impl();
+    let tipe = layout.field_type;
+    let idents = layout.idents;
+    let rest = idents.iter().skip(1);
+    let first = idents.iter().take(1).cycle().take(idents.len().saturating_sub(1));
+    let is_uniform = element_bound(tipe,quote!(::std::cmp::PartialEq)).map(|equality_bound| quote! {
+        /// Returns whether every slot holds the same value, stopping at the first slot that differs from the first one
+        pub fn is_uniform(&self) -> bool #equality_bound {
+            true #(&& ::std::cmp::PartialEq::eq(&self.#first,&self.#rest))*
+        }
+    });
+    quote! {
+        impl #impl_generics #name #type_generics #where_clause {
+            /// Returns whether `predicate` holds for every slot, checking them in index order and stopping at the first slot it rejects
+            pub fn all(&self, mut predicate: impl ::std::ops::FnMut(&#tipe) -> bool) -> bool {
+                true #(&& predicate(&self.#idents))*
+            }
+            /// Returns whether `predicate` holds for any slot, checking them in index order and stopping at the first slot it accepts
+            pub fn any(&self, mut predicate: impl ::std::ops::FnMut(&#tipe) -> bool) -> bool {
+                false #(|| predicate(&self.#idents))*
+            }
+            #is_uniform
+        }
+    }
+}
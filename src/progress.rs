@@ -0,0 +1,87 @@
+/*
+Copyright 2024 Benjamin Richcreek
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+//! Generation of a deserialization entry point that reports its progress
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::parse_quote;
+use crate::{Layout,with_lifetime,deserialize};
+/// Creates the `deserialize_with_progress` associated function and the visitor it uses
+pub(crate) fn generate(layout: &Layout) -> TokenStream {
+    let name = &layout.structure.ident;
+    let generics = &layout.structure.generics;
+    let (impl_generics, type_generics, where_clause) = generics.split_for_impl();
+    let tipe = layout.field_type;
+    let visitor_generics = with_lifetime(generics,"'faux_progress");
+    let (_, visitor_type_generics, visitor_where_clause) = visitor_generics.split_for_impl();
+    let deserializable = layout.with_serde_bound(&deserialize::with_carried_bound(layout,&with_lifetime(&visitor_generics,"'__de"),&quote!(::serde::Deserialize<'__de>)),parse_quote!(#tipe: ::serde::Deserialize<'__de>));
+    let (deserialize_impl_generics, _, deserialize_where_clause) = deserializable.split_for_impl();
+    let locals = deserialize::slot_locals(layout);
+    let carried_locals = deserialize::carried_locals(layout);
+    let carried_arms = deserialize::carried_arms(layout);
+    let after_slot = quote! {
+        read += 1;
+        if read % self.every == 0 && !(self.progress)(index,key) {
+            return ::std::result::Result::Err(<__A::Error as ::serde::de::Error>::custom("deserialization was cancelled by the progress callback"));
+        }
+    };
+    let slot_lookup = deserialize::slot_lookup(layout,&after_slot);
+    let slot_arms = deserialize::slot_arms(layout,&after_slot);
+    let ignore_arm = deserialize::ignore_arm();
+    let assembled = deserialize::assemble(layout);
+    let carried_bounds = deserialize::carried_predicates(layout,&quote!(::serde::Deserialize<'__de>));
+    let expecting = format!("a map containing every key of {}",name);
+    quote! {
+        const _: () = {
+            struct __FauxProgressVisitor #visitor_generics #visitor_where_clause {
+                progress: &'faux_progress mut dyn ::std::ops::FnMut(u32,&str) -> bool,
+                every: usize,
+                marker: ::std::marker::PhantomData<fn() -> #name #type_generics>,
+            }
+            impl #deserialize_impl_generics ::serde::de::Visitor<'__de> for __FauxProgressVisitor #visitor_type_generics #deserialize_where_clause {
+                type Value = #name #type_generics;
+                fn expecting(&self, formatter: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+                    formatter.write_str(#expecting)
+                }
+                fn visit_map<__A: ::serde::de::MapAccess<'__de>>(self, mut map: __A) -> ::std::result::Result<Self::Value,__A::Error> {
+                    #locals
+                    #carried_locals
+                    let mut read: usize = 0;
+                    while let ::std::option::Option::Some(key) = map.next_key::<::std::string::String>()? {
+                        #slot_lookup
+                        match key.as_str() {
+                            #slot_arms
+                            #carried_arms
+                            #ignore_arm
+                        }
+                    }
+                    ::std::result::Result::Ok(#assembled)
+                }
+            }
+            impl #impl_generics #name #type_generics #where_clause {
+                /// Deserializes an instance like a derived [`Deserialize`](::serde::Deserialize) implementation would, calling `progress` with the index and key of every `every`th slot read
+                ///
+                /// If `progress` returns `false`, deserialization stops and an error is returned, which allows long-running reads to be cancelled. An `every` of `0` is treated as `1`.
+                pub fn deserialize_with_progress<'__de, __D: ::serde::Deserializer<'__de>>(deserializer: __D, every: usize, mut progress: impl ::std::ops::FnMut(u32,&str) -> bool) -> ::std::result::Result<Self,__D::Error> where #tipe: ::serde::Deserialize<'__de>, #(#carried_bounds),* {
+                    deserializer.deserialize_map(__FauxProgressVisitor {
+                        progress: &mut progress,
+                        every: every.max(1),
+                        marker: ::std::marker::PhantomData,
+                    })
+                }
+            }
+        };
+    }
+}
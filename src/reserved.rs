@@ -0,0 +1,39 @@
+/*
+Copyright 2024 Benjamin Richcreek
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+//! Checks that no generated key collides with a key owned by other code
+use syn::LitStr;
+use structurray_keys::decode_key;
+use crate::{Arguments,checksum,tombstones,encoding};
+/// Returns whether the attribute described by `arguments` writes `key` at the top level of the serialized map
+pub(crate) fn generates(arguments: &Arguments, key: &str) -> bool {
+    if let Some(groups) = arguments.split_keys {
+        return decode_key(key).is_some_and(|index| index < groups);
+    }
+    let slot = |key: &str| encoding::position(arguments,key).is_some();
+    slot(key)
+        || (arguments.timestamps && key.strip_suffix("_t").is_some_and(slot))
+        || (arguments.tombstones && key == tombstones::TOMBSTONE_KEY)
+        || (arguments.checksum && key == checksum::CHECKSUM_KEY)
+}
+/// Rejects every key in `reserved` that the attribute described by `arguments` would also generate
+pub(crate) fn check(arguments: &Arguments, reserved: &[LitStr]) -> Result<(),syn::Error> {
+    for key in reserved {
+        if generates(arguments,&key.value()) {
+            return Err(syn::Error::new(key.span(),format!("the reserved key \"{}\" collides with a generated key",key.value())));
+        }
+    }
+    Ok(())
+}
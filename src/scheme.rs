@@ -0,0 +1,38 @@
+/*
+Copyright 2024 Benjamin Richcreek
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+//! Checks that the key encoding still matches the scheme a psuedo-array was pinned to
+use syn::LitInt;
+use structurray_keys::{key_digest,encode_index};
+/// The number of keys hashed to fingerprint a scheme, which covers every key with one or two digits and the first keys with three
+const SAMPLE_SIZE: u32 = 4000;
+/// The fingerprint of each key scheme, in order starting from scheme `1`, along with the key of [`u32::MAX`] under that scheme
+const SCHEMES: [([u8; 32], &str); 1] = [
+    ([
+        0xc0, 0x18, 0x44, 0x27, 0x28, 0x8f, 0x51, 0xb3, 0x50, 0xb2, 0x98, 0x1d, 0x14, 0x79, 0x20, 0xfd,
+        0x54, 0xb5, 0x70, 0x71, 0xad, 0xae, 0xa3, 0x38, 0xcb, 0x0a, 0x0a, 0xc6, 0xaf, 0x26, 0xb1, 0x42,
+    ],"4GFfc3"),
+];
+/// Rejects `scheme` if it isn't a known scheme, or if the keys this crate generates no longer match the keys that scheme promised
+pub(crate) fn check(scheme: &LitInt) -> Result<(),syn::Error> {
+    let number: usize = scheme.base10_parse()?;
+    let Some((fingerprint, last)) = number.checked_sub(1).and_then(|index| SCHEMES.get(index)) else {
+        return Err(syn::Error::new(scheme.span(),format!("key scheme {} is unknown to this version of structurray, which supports schemes 1 through {}",number,SCHEMES.len())));
+    };
+    if key_digest(SAMPLE_SIZE) != *fingerprint || encode_index(u32::MAX) != *last {
+        return Err(syn::Error::new(scheme.span(),format!("this version of structurray no longer generates the keys of key scheme {}, so compiling would silently change the keys of stored documents",number)));
+    }
+    Ok(())
+}
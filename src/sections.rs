@@ -0,0 +1,101 @@
+/*
+Copyright 2024 Benjamin Richcreek
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+//! Generation of typed sections, which give a range of slots its own element type
+use proc_macro2::TokenStream;
+use quote::{quote,format_ident};
+use syn::{Type,Ident,LitInt,Token};
+use syn::parse::{Parse,ParseStream};
+use crate::Layout;
+/// One section of the `sections` option, written as `name: Type = start..end`
+pub(crate) struct Section {
+    name: Ident,
+    pub(crate) element: Type,
+    start: LitInt,
+    end: LitInt,
+}
+impl Parse for Section {
+    fn parse(input: ParseStream) -> Result<Self,syn::Error> {
+        let name: Ident = input.parse()?;
+        input.parse::<Token![:]>()?;
+        let element: Type = input.parse()?;
+        input.parse::<Token![=]>()?;
+        let start: LitInt = input.parse()?;
+        input.parse::<Token![..]>()?;
+        let end: LitInt = input.parse()?;
+        Ok(Section {
+            name,
+            element,
+            start,
+            end,
+        })
+    }
+}
+impl Section {
+    /// Returns the indices of the slots in this section
+    pub(crate) fn range(&self) -> std::ops::Range<u32> {
+        self.start.base10_parse().expect("The bounds of sections were already parsed successfully")..self.end.base10_parse().expect("The bounds of sections were already parsed successfully")
+    }
+}
+/// Rejects sections that are empty, extend past the last of `count` slots, or overlap each other
+pub(crate) fn validate(sections: &[Section], count: u32) -> Result<(),syn::Error> {
+    let mut looper = 0;
+    while looper < sections.len() {
+        let section = &sections[looper];
+        let start: u32 = section.start.base10_parse()?;
+        let end: u32 = section.end.base10_parse()?;
+        if start >= end || end > count {
+            return Err(syn::Error::new(section.name.span(),format!("the section `{}` should cover at least one slot and end at or before {}",section.name,count)));
+        }
+        for other in &sections[..looper] {
+            let range = other.range();
+            if start < range.end && range.start < end {
+                return Err(syn::Error::new(section.name.span(),format!("the section `{}` overlaps the section `{}`",section.name,other.name)));
+            }
+        }
+        looper += 1;
+    }
+    Ok(())
+}
+/// Creates a pair of accessors for each section, which borrow every slot in it at once
+pub(crate) fn generate(layout: &Layout, sections: &[Section]) -> TokenStream {
+    let name = &layout.structure.ident;
+    let (impl_generics, type_generics, where_clause) = layout.structure.generics.split_for_impl();
+    let accessors = sections.iter().map(|section| {
+        let getter = &section.name;
+        let mutable_getter = format_ident!("{}_mut",section.name);
+        let element = &section.element;
+        let range = section.range();
+        let length = range.len();
+        let idents = &layout.idents[range.start as usize..range.end as usize];
+        let documentation = format!("Borrows the slots of the `{}` section, which are the slots from index {} up to (but not including) index {}",getter,range.start,range.end);
+        let mutable_documentation = format!("Mutably borrows the slots of the `{}` section, which are the slots from index {} up to (but not including) index {}",getter,range.start,range.end);
+        quote! {
+            #[doc = #documentation]
+            pub fn #getter(&self) -> [&#element; #length] {
+                [#(&self.#idents),*]
+            }
+            #[doc = #mutable_documentation]
+            pub fn #mutable_getter(&mut self) -> [&mut #element; #length] {
+                [#(&mut self.#idents),*]
+            }
+        }
+    });
+    quote! {
+        impl #impl_generics #name #type_generics #where_clause {
+            #(#accessors)*
+        }
+    }
+}
@@ -0,0 +1,77 @@
+/*
+Copyright 2024 Benjamin Richcreek
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+//! Generation of runtime-only shadow fields that are never serialized
+use proc_macro2::TokenStream;
+use quote::{quote,format_ident};
+use syn::{Ident,Type};
+use crate::Layout;
+/// Names the shadow field of each slot by appending `_shadow` to the slot's identifier
+pub(crate) fn idents(slots: &[Ident]) -> Vec<Ident> {
+    slots.iter().map(|slot| format_ident!("{}_shadow",slot)).collect()
+}
+/// Creates the `shadow` and `shadow_mut` methods, which access the shadow field of a slot by index
+pub(crate) fn generate(layout: &Layout, shadow: &Type) -> TokenStream {
+    let name = &layout.structure.ident;
+    let (impl_generics, type_generics, where_clause) = layout.structure.generics.split_for_impl();
+    let shadows = idents(layout.idents);
+    let count = shadows.len();
+    let indices: Vec<u32> = (0..count as u32).collect();
+    let out_of_range = layout.out_of_range();
+    if layout.no_panic {
+        return quote! {
+            impl #impl_generics #name #type_generics #where_clause {
+                /// Borrows the runtime-only state kept alongside the slot at `index`, or returns `None` if `index` is out of range
+                pub fn shadow(&self, index: u32) -> ::std::option::Option<&#shadow> {
+                    match index {
+                        #(#indices => ::std::option::Option::Some(&self.#shadows),)*
+                        _ => ::std::option::Option::None,
+                    }
+                }
+                /// Mutably borrows the runtime-only state kept alongside the slot at `index`, or returns `None` if `index` is out of range
+                pub fn shadow_mut(&mut self, index: u32) -> ::std::option::Option<&mut #shadow> {
+                    match index {
+                        #(#indices => ::std::option::Option::Some(&mut self.#shadows),)*
+                        _ => ::std::option::Option::None,
+                    }
+                }
+            }
+        };
+    }
+    quote! {
+        impl #impl_generics #name #type_generics #where_clause {
+            /// Borrows the runtime-only state kept alongside the slot at `index`
+            ///
+            /// # Panics
+            /// Panics if `index` is out of range
+            pub fn shadow(&self, index: u32) -> &#shadow {
+                match index {
+                    #(#indices => &self.#shadows,)*
+                    _ => #out_of_range,
+                }
+            }
+            /// Mutably borrows the runtime-only state kept alongside the slot at `index`
+            ///
+            /// # Panics
+            /// Panics if `index` is out of range
+            pub fn shadow_mut(&mut self, index: u32) -> &mut #shadow {
+                match index {
+                    #(#indices => &mut self.#shadows,)*
+                    _ => #out_of_range,
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,99 @@
+/*
+Copyright 2024 Benjamin Richcreek
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+//! Generation of slice and array conversions for the `repr(C)` layout
+use proc_macro2::TokenStream;
+use quote::quote;
+use crate::Layout;
+/// Creates an expression for a pointer to the first slot, or a dangling but aligned pointer if there are no slots
+///
+/// Pointing at the first slot rather than at the whole struct keeps the views correct when fields declared in the struct body come before the slots.
+fn first_slot(layout: &Layout, mutable: bool) -> TokenStream {
+    let tipe = layout.field_type;
+    match (layout.idents.first(), mutable) {
+        (Some(first), false) => quote! { ::std::ptr::addr_of!(self.#first) },
+        (Some(first), true) => quote! { ::std::ptr::addr_of_mut!(self.#first) },
+        (None, _) => quote! { ::std::ptr::NonNull::<#tipe>::dangling().as_ptr() },
+    }
+}
+/// Creates the [`AsRef`], [`AsMut`], [`Borrow`](std::borrow::Borrow), and [`BorrowMut`](std::borrow::BorrowMut) implementations that view every slot as one slice
+///
+/// These are only sound because the `repr_c` option keeps the slots next to each other, in index order, and every slot has the same type, so there is no padding between them.
+pub(crate) fn generate(layout: &Layout) -> TokenStream {
+    let first = first_slot(layout,false);
+    let first_mut = first_slot(layout,true);
+    let name = &layout.structure.ident;
+    let (impl_generics, type_generics, where_clause) = layout.structure.generics.split_for_impl();
+    let tipe = layout.field_type;
+    let count = layout.idents.len();
+    quote! {
+        impl #impl_generics ::std::convert::AsRef<[#tipe]> for #name #type_generics #where_clause {
+            fn as_ref(&self) -> &[#tipe] {
+                // SAFETY: the struct is repr(C) and declares its slots one after another, and they all have the same type, so they are laid out exactly like an array
+                unsafe {
+                    ::std::slice::from_raw_parts(#first as *const #tipe,#count)
+                }
+            }
+        }
+        impl #impl_generics ::std::convert::AsMut<[#tipe]> for #name #type_generics #where_clause {
+            fn as_mut(&mut self) -> &mut [#tipe] {
+                // SAFETY: see `as_ref`
+                unsafe {
+                    ::std::slice::from_raw_parts_mut(#first_mut as *mut #tipe,#count)
+                }
+            }
+        }
+        impl #impl_generics ::std::borrow::Borrow<[#tipe]> for #name #type_generics #where_clause {
+            fn borrow(&self) -> &[#tipe] {
+                ::std::convert::AsRef::as_ref(self)
+            }
+        }
+        impl #impl_generics ::std::borrow::BorrowMut<[#tipe]> for #name #type_generics #where_clause {
+            fn borrow_mut(&mut self) -> &mut [#tipe] {
+                ::std::convert::AsMut::as_mut(self)
+            }
+        }
+    }
+}
+/// Creates the [`Deref`](std::ops::Deref) and [`DerefMut`](std::ops::DerefMut) implementations that view every slot as one array
+///
+/// Like the slice conversions, these rely on the slots being laid out exactly like an array by the `repr_c` option.
+pub(crate) fn generate_deref(layout: &Layout) -> TokenStream {
+    let first = first_slot(layout,false);
+    let first_mut = first_slot(layout,true);
+    let name = &layout.structure.ident;
+    let (impl_generics, type_generics, where_clause) = layout.structure.generics.split_for_impl();
+    let tipe = layout.field_type;
+    let count = layout.idents.len();
+    quote! {
+        impl #impl_generics ::std::ops::Deref for #name #type_generics #where_clause {
+            type Target = [#tipe; #count];
+            fn deref(&self) -> &[#tipe; #count] {
+                // SAFETY: the struct is repr(C) and declares its slots one after another, and they all have the same type, so they are laid out exactly like an array
+                unsafe {
+                    &*(#first as *const [#tipe; #count])
+                }
+            }
+        }
+        impl #impl_generics ::std::ops::DerefMut for #name #type_generics #where_clause {
+            fn deref_mut(&mut self) -> &mut [#tipe; #count] {
+                // SAFETY: see `deref`
+                unsafe {
+                    &mut *(#first_mut as *mut [#tipe; #count])
+                }
+            }
+        }
+    }
+}
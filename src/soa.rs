@@ -0,0 +1,127 @@
+/*
+Copyright 2024 Benjamin Richcreek
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+//! Generation of struct-of-arrays companions
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::Ident;
+use crate::Layout;
+/// Creates the struct-of-arrays companion named `columns`, along with its methods and conversions
+pub(crate) fn generate(layout: &Layout, columns: &Ident) -> TokenStream {
+    let name = &layout.structure.ident;
+    let visibility = &layout.structure.vis;
+    let generics = &layout.structure.generics;
+    let (impl_generics, type_generics, where_clause) = generics.split_for_impl();
+    let tipe = layout.field_type;
+    let idents = layout.idents;
+    let count = idents.len();
+    let length = match idents.first() {
+        Some(first) => quote! { self.#first.len() },
+        None => quote! { 0 },
+    };
+    let failure_doc = layout.checked_doc("the columns don't all hold the same number of records");
+    let output = layout.checked_output(quote! { ::std::vec::Vec<#name #type_generics> });
+    let misaligned = if layout.no_panic {
+        quote! { return ::std::option::Option::None; }
+    } else {
+        quote! { panic!("every column of a struct-of-arrays companion should hold the same number of records"); }
+    };
+    let records = layout.checked_return(quote! { records });
+    // With no columns there are no records to reassemble, and the loop below would never end
+    let reassemble = if count == 0 {
+        quote! {}
+    } else {
+        quote! {
+            while let (#(::std::option::Option::Some(#idents),)*) = (#(#idents.next(),)*) {
+                records.push(#name {
+                    #(#idents),*
+                });
+            }
+        }
+    };
+    // A conversion can't report misaligned columns without panicking, so `into_records` is the only way back if the `no_panic` option was given
+    let into_records = if layout.no_panic {
+        quote! {}
+    } else {
+        quote! {
+            impl #impl_generics ::std::convert::From<#columns #type_generics> for ::std::vec::Vec<#name #type_generics> #where_clause {
+                fn from(columns: #columns #type_generics) -> Self {
+                    columns.into_records()
+                }
+            }
+        }
+    };
+    quote! {
+        /// A struct-of-arrays companion holding one column per slot
+        #visibility struct #columns #generics #where_clause {
+            #(pub #idents : ::std::vec::Vec<#tipe>),*
+        }
+        impl #impl_generics #columns #type_generics #where_clause {
+            /// Creates a companion holding no records
+            pub fn new() -> Self {
+                #columns {
+                    #(#idents : ::std::vec::Vec::new()),*
+                }
+            }
+            /// Returns the number of records stored in each column, which is the length of the first column
+            ///
+            /// Since the columns are public, they can be given different lengths, which `is_aligned` detects.
+            pub fn len(&self) -> usize {
+                #length
+            }
+            /// Returns `true` if no records are stored
+            pub fn is_empty(&self) -> bool {
+                self.len() == 0
+            }
+            /// Splits `record` into its slots and appends each slot to its column
+            pub fn push(&mut self, record: #name #type_generics) {
+                #(self.#idents.push(record.#idents);)*
+            }
+            /// Returns `true` if every column holds the same number of records
+            pub fn is_aligned(&self) -> bool {
+                let length = self.len();
+                let lengths: [usize; #count] = [#(self.#idents.len()),*];
+                lengths.iter().all(|column| *column == length)
+            }
+            /// Reassembles the columns into individual records, in the order they were pushed
+            #failure_doc
+            pub fn into_records(self) #output {
+                if !self.is_aligned() {
+                    #misaligned
+                }
+                let length = self.len();
+                let mut records = ::std::vec::Vec::with_capacity(length);
+                #(let mut #idents = self.#idents.into_iter();)*
+                #reassemble
+                #records
+            }
+        }
+        impl #impl_generics ::std::default::Default for #columns #type_generics #where_clause {
+            fn default() -> Self {
+                Self::new()
+            }
+        }
+        impl #impl_generics ::std::convert::From<::std::vec::Vec<#name #type_generics>> for #columns #type_generics #where_clause {
+            fn from(records: ::std::vec::Vec<#name #type_generics>) -> Self {
+                let mut columns = Self::new();
+                for record in records {
+                    columns.push(record);
+                }
+                columns
+            }
+        }
+        #into_records
+    }
+}
@@ -0,0 +1,206 @@
+/*
+Copyright 2024 Benjamin Richcreek
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+//! Generation of serialization that splits the slots across several top-level keys
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::parse_quote;
+use structurray_keys::encode_index;
+use crate::{Layout,with_lifetime};
+/// Creates [`Serialize`](https://docs.rs/serde/latest/serde/trait.Serialize.html) and [`Deserialize`](https://docs.rs/serde/latest/serde/trait.Deserialize.html) implementations that nest the slots under `groups` top-level keys
+///
+/// Slots are divided into contiguous groups whose sizes differ by at most one, and each group is serialized under the key of its own index.
+pub(crate) fn generate(layout: &Layout, groups: u32) -> TokenStream {
+    let name = &layout.structure.ident;
+    let generics = &layout.structure.generics;
+    let (_, type_generics, where_clause) = generics.split_for_impl();
+    let tipe = layout.field_type;
+    let idents = layout.idents;
+    let names = layout.names;
+    let extra_fields = layout.extra_fields;
+    let count = idents.len();
+    let group_count = groups as usize;
+    let group_keys: Vec<String> = (0..groups).map(encode_index).collect();
+    let group_indices: Vec<u32> = (0..groups).collect();
+    let group_bodies = (0..group_count).map(|group| {
+        let start = group * count / group_count;
+        let end = (group + 1) * count / group_count;
+        let group_idents = &idents[start..end];
+        let group_names = &names[start..end];
+        let length = end - start;
+        quote! {
+            let mut map = serializer.serialize_map(::std::option::Option::Some(#length))?;
+            #(map.serialize_entry(#group_names,&self.0.#group_idents)?;)*
+            map.end()
+        }
+    });
+    let group_generics = with_lifetime(generics,"'faux_group");
+    let (_, group_type_generics, group_where_clause) = group_generics.split_for_impl();
+    let serializable_group = layout.with_serde_bound(&group_generics,parse_quote!(#tipe: ::serde::Serialize));
+    let (group_impl_generics, _, group_serialize_where_clause) = serializable_group.split_for_impl();
+    let serializable = layout.with_serde_bound(generics,parse_quote!(#tipe: ::serde::Serialize));
+    let (serialize_impl_generics, _, serialize_where_clause) = serializable.split_for_impl();
+    let deserializable_group = layout.with_serde_bound(&with_lifetime(&group_generics,"'__de"),parse_quote!(#tipe: ::serde::Deserialize<'__de>));
+    let (seed_impl_generics, _, seed_where_clause) = deserializable_group.split_for_impl();
+    let deserializable = layout.with_serde_bound(&with_lifetime(generics,"'__de"),parse_quote!(#tipe: ::serde::Deserialize<'__de>));
+    let (deserialize_impl_generics, _, deserialize_where_clause) = deserializable.split_for_impl();
+    let expecting = format!("a map of {} groups of keys of {}",groups,name);
+    let serialize_body = layout.instrument("serialize",quote! { ::std::result::Result<__S::Ok,__S::Error> },quote! {
+        use ::serde::ser::SerializeMap;
+        let mut map = serializer.serialize_map(::std::option::Option::Some(#group_count))?;
+        #(map.serialize_entry(#group_keys,&FauxGroup(self,#group_indices))?;)*
+        map.end()
+    });
+    let deserialize_body = layout.instrument("deserialize",quote! { ::std::result::Result<Self,__D::Error> },quote! {
+        deserializer.deserialize_map(__FauxVisitor(::std::marker::PhantomData))
+    });
+    quote! {
+        const _: () = {
+            struct FauxGroup #group_generics (&'faux_group #name #type_generics,u32) #group_where_clause;
+            impl #group_impl_generics ::serde::Serialize for FauxGroup #group_type_generics #group_serialize_where_clause {
+                fn serialize<__S: ::serde::Serializer>(&self, serializer: __S) -> ::std::result::Result<__S::Ok,__S::Error> {
+                    use ::serde::ser::SerializeMap;
+                    match self.1 {
+                        #(#group_indices => { #group_bodies },)*
+                        _ => unreachable!(),
+                    }
+                }
+            }
+            impl #serialize_impl_generics ::serde::Serialize for #name #type_generics #serialize_where_clause {
+                fn serialize<__S: ::serde::Serializer>(&self, serializer: __S) -> ::std::result::Result<__S::Ok,__S::Error> {
+                    #serialize_body
+                }
+            }
+            struct FauxSlots #generics #where_clause {
+                #(#idents: ::std::option::Option<#tipe>,)*
+            }
+            struct __FauxGroupSeed #group_generics (&'faux_group mut FauxSlots #type_generics) #group_where_clause;
+            impl #seed_impl_generics ::serde::de::DeserializeSeed<'__de> for __FauxGroupSeed #group_type_generics #seed_where_clause {
+                type Value = ();
+                fn deserialize<__D: ::serde::Deserializer<'__de>>(self, deserializer: __D) -> ::std::result::Result<(),__D::Error> {
+                    deserializer.deserialize_map(self)
+                }
+            }
+            impl #seed_impl_generics ::serde::de::Visitor<'__de> for __FauxGroupSeed #group_type_generics #seed_where_clause {
+                type Value = ();
+                fn expecting(&self, formatter: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+                    formatter.write_str("a map of keys to slot values")
+                }
+                fn visit_map<__A: ::serde::de::MapAccess<'__de>>(self, mut map: __A) -> ::std::result::Result<(),__A::Error> {
+                    while let ::std::option::Option::Some(key) = map.next_key::<::std::string::String>()? {
+                        match key.as_str() {
+                            #(#names => {
+                                if self.0.#idents.is_some() {
+                                    return ::std::result::Result::Err(<__A::Error as ::serde::de::Error>::duplicate_field(#names));
+                                }
+                                self.0.#idents = ::std::option::Option::Some(map.next_value()?);
+                            },)*
+                            _ => {
+                                map.next_value::<::serde::de::IgnoredAny>()?;
+                            },
+                        }
+                    }
+                    ::std::result::Result::Ok(())
+                }
+            }
+            struct __FauxVisitor #generics (::std::marker::PhantomData<fn() -> #name #type_generics>) #where_clause;
+            impl #deserialize_impl_generics ::serde::de::Visitor<'__de> for __FauxVisitor #type_generics #deserialize_where_clause {
+                type Value = #name #type_generics;
+                fn expecting(&self, formatter: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+                    formatter.write_str(#expecting)
+                }
+                fn visit_map<__A: ::serde::de::MapAccess<'__de>>(self, mut map: __A) -> ::std::result::Result<Self::Value,__A::Error> {
+                    let mut slots = FauxSlots {
+                        #(#idents: ::std::option::Option::None,)*
+                    };
+                    while map.next_key::<::serde::de::IgnoredAny>()?.is_some() {
+                        map.next_value_seed(__FauxGroupSeed(&mut slots))?;
+                    }
+                    ::std::result::Result::Ok(#name {
+                        #(#idents: slots.#idents.ok_or_else(|| <__A::Error as ::serde::de::Error>::missing_field(#names))?,)*
+                        #(#extra_fields: ::std::default::Default::default(),)*
+                    })
+                }
+            }
+            impl #deserialize_impl_generics ::serde::Deserialize<'__de> for #name #type_generics #deserialize_where_clause {
+                fn deserialize<__D: ::serde::Deserializer<'__de>>(deserializer: __D) -> ::std::result::Result<Self,__D::Error> {
+                    #deserialize_body
+                }
+            }
+        };
+    }
+}
+/// Creates `chunk`, `chunk_mut`, and `chunks`, which view each group of slots written under one top-level key as a slice
+///
+/// Like the slice conversions, these rely on the slots being laid out exactly like an array by the `repr_c` option.
+pub(crate) fn generate_chunks(layout: &Layout, groups: u32) -> TokenStream {
+    let name = &layout.structure.ident;
+    let (impl_generics, type_generics, where_clause) = layout.structure.generics.split_for_impl();
+    let tipe = layout.field_type;
+    let count = layout.idents.len();
+    let group_count = groups as usize;
+    let bounds = (0..=group_count).map(|group| group * count / group_count);
+    let bound_count = group_count + 1;
+    if layout.no_panic {
+        return quote! {
+            impl #impl_generics #name #type_generics #where_clause {
+                /// The index of the first slot of each chunk, followed by the number of slots
+                const CHUNK_BOUNDS: [usize; #bound_count] = [#(#bounds),*];
+                /// Borrows the slots of the chunk at `chunk`, which are written under the top-level key of that index, or returns `None` if `chunk` is out of range
+                pub fn chunk(&self, chunk: u32) -> ::std::option::Option<&[#tipe]> {
+                    let start = *Self::CHUNK_BOUNDS.get(chunk as usize)?;
+                    let end = *Self::CHUNK_BOUNDS.get(chunk as usize + 1)?;
+                    ::std::convert::AsRef::<[#tipe]>::as_ref(self).get(start..end)
+                }
+                /// Mutably borrows the slots of the chunk at `chunk`, which are written under the top-level key of that index, or returns `None` if `chunk` is out of range
+                pub fn chunk_mut(&mut self, chunk: u32) -> ::std::option::Option<&mut [#tipe]> {
+                    let start = *Self::CHUNK_BOUNDS.get(chunk as usize)?;
+                    let end = *Self::CHUNK_BOUNDS.get(chunk as usize + 1)?;
+                    ::std::convert::AsMut::<[#tipe]>::as_mut(self).get_mut(start..end)
+                }
+                /// Iterates over the slots of every chunk, in order
+                pub fn chunks(&self) -> impl ::std::iter::DoubleEndedIterator<Item = &[#tipe]> + ::std::iter::ExactSizeIterator {
+                    (0..#groups).map(move |chunk| self.chunk(chunk).unwrap_or_default())
+                }
+            }
+        };
+    }
+    quote! {
+        impl #impl_generics #name #type_generics #where_clause {
+            /// The index of the first slot of each chunk, followed by the number of slots
+            const CHUNK_BOUNDS: [usize; #bound_count] = [#(#bounds),*];
+            /// Borrows the slots of the chunk at `chunk`, which are written under the top-level key of that index
+            ///
+            /// # Panics
+            /// Panics if `chunk` is out of range
+            pub fn chunk(&self, chunk: u32) -> &[#tipe] {
+                assert!((chunk as usize) < #group_count,"chunk {} is out of range for a psuedo-array with {} chunks",chunk,#group_count);
+                &::std::convert::AsRef::<[#tipe]>::as_ref(self)[Self::CHUNK_BOUNDS[chunk as usize]..Self::CHUNK_BOUNDS[chunk as usize + 1]]
+            }
+            /// Mutably borrows the slots of the chunk at `chunk`, which are written under the top-level key of that index
+            ///
+            /// # Panics
+            /// Panics if `chunk` is out of range
+            pub fn chunk_mut(&mut self, chunk: u32) -> &mut [#tipe] {
+                assert!((chunk as usize) < #group_count,"chunk {} is out of range for a psuedo-array with {} chunks",chunk,#group_count);
+                &mut ::std::convert::AsMut::<[#tipe]>::as_mut(self)[Self::CHUNK_BOUNDS[chunk as usize]..Self::CHUNK_BOUNDS[chunk as usize + 1]]
+            }
+            /// Iterates over the slots of every chunk, in order
+            pub fn chunks(&self) -> impl ::std::iter::DoubleEndedIterator<Item = &[#tipe]> + ::std::iter::ExactSizeIterator {
+                (0..#groups).map(move |chunk| self.chunk(chunk))
+            }
+        }
+    }
+}
@@ -0,0 +1,131 @@
+/*
+Copyright 2024 Benjamin Richcreek
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+//! Generation of heap-allocated builders that initialize one slot at a time
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::Ident;
+use crate::Layout;
+/// Creates the staging type named `staging`, which fills an uninitialized, heap-allocated instance slot by slot
+///
+/// The panicking `finish` is left out if the `no_panic` option was given, leaving `try_finish`.
+pub(crate) fn generate(layout: &Layout, staging: &Ident) -> TokenStream {
+    let name = &layout.structure.ident;
+    let visibility = &layout.structure.vis;
+    let generics = &layout.structure.generics;
+    let (impl_generics, type_generics, where_clause) = generics.split_for_impl();
+    let tipe = layout.field_type;
+    let idents = layout.idents;
+    let extra_fields = layout.extra_fields;
+    let count = idents.len();
+    let indices: Vec<u32> = (0..count as u32).collect();
+    let failure_doc = layout.checked_doc("`index` is out of range");
+    let output = layout.checked_output(quote! {});
+    let slot = layout.unwrap_index(quote! { slot });
+    let finished = layout.checked_return(quote! {});
+    let finish = if layout.no_panic {
+        quote! {}
+    } else {
+        quote! {
+            /// Returns the finished instance
+            ///
+            /// # Panics
+            /// Panics if any slot hasn't been initialized, listing the missing indices
+            pub fn finish(self) -> ::std::boxed::Box<#name #type_generics> {
+                match self.try_finish() {
+                    ::std::result::Result::Ok(finished) => finished,
+                    ::std::result::Result::Err(unfinished) => panic!("the slots at indices {:?} were never initialized",unfinished.missing()),
+                }
+            }
+        }
+    };
+    quote! {
+        /// An instance under construction, stored on the heap, whose slots are initialized one at a time
+        #visibility struct #staging #generics #where_clause {
+            value: ::std::boxed::Box<::std::mem::MaybeUninit<#name #type_generics>>,
+            initialized: ::std::vec::Vec<bool>,
+        }
+        impl #impl_generics #staging #type_generics #where_clause {
+            /// Allocates an instance without initializing any of its slots
+            pub fn uninit() -> Self {
+                let mut value = ::std::boxed::Box::<#name #type_generics>::new_uninit();
+                let pointer = value.as_mut_ptr();
+                // SAFETY: each pointer is created without a reference to uninitialized memory and points to a field of the allocation
+                unsafe {
+                    #(::std::ptr::addr_of_mut!((*pointer).#extra_fields).write(::std::default::Default::default());)*
+                }
+                #staging {
+                    value,
+                    initialized: ::std::vec![false; #count],
+                }
+            }
+            /// Initializes the slot at `index` with `value`, dropping the value it previously held if it was already initialized
+            #failure_doc
+            pub fn set(&mut self, index: u32, value: #tipe) #output {
+                let pointer = self.value.as_mut_ptr();
+                // SAFETY: each pointer is created without a reference to uninitialized memory and points to a field of the allocation
+                let slot = unsafe {
+                    match index {
+                        #(#indices => ::std::option::Option::Some(::std::ptr::addr_of_mut!((*pointer).#idents)),)*
+                        _ => ::std::option::Option::None,
+                    }
+                };
+                let slot = #slot;
+                let was_initialized = ::std::mem::replace(&mut self.initialized[index as usize],true);
+                // SAFETY: the slot pointer comes from the allocation, and it is only dropped if it was initialized by an earlier call
+                unsafe {
+                    if was_initialized {
+                        ::std::ptr::drop_in_place(slot);
+                    }
+                    slot.write(value);
+                }
+                #finished
+            }
+            /// Returns `true` once every slot has been initialized
+            pub fn is_complete(&self) -> bool {
+                self.initialized.iter().all(|initialized| *initialized)
+            }
+            /// Lists the indices of the slots that haven't been initialized yet
+            pub fn missing(&self) -> ::std::vec::Vec<u32> {
+                (0..#count as u32).filter(|index| !self.initialized[*index as usize]).collect()
+            }
+            /// Returns the finished instance if every slot has been initialized, or gives the staging area back otherwise
+            pub fn try_finish(self) -> ::std::result::Result<::std::boxed::Box<#name #type_generics>,Self> {
+                if !self.is_complete() {
+                    return ::std::result::Result::Err(self);
+                }
+                let staged = ::std::mem::ManuallyDrop::new(self);
+                // SAFETY: `staged` is never used or dropped again, so the box and the flags are each read exactly once, and every field was initialized
+                unsafe {
+                    ::std::mem::drop(::std::ptr::read(&staged.initialized));
+                    ::std::result::Result::Ok(::std::ptr::read(&staged.value).assume_init())
+                }
+            }
+            #finish
+        }
+        impl #impl_generics ::std::ops::Drop for #staging #type_generics #where_clause {
+            fn drop(&mut self) {
+                let pointer = self.value.as_mut_ptr();
+                // SAFETY: only fields that were initialized are dropped, and the allocation itself is freed by the box without dropping its contents
+                unsafe {
+                    #(::std::ptr::drop_in_place(::std::ptr::addr_of_mut!((*pointer).#extra_fields));)*
+                    #(if self.initialized[#indices as usize] {
+                        ::std::ptr::drop_in_place(::std::ptr::addr_of_mut!((*pointer).#idents));
+                    })*
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,57 @@
+/*
+Copyright 2024 Benjamin Richcreek
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+//! Generation of opt-in round-trip and key-ordering tests
+use proc_macro2::TokenStream;
+use quote::{quote,format_ident};
+use crate::Layout;
+/// Creates a `#[cfg(test)]` module testing the serialization of the psuedo-array described by `layout`
+///
+/// # Errors
+/// Returns an error if the [`struct`](https://doc.rust-lang.org/1.58.1/std/keyword.struct.html) is generic, since the tests need a concrete type to construct
+pub(crate) fn generate(layout: &Layout) -> Result<TokenStream,syn::Error> {
+    let name = &layout.structure.ident;
+    if !layout.structure.generics.params.is_empty() {
+        return Err(syn::Error::new_spanned(&layout.structure.generics,format!("the gen_tests option can't be used on {}, because tests can only be generated for structs without generic parameters",name)));
+    }
+    let module = format_ident!("{}_faux_array_tests",name);
+    let names = layout.names;
+    Ok(quote! {
+        #[cfg(test)]
+        #[allow(non_snake_case)]
+        mod #module {
+            use super::#name;
+            #[test]
+            fn round_trip() {
+                let original = <#name as ::std::default::Default>::default();
+                let written = ::serde_json::to_string(&original).expect("A default instance should serialize");
+                let read: #name = ::serde_json::from_str(&written).expect("A serialized instance should deserialize");
+                let rewritten = ::serde_json::to_string(&read).expect("A deserialized instance should serialize");
+                assert_eq!(written,rewritten);
+            }
+            #[test]
+            fn key_order() {
+                let written = ::serde_json::to_string(&<#name as ::std::default::Default>::default()).expect("A default instance should serialize");
+                let keys: &[&str] = &[#(#names),*];
+                let mut previous: usize = 0;
+                for key in keys {
+                    let quoted = ::std::format!("\"{}\":",key);
+                    let position = written[previous..].find(&quoted).unwrap_or_else(|| panic!("The key {} is missing or out of order in {}",key,written));
+                    previous += position + quoted.len();
+                }
+            }
+        }
+    })
+}
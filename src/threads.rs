@@ -0,0 +1,55 @@
+/*
+Copyright 2024 Benjamin Richcreek
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+//! Generation of compile-time assertions about thread safety
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::Ident;
+use crate::Layout;
+/// Creates a constant item that fails to compile unless the psuedo-array implements the auto trait `trait_name` if `implemented` is `true`, or doesn't implement it if `implemented` is `false`
+///
+/// The check for a missing implementation relies on an ambiguous trait resolution, which only compiles if the trait isn't implemented.
+pub(crate) fn generate(layout: &Layout, trait_name: &str, implemented: bool) -> TokenStream {
+    let name = &layout.structure.ident;
+    let (impl_generics, type_generics, where_clause) = layout.structure.generics.split_for_impl();
+    let auto = Ident::new(trait_name,proc_macro2::Span::call_site());
+    if implemented {
+        quote! {
+            const _: () = {
+                fn implemented<Checked: ?::std::marker::Sized + ::std::marker::#auto>() {}
+                #[allow(dead_code)]
+                fn check #impl_generics () #where_clause {
+                    implemented::<#name #type_generics>();
+                }
+            };
+        }
+    } else {
+        quote! {
+            const _: () = {
+                trait AmbiguousIfImplemented<Marker> {
+                    fn check() {}
+                }
+                impl<Checked: ?::std::marker::Sized> AmbiguousIfImplemented<()> for Checked {}
+                #[allow(dead_code)]
+                struct Invalid;
+                impl<Checked: ?::std::marker::Sized + ::std::marker::#auto> AmbiguousIfImplemented<Invalid> for Checked {}
+                #[allow(dead_code)]
+                fn check #impl_generics () #where_clause {
+                    <#name #type_generics as AmbiguousIfImplemented<_>>::check();
+                }
+            };
+        }
+    }
+}
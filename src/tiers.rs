@@ -0,0 +1,152 @@
+/*
+Copyright 2024 Benjamin Richcreek
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+//! Generation of hot and cold views that split the slots across two structs
+use proc_macro2::TokenStream;
+use quote::{quote,format_ident};
+use crate::Layout;
+/// Creates the hot and cold [`struct`](https://doc.rust-lang.org/1.58.1/std/keyword.struct.html)s, which hold the first `hot` slots and the rest respectively, along with the facade that joins them
+pub(crate) fn generate(layout: &Layout, hot: u32) -> TokenStream {
+    let name = &layout.structure.ident;
+    let attributes = &layout.structure.attrs;
+    let visibility = &layout.structure.vis;
+    let generics = &layout.structure.generics;
+    let (impl_generics, type_generics, where_clause) = generics.split_for_impl();
+    let hot_name = format_ident!("{}Hot",name);
+    let cold_name = format_ident!("{}Cold",name);
+    let facade = format_ident!("{}HotCold",name);
+    let tipe = layout.field_type;
+    let idents = layout.idents;
+    let extra_fields = layout.extra_fields;
+    let count = idents.len();
+    let split = hot as usize;
+    let out_of_range = layout.out_of_range();
+    let (hot_idents, cold_idents) = idents.split_at(split);
+    let (hot_attributes, cold_attributes) = layout.slot_attributes.split_at(split);
+    let hot_indices: Vec<u32> = (0..hot).collect();
+    let cold_indices: Vec<u32> = (hot..count as u32).collect();
+    let hot_doc = format!("The {} frequently updated slots of [`{}`], which keep the same keys", hot, name);
+    let cold_doc = format!("The {} rarely updated slots of [`{}`], which keep the same keys", count - split, name);
+    let facade_doc = format!("Joins [`{}`] and [`{}`], so every slot of [`{}`] can be reached by its index", hot_name, cold_name, name);
+    let accessors = if layout.no_panic {
+        quote! {
+            /// Borrows the slot at `index`, or returns `None` if `index` is out of range
+            pub fn value_at(&self, index: u32) -> ::std::option::Option<&#tipe> {
+                match index {
+                    #(#hot_indices => ::std::option::Option::Some(&self.hot.#hot_idents),)*
+                    #(#cold_indices => ::std::option::Option::Some(&self.cold.#cold_idents),)*
+                    _ => ::std::option::Option::None,
+                }
+            }
+            /// Mutably borrows the slot at `index`, or returns `None` if `index` is out of range
+            pub fn value_at_mut(&mut self, index: u32) -> ::std::option::Option<&mut #tipe> {
+                match index {
+                    #(#hot_indices => ::std::option::Option::Some(&mut self.hot.#hot_idents),)*
+                    #(#cold_indices => ::std::option::Option::Some(&mut self.cold.#cold_idents),)*
+                    _ => ::std::option::Option::None,
+                }
+            }
+            /// Replaces the value of the slot at `index`, returning the old value, or returns `None` without using `value` if `index` is out of range
+            pub fn set_value_at(&mut self, index: u32, value: #tipe) -> ::std::option::Option<#tipe> {
+                self.value_at_mut(index).map(|slot| ::std::mem::replace(slot,value))
+            }
+        }
+    } else {
+        quote! {
+            /// Borrows the slot at `index`
+            ///
+            /// # Panics
+            /// Panics if `index` is out of range
+            pub fn value_at(&self, index: u32) -> &#tipe {
+                match index {
+                    #(#hot_indices => &self.hot.#hot_idents,)*
+                    #(#cold_indices => &self.cold.#cold_idents,)*
+                    _ => #out_of_range,
+                }
+            }
+            /// Mutably borrows the slot at `index`
+            ///
+            /// # Panics
+            /// Panics if `index` is out of range
+            pub fn value_at_mut(&mut self, index: u32) -> &mut #tipe {
+                match index {
+                    #(#hot_indices => &mut self.hot.#hot_idents,)*
+                    #(#cold_indices => &mut self.cold.#cold_idents,)*
+                    _ => #out_of_range,
+                }
+            }
+            /// Replaces the value of the slot at `index`, returning the old value
+            ///
+            /// # Panics
+            /// Panics if `index` is out of range
+            pub fn set_value_at(&mut self, index: u32, value: #tipe) -> #tipe {
+                ::std::mem::replace(self.value_at_mut(index),value)
+            }
+        }
+    };
+    quote! {
+        #[doc = #hot_doc]
+        #(#attributes)*
+        #[allow(non_camel_case_types)]
+        #visibility struct #hot_name #generics #where_clause {
+            #(#hot_attributes pub #hot_idents : #tipe),*
+        }
+        #[doc = #cold_doc]
+        #(#attributes)*
+        #[allow(non_camel_case_types)]
+        #visibility struct #cold_name #generics #where_clause {
+            #(#cold_attributes pub #cold_idents : #tipe),*
+        }
+        #[doc = #facade_doc]
+        #visibility struct #facade #generics #where_clause {
+            /// The frequently updated slots
+            pub hot: #hot_name #type_generics,
+            /// The rarely updated slots
+            pub cold: #cold_name #type_generics,
+        }
+        impl #impl_generics #facade #type_generics #where_clause {
+            /// The number of slots kept in the hot [`struct`](https://doc.rust-lang.org/1.58.1/std/keyword.struct.html), which are the slots with the lowest indices
+            pub const HOT_SLOTS: u32 = #hot;
+            /// Returns `true` if the slot at `index` is kept in the hot [`struct`](https://doc.rust-lang.org/1.58.1/std/keyword.struct.html)
+            pub const fn is_hot(index: u32) -> bool {
+                index < #hot
+            }
+            #accessors
+        }
+        impl #impl_generics ::std::convert::From<#name #type_generics> for #facade #type_generics #where_clause {
+            /// Moves each slot of `value` into the hot or cold [`struct`](https://doc.rust-lang.org/1.58.1/std/keyword.struct.html)
+            fn from(value: #name #type_generics) -> Self {
+                #facade {
+                    hot: #hot_name {
+                        #(#hot_idents: value.#hot_idents,)*
+                    },
+                    cold: #cold_name {
+                        #(#cold_idents: value.#cold_idents,)*
+                    },
+                }
+            }
+        }
+        impl #impl_generics ::std::convert::From<#facade #type_generics> for #name #type_generics #where_clause {
+            /// Moves the slots of both halves back into one [`struct`](https://doc.rust-lang.org/1.58.1/std/keyword.struct.html)
+            fn from(value: #facade #type_generics) -> Self {
+                #name {
+                    #(#hot_idents: value.hot.#hot_idents,)*
+                    #(#cold_idents: value.cold.#cold_idents,)*
+                    #(#extra_fields: ::std::default::Default::default(),)*
+                }
+            }
+        }
+    }
+}
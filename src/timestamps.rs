@@ -0,0 +1,78 @@
+/*
+Copyright 2024 Benjamin Richcreek
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+//! Generation of per-slot update timestamps
+use proc_macro2::TokenStream;
+use quote::{quote,format_ident};
+use syn::Ident;
+use crate::Layout;
+/// Names the timestamp field of each slot by appending `_t` to the slot's identifier
+pub(crate) fn idents(slots: &[Ident]) -> Vec<Ident> {
+    slots.iter().map(|slot| format_ident!("{}_t",slot)).collect()
+}
+/// Creates the `touch`, `touch_at`, and `touched_at` methods
+pub(crate) fn generate(layout: &Layout) -> TokenStream {
+    let name = &layout.structure.ident;
+    let (impl_generics, type_generics, where_clause) = layout.structure.generics.split_for_impl();
+    let stamps = idents(layout.idents);
+    let indices = 0..layout.idents.len() as u32;
+    let second_indices = indices.clone();
+    let stamp_mut = layout.unwrap_index(quote! {
+        match index {
+            #(#indices => ::std::option::Option::Some(&mut self.#stamps),)*
+            _ => ::std::option::Option::None,
+        }
+    });
+    let stamp = layout.unwrap_index(quote! {
+        match index {
+            #(#second_indices => ::std::option::Option::Some(self.#stamps),)*
+            _ => ::std::option::Option::None,
+        }
+    });
+    let now = if layout.no_panic {
+        quote! { ::std::time::SystemTime::now().duration_since(::std::time::UNIX_EPOCH).ok()? }
+    } else {
+        quote! { ::std::time::SystemTime::now().duration_since(::std::time::UNIX_EPOCH).expect("The system clock should be set after the Unix epoch") }
+    };
+    let unit = layout.checked_output(quote! {});
+    let millis = layout.checked_output(quote!(u64));
+    let touched = layout.checked_return(quote! {});
+    let touched_at = layout.checked_return(quote!(stamp));
+    let touch_doc = layout.checked_doc("`index` is out of range or the system clock is set before the Unix epoch");
+    let index_doc = layout.checked_doc("`index` is out of range");
+    quote! {
+        impl #impl_generics #name #type_generics #where_clause {
+            /// Records the current time as the moment the slot at `index` was last updated
+            #touch_doc
+            pub fn touch(&mut self, index: u32) #unit {
+                let now = #now;
+                self.touch_at(index,now.as_millis() as u64)
+            }
+            /// Records `millis`, in milliseconds since the Unix epoch, as the moment the slot at `index` was last updated
+            #index_doc
+            pub fn touch_at(&mut self, index: u32, millis: u64) #unit {
+                let stamp = #stamp_mut;
+                *stamp = millis;
+                #touched
+            }
+            /// Returns when the slot at `index` was last updated, in milliseconds since the Unix epoch
+            #index_doc
+            pub fn touched_at(&self, index: u32) #millis {
+                let stamp = #stamp;
+                #touched_at
+            }
+        }
+    }
+}
@@ -0,0 +1,79 @@
+/*
+Copyright 2024 Benjamin Richcreek
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+//! Generation of zero-sized key tokens for typed queries
+use proc_macro2::TokenStream;
+use quote::{quote,format_ident};
+use syn::{Visibility,VisRestricted};
+use crate::Layout;
+/// The number of tokens declared in each module, so that large psuedo-arrays don't put thousands of types in one namespace
+const CHUNK_SIZE: usize = 256;
+/// Adjusts `visibility`, written for an item beside the [`struct`](https://doc.rust-lang.org/1.58.1/std/keyword.struct.html), for an item `depth` modules below it
+fn nested(visibility: &Visibility, depth: usize) -> TokenStream {
+    let supers = std::iter::repeat_n(quote!(super),depth);
+    match visibility {
+        Visibility::Public(_) => quote!(pub),
+        Visibility::Restricted(VisRestricted { path, .. }) if path.is_ident("crate") || path.leading_colon.is_some() || path.segments.first().is_some_and(|segment| segment.ident == "crate") => quote!(#visibility),
+        Visibility::Restricted(VisRestricted { path, .. }) if path.is_ident("self") => quote!(pub(in #(#supers)::*)),
+        Visibility::Restricted(VisRestricted { path, .. }) => quote!(pub(in #(#supers::)* #path)),
+        Visibility::Inherited => quote!(pub(in #(#supers)::*)),
+    }
+}
+/// Creates a module, named after the [`struct`](https://doc.rust-lang.org/1.58.1/std/keyword.struct.html) with `_keys` appended, holding a `KeyToken` implementor for every slot
+///
+/// The tokens are split into submodules of [`CHUNK_SIZE`] tokens each, which are all re-exported from the outer module.
+/// # Errors
+/// Returns an error if the [`struct`](https://doc.rust-lang.org/1.58.1/std/keyword.struct.html) is generic, since the tokens are unit structs and can't name a generic owner
+pub(crate) fn generate(layout: &Layout) -> Result<TokenStream,syn::Error> {
+    let name = &layout.structure.ident;
+    if !layout.structure.generics.params.is_empty() {
+        return Err(syn::Error::new_spanned(&layout.structure.generics,format!("the key_tokens option can't be used on {}, because key tokens can only be generated for structs without generic parameters",name)));
+    }
+    let visibility = &layout.structure.vis;
+    let module = format_ident!("{}_keys",name);
+    let documentation = format!("Zero-sized key tokens for each slot of [`{}`]",name);
+    let reexport_visibility = nested(visibility,1);
+    let token_visibility = nested(visibility,2);
+    let chunks = layout.idents.chunks(CHUNK_SIZE).zip(layout.names.chunks(CHUNK_SIZE)).enumerate().map(|(number, (idents, names))| {
+        let chunk = format_ident!("chunk_{}",number);
+        let first = number * CHUNK_SIZE;
+        let indices = (first..first + idents.len()).map(|index| index as u32);
+        let chunk_documentation = format!("The key tokens of slots {} through {}",first,first + idents.len() - 1);
+        let token_documentation = names.iter().map(|key| format!("The key `{}`",key));
+        quote! {
+            #[doc = #chunk_documentation]
+            #reexport_visibility mod #chunk {
+                #(
+                    #[doc = #token_documentation]
+                    #[derive(Clone,Copy,Debug,Default,PartialEq,Eq,PartialOrd,Ord,Hash)]
+                    #token_visibility struct #idents;
+                    impl ::structurray_keys::KeyToken for #idents {
+                        type Owner = super::super::#name;
+                        const KEY: &'static str = #names;
+                        const INDEX: u32 = #indices;
+                    }
+                )*
+            }
+            #reexport_visibility use self::#chunk::*;
+        }
+    });
+    Ok(quote! {
+        #[doc = #documentation]
+        #[allow(non_snake_case,non_camel_case_types)]
+        #visibility mod #module {
+            #(#chunks)*
+        }
+    })
+}
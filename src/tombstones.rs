@@ -0,0 +1,134 @@
+/*
+Copyright 2024 Benjamin Richcreek
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+//! Generation of the soft-deletion bitset
+use proc_macro2::{TokenStream,Span};
+use quote::{quote,format_ident};
+use syn::Ident;
+use crate::{Layout,base64};
+/// The key the bitset is serialized under. Like the checksum key, key schemes that would give a slot this key are rejected when the attribute is expanded.
+pub(crate) const TOMBSTONE_KEY: &str = "deleted";
+/// Names the field holding the bitset
+pub(crate) fn ident() -> Ident {
+    Ident::new("_deleted",Span::call_site())
+}
+/// Names the bitset type generated for the [`struct`](https://doc.rust-lang.org/1.58.1/std/keyword.struct.html) named `name`
+pub(crate) fn type_name(name: &Ident) -> Ident {
+    format_ident!("{}Tombstones",name)
+}
+/// Creates the bitset type, its base64 serialization, and the `mark_deleted`, `restore`, and `is_deleted` methods
+pub(crate) fn generate(layout: &Layout) -> TokenStream {
+    let name = &layout.structure.ident;
+    let visibility = &layout.structure.vis;
+    let (impl_generics, type_generics, where_clause) = layout.structure.generics.split_for_impl();
+    let tombstones = type_name(name);
+    let field = ident();
+    let count = layout.idents.len();
+    let bytes = count.div_ceil(8);
+    let check_index = layout.check_index();
+    let expecting = format!("a base64 string holding {} bytes",bytes);
+    let encode = base64::encode(quote!(&self.0));
+    let decode = base64::decode(quote!(encoded));
+    let methods = if layout.no_panic {
+        quote! {
+            /// Marks the slot at `index` as logically deleted without changing its value, or returns `None` if `index` is out of range
+            pub fn mark_deleted(&mut self, index: u32) -> ::std::option::Option<()> {
+                if index as usize >= #count {
+                    return ::std::option::Option::None;
+                }
+                *self.#field.0.get_mut(index as usize / 8)? |= 1 << (index % 8);
+                ::std::option::Option::Some(())
+            }
+            /// Clears the deletion mark of the slot at `index`, or returns `None` if `index` is out of range
+            pub fn restore(&mut self, index: u32) -> ::std::option::Option<()> {
+                if index as usize >= #count {
+                    return ::std::option::Option::None;
+                }
+                *self.#field.0.get_mut(index as usize / 8)? &= !(1 << (index % 8));
+                ::std::option::Option::Some(())
+            }
+            /// Returns whether the slot at `index` has been marked as deleted, or `None` if `index` is out of range
+            pub fn is_deleted(&self, index: u32) -> ::std::option::Option<bool> {
+                if index as usize >= #count {
+                    return ::std::option::Option::None;
+                }
+                ::std::option::Option::Some(self.#field.0.get(index as usize / 8)? & (1 << (index % 8)) != 0)
+            }
+        }
+    } else {
+        quote! {
+            /// Marks the slot at `index` as logically deleted without changing its value
+            ///
+            /// # Panics
+            /// Panics if `index` is out of range
+            pub fn mark_deleted(&mut self, index: u32) {
+                #check_index
+                self.#field.0[index as usize / 8] |= 1 << (index % 8);
+            }
+            /// Clears the deletion mark of the slot at `index`
+            ///
+            /// # Panics
+            /// Panics if `index` is out of range
+            pub fn restore(&mut self, index: u32) {
+                #check_index
+                self.#field.0[index as usize / 8] &= !(1 << (index % 8));
+            }
+            /// Returns `true` if the slot at `index` has been marked as deleted
+            ///
+            /// # Panics
+            /// Panics if `index` is out of range
+            pub fn is_deleted(&self, index: u32) -> bool {
+                #check_index
+                self.#field.0[index as usize / 8] & (1 << (index % 8)) != 0
+            }
+        }
+    };
+    quote! {
+        /// A bitset with one bit per slot, serialized as a single base64 string
+        #[derive(Clone,Copy,PartialEq,Eq,Hash,Debug)]
+        #visibility struct #tombstones([u8; #bytes]);
+        impl ::std::default::Default for #tombstones {
+            fn default() -> Self {
+                #tombstones([0; #bytes])
+            }
+        }
+        impl #tombstones {
+            /// Encodes the bitset as padded base64
+            pub fn to_base64(&self) -> ::std::string::String {
+                #encode
+            }
+            /// Decodes a bitset from padded base64, returning [`None`] if `encoded` isn't valid base64 or holds the wrong number of bytes
+            pub fn from_base64(encoded: &str) -> ::std::option::Option<Self> {
+                let decoded = #decode?;
+                let bits: [u8; #bytes] = decoded.try_into().ok()?;
+                ::std::option::Option::Some(#tombstones(bits))
+            }
+        }
+        impl ::serde::Serialize for #tombstones {
+            fn serialize<__S: ::serde::Serializer>(&self, serializer: __S) -> ::std::result::Result<__S::Ok,__S::Error> {
+                serializer.serialize_str(&self.to_base64())
+            }
+        }
+        impl<'__de> ::serde::Deserialize<'__de> for #tombstones {
+            fn deserialize<__D: ::serde::Deserializer<'__de>>(deserializer: __D) -> ::std::result::Result<Self,__D::Error> {
+                let encoded: ::std::string::String = ::serde::Deserialize::deserialize(deserializer)?;
+                #tombstones::from_base64(&encoded).ok_or_else(|| <__D::Error as ::serde::de::Error>::invalid_value(::serde::de::Unexpected::Str(&encoded),&#expecting))
+            }
+        }
+        impl #impl_generics #name #type_generics #where_clause {
+            #methods
+        }
+    }
+}
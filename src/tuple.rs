@@ -0,0 +1,43 @@
+/*
+Copyright 2024 Benjamin Richcreek
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+//! Generation of conversions from tuples holding one value per slot
+use proc_macro2::TokenStream;
+use quote::quote;
+use crate::Layout;
+/// The largest number of slots converted from a tuple when the `tuple_from` option isn't given a limit
+pub(crate) const DEFAULT_LIMIT: u32 = 16;
+/// Creates an implementation of [`From`] for a tuple with one element for each slot
+pub(crate) fn generate(layout: &Layout) -> TokenStream {
+    let name = &layout.structure.ident;
+    let (impl_generics, type_generics, where_clause) = layout.structure.generics.split_for_impl();
+    let tipe = layout.field_type;
+    let idents = layout.idents;
+    let extra_fields = layout.extra_fields;
+    let tipes = vec![tipe; idents.len()];
+    let tuple = quote! { (#(#tipes,)*) };
+    quote! {
+        impl #impl_generics ::std::convert::From<#tuple> for #name #type_generics #where_clause {
+            /// Puts each element of `tuple` in the slot with the same index
+            fn from(tuple: #tuple) -> Self {
+                let (#(#idents,)*) = tuple;
+                #name {
+                    #(#idents,)*
+                    #(#extra_fields: ::std::default::Default::default(),)*
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,59 @@
+/*
+Copyright 2024 Benjamin Richcreek
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+//! Handling of tuple structs, whose slots are unnamed fields
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Fields,Ident,Index,ItemStruct,parse_quote};
+use crate::Layout;
+/// The options that don't depend on the slots having names
+const SUPPORTED: [&str; 20] = ["allow","alphabet","assert_send","assert_sync","bound","capnp","container_attrs","encoding","field_names","field_vis","key_digest","key_enum","key_scheme","key_table","len","namespace","pad","reserved_keys","serde","start"];
+/// Returns whether `structure` is written as a tuple struct without fields, like `struct Positions();`, so that its slots should be unnamed fields
+pub(crate) fn requested(structure: &ItemStruct) -> bool {
+    matches!(&structure.fields,Fields::Unnamed(unnamed) if unnamed.unnamed.is_empty())
+}
+/// Rejects the first of `options` that relies on the slots having names
+///
+/// # Errors
+/// Returns an error pointing at the first option that isn't supported by tuple structs
+pub(crate) fn check(options: &[Ident]) -> Result<(),syn::Error> {
+    match options.iter().find(|option| !SUPPORTED.contains(&option.to_string().as_str())) {
+        Some(option) => Err(syn::Error::new(option.span(),format!("the `{}` option relies on the slots having names, so it can't be used on a tuple struct",option))),
+        None => Ok(()),
+    }
+}
+/// Creates a [`Serialize`](https://docs.rs/serde/latest/serde/trait.Serialize.html) implementation that writes each slot under its key, just like the slots of a struct with named fields
+pub(crate) fn generate(layout: &Layout) -> TokenStream {
+    let name = &layout.structure.ident;
+    let generics = &layout.structure.generics;
+    let (_, type_generics, _) = generics.split_for_impl();
+    let tipe = layout.field_type;
+    let serializable = layout.with_serde_bound(generics,parse_quote!(#tipe: ::serde::Serialize));
+    let (impl_generics, _, where_clause) = serializable.split_for_impl();
+    let names = layout.names;
+    let positions = (0..names.len()).map(Index::from);
+    let count = names.len();
+    let label = name.to_string();
+    quote! {
+        impl #impl_generics ::serde::Serialize for #name #type_generics #where_clause {
+            fn serialize<__S: ::serde::Serializer>(&self, serializer: __S) -> ::std::result::Result<__S::Ok,__S::Error> {
+                use ::serde::ser::SerializeStruct;
+                let mut state = serializer.serialize_struct(#label,#count)?;
+                #(state.serialize_field(#names,&self.#positions)?;)*
+                state.end()
+            }
+        }
+    }
+}
@@ -0,0 +1,46 @@
+/*
+Copyright 2024 Benjamin Richcreek
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+//! Generation of validation that runs a function on every slot
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Path,Type};
+use crate::Layout;
+/// Creates the `validate` method, which passes every slot to `function` and collects each [`Err`] it returns as an `error` along with the slot's index
+pub(crate) fn generate(layout: &Layout, function: &Path, error: &Type) -> TokenStream {
+    let name = &layout.structure.ident;
+    let (impl_generics, type_generics, where_clause) = layout.structure.generics.split_for_impl();
+    let idents = layout.idents;
+    let indices = 0..idents.len() as u32;
+    quote! {
+        impl #impl_generics #name #type_generics #where_clause {
+            /// Validates every slot, in index order
+            ///
+            /// # Errors
+            /// Returns the index and error of every slot that failed validation
+            pub fn validate(&self) -> ::std::result::Result<(),::std::vec::Vec<(u32,#error)>> {
+                let mut failures: ::std::vec::Vec<(u32,#error)> = ::std::vec::Vec::new();
+                #(if let ::std::result::Result::Err(error) = #function(&self.#idents) {
+                    failures.push((#indices,error));
+                })*
+                if failures.is_empty() {
+                    ::std::result::Result::Ok(())
+                } else {
+                    ::std::result::Result::Err(failures)
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,69 @@
+/*
+Copyright 2024 Benjamin Richcreek
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+//! Generation of a visitor-based [`Deserialize`](https://docs.rs/serde/latest/serde/trait.Deserialize.html) implementation
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::parse_quote;
+use crate::{Layout,with_lifetime,deserialize};
+/// Creates a [`Deserialize`](https://docs.rs/serde/latest/serde/trait.Deserialize.html) implementation that reads every slot from a map, accepting the aliases of each key
+pub(crate) fn generate(layout: &Layout) -> TokenStream {
+    let name = &layout.structure.ident;
+    let generics = &layout.structure.generics;
+    let (_, type_generics, where_clause) = generics.split_for_impl();
+    let tipe = layout.field_type;
+    let deserializable = layout.with_serde_bound(&deserialize::with_carried_bound(layout,&with_lifetime(generics,"'__de"),&quote!(::serde::Deserialize<'__de>)),parse_quote!(#tipe: ::serde::Deserialize<'__de>));
+    let (deserialize_impl_generics, _, deserialize_where_clause) = deserializable.split_for_impl();
+    let locals = deserialize::slot_locals(layout);
+    let carried_locals = deserialize::carried_locals(layout);
+    let carried_arms = deserialize::carried_arms(layout);
+    let slot_lookup = deserialize::slot_lookup(layout,&quote! {});
+    let slot_arms = deserialize::slot_arms(layout,&quote! {});
+    let ignore_arm = deserialize::ignore_arm();
+    let assembled = deserialize::assemble(layout);
+    let expecting = format!("a map containing every key of {}",name);
+    let deserialize_body = layout.instrument("deserialize",quote! { ::std::result::Result<Self,__D::Error> },quote! {
+        deserializer.deserialize_map(__FauxVisitor(::std::marker::PhantomData))
+    });
+    quote! {
+        const _: () = {
+            struct __FauxVisitor #generics (::std::marker::PhantomData<fn() -> #name #type_generics>) #where_clause;
+            impl #deserialize_impl_generics ::serde::de::Visitor<'__de> for __FauxVisitor #type_generics #deserialize_where_clause {
+                type Value = #name #type_generics;
+                fn expecting(&self, formatter: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+                    formatter.write_str(#expecting)
+                }
+                fn visit_map<__A: ::serde::de::MapAccess<'__de>>(self, mut map: __A) -> ::std::result::Result<Self::Value,__A::Error> {
+                    #locals
+                    #carried_locals
+                    while let ::std::option::Option::Some(key) = map.next_key::<::std::string::String>()? {
+                        #slot_lookup
+                        match key.as_str() {
+                            #slot_arms
+                            #carried_arms
+                            #ignore_arm
+                        }
+                    }
+                    ::std::result::Result::Ok(#assembled)
+                }
+            }
+            impl #deserialize_impl_generics ::serde::Deserialize<'__de> for #name #type_generics #deserialize_where_clause {
+                fn deserialize<__D: ::serde::Deserializer<'__de>>(deserializer: __D) -> ::std::result::Result<Self,__D::Error> {
+                    #deserialize_body
+                }
+            }
+        };
+    }
+}
@@ -0,0 +1,33 @@
+/*
+Copyright 2024 Benjamin Richcreek
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+#![cfg(feature = "serde")]
+use structurray::faux_array;
+use serde::Serialize;
+#[faux_array(u8,3,canonical_json)]
+#[derive(Serialize,Default)]
+struct Snapshot {}
+#[faux_array(u8,3,canonical_json,no_panic)]
+#[derive(Serialize,Default)]
+struct CheckedSnapshot {}
+#[test]
+fn sorts_keys() {
+    let snapshot = Snapshot { _0: 1, _1: 2, _2: 3 };
+    assert_eq!(snapshot.to_canonical_json(),"{\n  \"0\": 1,\n  \"1\": 2,\n  \"2\": 3\n}");
+}
+#[test]
+fn returns_result_without_panics() {
+    assert_eq!(CheckedSnapshot::default().to_canonical_json().unwrap(),"{\n  \"0\": 0,\n  \"1\": 0,\n  \"2\": 0\n}");
+}
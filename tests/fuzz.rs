@@ -0,0 +1,32 @@
+/*
+Copyright 2024 Benjamin Richcreek
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+//! The round trip only exists under `cfg(fuzzing)`, so it is exercised with `RUSTFLAGS="--cfg fuzzing" cargo test --test fuzz`; otherwise this only checks that the option expands
+#![cfg(feature = "serde")]
+use structurray::faux_array;
+use serde::{Serialize,Deserialize};
+#[faux_array(u8,3,fuzz)]
+#[derive(Serialize,Deserialize)]
+struct Scores {}
+#[faux_array(u8,3,fuzz,no_panic)]
+#[derive(Serialize,Deserialize)]
+struct CheckedScores {}
+#[cfg(fuzzing)]
+#[test]
+fn round_trips() {
+    Scores::fuzz_round_trip(br#"{"0":1,"1":2,"2":3}"#);
+    Scores::fuzz_round_trip(b"not json");
+    assert!(CheckedScores::fuzz_round_trip(br#"{"0":1,"1":2,"2":3}"#));
+}
@@ -0,0 +1,26 @@
+/*
+Copyright 2024 Benjamin Richcreek
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+//! Integration tests are compiled with `cfg(test)`, so the tests generated here run alongside the ones written by hand
+#![cfg(feature = "serde")]
+use structurray::faux_array;
+use serde::{Serialize,Deserialize};
+#[faux_array(u64,40,gen_tests)]
+#[derive(Serialize,Deserialize,Default)]
+struct Counters {}
+#[test]
+fn generates_module() {
+    let _ = Counters::default();
+}
@@ -0,0 +1,35 @@
+/*
+Copyright 2024 Benjamin Richcreek
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+#![cfg(feature = "serde")]
+use structurray::faux_array;
+use serde::Serialize;
+#[faux_array(u32,12,index_map)]
+#[derive(Serialize,Default)]
+struct Ordered {}
+#[test]
+fn keys_in_index_order() {
+    let ordered = Ordered { _a: 7, ..Ordered::default() };
+    let map = ordered.to_index_map();
+    assert_eq!(map.get_index(10),Some((&"a",&7)));
+    assert_eq!(map.keys().copied().collect::<Vec<_>>(),["0","1","2","3","4","5","6","7","8","9","a","b"]);
+    assert_eq!(Ordered::from_index_map(map).map(|ordered| ordered._a),Some(7));
+}
+#[test]
+fn missing_key() {
+    let mut map = Ordered::default().to_index_map();
+    map.shift_remove("b");
+    assert!(Ordered::from_index_map(map).is_none());
+}
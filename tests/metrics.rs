@@ -0,0 +1,39 @@
+/*
+Copyright 2024 Benjamin Richcreek
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+#![cfg(feature = "serde")]
+use std::cell::RefCell;
+use std::time::Duration;
+use structurray::faux_array;
+use serde::Serialize;
+thread_local! {
+    static RECORDED: RefCell<Vec<(String,String,usize,bool)>> = const { RefCell::new(Vec::new()) };
+}
+fn record(name: &str, operation: &str, slots: usize, _elapsed: Duration, succeeded: bool) {
+    RECORDED.with(|recorded| recorded.borrow_mut().push((name.to_owned(),operation.to_owned(),slots,succeeded)));
+}
+#[faux_array(u32,3,deserialize,metrics = "record")]
+#[derive(Serialize)]
+struct Observed {}
+#[test]
+fn records_deserialization() {
+    let observed: Observed = serde_json::from_str(r#"{"0":1,"1":2,"2":3}"#).unwrap();
+    assert_eq!(observed._2,3);
+    assert!(serde_json::from_str::<Observed>(r#"{"0":"one"}"#).is_err());
+    RECORDED.with(|recorded| assert_eq!(*recorded.borrow(),[
+        (String::from("Observed"),String::from("deserialize"),3,true),
+        (String::from("Observed"),String::from("deserialize"),3,false),
+    ]));
+}
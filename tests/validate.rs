@@ -0,0 +1,30 @@
+/*
+Copyright 2024 Benjamin Richcreek
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+#![cfg(feature = "serde")]
+use structurray::faux_array;
+use serde::Serialize;
+use validator::Validate;
+#[faux_array(String,3,validate(length(min = 1, max = 4)))]
+#[derive(Serialize,Validate)]
+struct Names {}
+#[test]
+fn checks_every_slot() {
+    let mut names = Names { _0: String::from("a"), _1: String::from("bc"), _2: String::from("def") };
+    assert!(names.validate().is_ok());
+    names._2 = String::from("ghijk");
+    let errors = names.validate().unwrap_err();
+    assert_eq!(errors.field_errors().len(),1);
+}